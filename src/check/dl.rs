@@ -0,0 +1,527 @@
+//! Checks of the global restrictions of OWL2 DL.
+//!
+//! The [`DlChecker`] verifies the restrictions of
+//! [section 11](https://www.w3.org/TR/owl2-syntax/#Global_Restrictions_on_Axioms_in_OWL_2_DL)
+//! of the OWL2 structural specification on the visited axioms:
+//!
+//! - only *simple* object properties are used in object cardinality
+//!   restrictions, `ObjectHasSelf`, `FunctionalObjectProperty`,
+//!   `InverseFunctionalObjectProperty`, `IrreflexiveObjectProperty`,
+//!   `AsymmetricObjectProperty` and `DisjointObjectProperties`, where a
+//!   property is simple if none of its subproperties is the superproperty
+//!   of a property chain or is transitive;
+//! - the object property hierarchy is *regular*, i.e. property chains do
+//!   not lead to cyclic definitions of object properties, except for the
+//!   chains implying `owl:topObjectProperty`, which are exempt;
+//! - the object property assertions between anonymous individuals form a
+//!   forest.
+//!
+//! # Example
+//!
+//! ```rust
+//! # extern crate horned_owl;
+//! # extern crate horned_visit;
+//! use horned_owl::model::*;
+//! use horned_owl::ontology::set::SetOntology;
+//!
+//! let b = Build::new();
+//! let mut ontology = SetOntology::new();
+//! ontology.insert(TransitiveObjectProperty(
+//!     b.object_property("http://example.com/part_of").into(),
+//! ));
+//! ontology.insert(FunctionalObjectProperty(
+//!     b.object_property("http://example.com/part_of").into(),
+//! ));
+//!
+//! let report = horned_visit::check::dl::check(&ontology);
+//! assert!(!report.is_dl());
+//! assert_eq!(report.violations().len(), 1);
+//! ```
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+
+use horned_owl::model::*;
+
+use crate::display::Functional;
+use crate::hierarchy::Hierarchy;
+use crate::visit;
+use crate::Visit;
+use crate::TOP_OBJECT_PROPERTY;
+
+/// An object property expression, as a property and an inversion flag.
+type Property = (ObjectProperty, bool);
+
+fn property(ope: &ObjectPropertyExpression) -> Property {
+    match ope {
+        ObjectPropertyExpression::ObjectProperty(op) => (op.clone(), false),
+        ObjectPropertyExpression::InverseObjectProperty(op) => (op.clone(), true),
+    }
+}
+
+fn name(ope: &ObjectPropertyExpression) -> &ObjectProperty {
+    match ope {
+        ObjectPropertyExpression::ObjectProperty(op) => op,
+        ObjectPropertyExpression::InverseObjectProperty(op) => op,
+    }
+}
+
+fn inverse((op, inverse): &Property) -> Property {
+    (op.clone(), !inverse)
+}
+
+/// A violation of the global restrictions of OWL2 DL.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Violation {
+    /// A non-simple object property is used where a simple one is required.
+    NonSimpleProperty {
+        property: ObjectPropertyExpression,
+        axiom: Axiom,
+    },
+    /// A property chain makes the object property hierarchy irregular.
+    IrregularPropertyChain(Axiom),
+    /// An object property assertion closes a cycle of anonymous individuals.
+    AnonymousIndividualCycle(Axiom),
+}
+
+impl Violation {
+    /// Get the offending axiom.
+    pub fn axiom(&self) -> &Axiom {
+        use self::Violation::*;
+        match self {
+            NonSimpleProperty { axiom, .. } => axiom,
+            IrregularPropertyChain(axiom) => axiom,
+            AnonymousIndividualCycle(axiom) => axiom,
+        }
+    }
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        use self::Violation::*;
+        match self {
+            NonSimpleProperty { property, axiom } => write!(
+                f,
+                "non-simple property {} used in: {}",
+                Functional(property),
+                Functional(axiom)
+            ),
+            IrregularPropertyChain(axiom) => {
+                write!(f, "irregular property chain: {}", Functional(axiom))
+            }
+            AnonymousIndividualCycle(axiom) => {
+                write!(f, "cycle of anonymous individuals: {}", Functional(axiom))
+            }
+        }
+    }
+}
+
+/// The violations of the global restrictions of OWL2 DL in an ontology.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DlReport {
+    violations: Vec<Violation>,
+}
+
+impl DlReport {
+    /// Get all the violations found in the ontology.
+    pub fn violations(&self) -> &[Violation] {
+        &self.violations
+    }
+
+    /// Check whether the ontology satisfies the global restrictions.
+    pub fn is_dl(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+impl Display for DlReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        for violation in self.violations.iter() {
+            writeln!(f, "{}", violation)?;
+        }
+        Ok(())
+    }
+}
+
+/// A visitor checking the global restrictions of OWL2 DL.
+///
+/// The restrictions concern the whole set of axioms of an ontology, so the
+/// violations are only computed once all axioms have been visited, by
+/// [`DlChecker::into_report`].
+#[derive(Debug, Default)]
+pub struct DlChecker<'ast> {
+    axiom: Option<&'ast Axiom>,
+    relation: Hierarchy<Property>,
+    composite: BTreeSet<&'ast ObjectProperty>,
+    simple: Vec<(&'ast Axiom, &'ast ObjectPropertyExpression)>,
+    chains: Vec<(
+        &'ast Axiom,
+        &'ast [ObjectPropertyExpression],
+        &'ast ObjectPropertyExpression,
+    )>,
+    anonymous: Vec<(
+        &'ast Axiom,
+        &'ast AnonymousIndividual,
+        &'ast AnonymousIndividual,
+    )>,
+}
+
+impl<'ast> DlChecker<'ast> {
+    /// Create a new checker.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record that `sub → sup` in the object property hierarchy.
+    fn add_inclusion(&mut self, sub: Property, sup: Property) {
+        self.relation.add_edge(inverse(&sub), inverse(&sup));
+        self.relation.add_edge(sub, sup);
+    }
+
+    /// Record that a simple object property is required by the current axiom.
+    fn require_simple(&mut self, ope: &'ast ObjectPropertyExpression) {
+        if let Some(axiom) = self.axiom {
+            self.simple.push((axiom, ope));
+        }
+    }
+
+    /// Check whether an object property expression is simple.
+    fn is_simple(&self, ope: &ObjectPropertyExpression) -> bool {
+        let p = property(ope);
+        !self.composite.contains(&p.0)
+            && self
+                .relation
+                .descendants(&p)
+                .iter()
+                .all(|sub| !self.composite.contains(&sub.0))
+    }
+
+    /// Get the axioms with property chains breaking the regularity.
+    fn irregular_chains(&self) -> Vec<&'ast Axiom> {
+        // the hierarchy between named properties, ignoring inversions
+        let mut names = Hierarchy::new();
+        for ((sub, _), (sup, _)) in self.relation.edges() {
+            if sub != sup {
+                names.add_edge(sub, sup);
+            }
+        }
+
+        // the strict order required by the hierarchy and by the chains
+        let mut order = Hierarchy::new();
+        for &sub in names.nodes() {
+            for &sup in names.ancestors(&sub) {
+                if sup != sub && !names.ancestors(&sup).contains(&sub) {
+                    order.add_edge(sub, sup);
+                }
+            }
+        }
+        let mut constraints = Vec::new();
+        for &(axiom, chain, sup) in self.chains.iter() {
+            if name(sup).0.as_ref() == TOP_OBJECT_PROPERTY {
+                continue;
+            }
+            let n = chain.len();
+            let smaller = if n == 2 && &chain[0] == sup && &chain[1] == sup {
+                &chain[..0]
+            } else if &chain[0] == sup {
+                &chain[1..]
+            } else if &chain[n - 1] == sup {
+                &chain[..n - 1]
+            } else {
+                chain
+            };
+            for ope in smaller.iter() {
+                constraints.push((axiom, name(ope), name(sup)));
+            }
+        }
+        for &(_, sub, sup) in constraints.iter() {
+            order.add_edge(sub, sup);
+        }
+
+        let cycles = order.cycles();
+        let mut irregular = Vec::<&Axiom>::new();
+        for &(axiom, sub, sup) in constraints.iter() {
+            let cyclic = cycles.iter().any(|c| c.contains(&sub) && c.contains(&sup));
+            if cyclic && !irregular.contains(&axiom) {
+                irregular.push(axiom);
+            }
+        }
+        irregular
+    }
+
+    /// Get the assertions closing a cycle between anonymous individuals.
+    fn anonymous_cycles(&self) -> Vec<&'ast Axiom> {
+        let mut parents = BTreeMap::<&AnonymousIndividual, &AnonymousIndividual>::new();
+        let mut cycles = Vec::new();
+        for &(axiom, from, to) in self.anonymous.iter() {
+            let (from, to) = (root(&parents, from), root(&parents, to));
+            if from == to {
+                cycles.push(axiom);
+            } else {
+                parents.insert(from, to);
+            }
+        }
+        cycles
+    }
+
+    /// Consume the checker and return the violations found.
+    pub fn into_report(self) -> DlReport {
+        let mut report = DlReport::default();
+        for &(axiom, ope) in self.simple.iter() {
+            if !self.is_simple(ope) {
+                report.violations.push(Violation::NonSimpleProperty {
+                    property: ope.clone(),
+                    axiom: axiom.clone(),
+                });
+            }
+        }
+        for axiom in self.irregular_chains() {
+            report
+                .violations
+                .push(Violation::IrregularPropertyChain(axiom.clone()));
+        }
+        for axiom in self.anonymous_cycles() {
+            report
+                .violations
+                .push(Violation::AnonymousIndividualCycle(axiom.clone()));
+        }
+        report
+    }
+}
+
+/// Find the representative of an anonymous individual in a union-find forest.
+fn root<'a>(
+    parents: &BTreeMap<&'a AnonymousIndividual, &'a AnonymousIndividual>,
+    mut individual: &'a AnonymousIndividual,
+) -> &'a AnonymousIndividual {
+    while let Some(&parent) = parents.get(individual) {
+        individual = parent;
+    }
+    individual
+}
+
+impl<'ast> Visit<'ast> for DlChecker<'ast> {
+    fn visit_axiom(&mut self, axiom: &'ast Axiom) {
+        self.axiom = Some(axiom);
+        match axiom {
+            Axiom::SubObjectPropertyOf(SubObjectPropertyOf { sub, sup }) => match sub {
+                SubObjectPropertyExpression::ObjectPropertyExpression(ope) => {
+                    self.add_inclusion(property(ope), property(sup));
+                }
+                SubObjectPropertyExpression::ObjectPropertyChain(chain) if chain.len() == 1 => {
+                    self.add_inclusion(property(&chain[0]), property(sup));
+                }
+                SubObjectPropertyExpression::ObjectPropertyChain(chain) => {
+                    if !chain.is_empty() {
+                        self.composite.insert(name(sup));
+                        self.chains.push((axiom, chain, sup));
+                    }
+                }
+            },
+            Axiom::EquivalentObjectProperties(EquivalentObjectProperties(opes)) => {
+                for pair in opes.windows(2) {
+                    self.add_inclusion(property(&pair[0]), property(&pair[1]));
+                    self.add_inclusion(property(&pair[1]), property(&pair[0]));
+                }
+            }
+            Axiom::InverseObjectProperties(InverseObjectProperties(first, second)) => {
+                let first = (first.clone(), false);
+                let second = (second.clone(), true);
+                self.add_inclusion(first.clone(), second.clone());
+                self.add_inclusion(second, first);
+            }
+            Axiom::SymmetricObjectProperty(SymmetricObjectProperty(ope)) => {
+                let p = property(ope);
+                self.add_inclusion(p.clone(), inverse(&p));
+            }
+            Axiom::TransitiveObjectProperty(TransitiveObjectProperty(ope)) => {
+                self.composite.insert(name(ope));
+            }
+            Axiom::FunctionalObjectProperty(FunctionalObjectProperty(ope))
+            | Axiom::InverseFunctionalObjectProperty(InverseFunctionalObjectProperty(ope))
+            | Axiom::IrreflexiveObjectProperty(IrreflexiveObjectProperty(ope))
+            | Axiom::AsymmetricObjectProperty(AsymmetricObjectProperty(ope)) => {
+                self.require_simple(ope);
+            }
+            Axiom::DisjointObjectProperties(DisjointObjectProperties(opes)) => {
+                for ope in opes.iter() {
+                    self.require_simple(ope);
+                }
+            }
+            Axiom::ObjectPropertyAssertion(ObjectPropertyAssertion {
+                from: Individual::Anonymous(from),
+                to: Individual::Anonymous(to),
+                ..
+            }) => {
+                self.anonymous.push((axiom, from, to));
+            }
+            _ => (),
+        }
+        visit::visit_axiom(self, axiom);
+        self.axiom = None;
+    }
+
+    fn visit_class_expression(&mut self, class_expression: &'ast ClassExpression) {
+        match class_expression {
+            ClassExpression::ObjectMinCardinality { ope, .. }
+            | ClassExpression::ObjectMaxCardinality { ope, .. }
+            | ClassExpression::ObjectExactCardinality { ope, .. }
+            | ClassExpression::ObjectHasSelf(ope) => self.require_simple(ope),
+            _ => (),
+        }
+        visit::visit_class_expression(self, class_expression);
+    }
+}
+
+/// Check the global restrictions of OWL2 DL on an ontology.
+pub fn check<'a, I>(axioms: I) -> DlReport
+where
+    I: IntoIterator<Item = &'a AnnotatedAxiom>,
+{
+    let mut checker = DlChecker::new();
+    for aa in axioms.into_iter() {
+        checker.visit_axiom(&aa.axiom);
+    }
+    checker.into_report()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::*;
+
+    fn anonymous_assertion(from: &str, to: &str) -> Axiom {
+        ObjectPropertyAssertion {
+            ope: op("r"),
+            from: anonymous(from),
+            to: anonymous(to),
+        }
+        .into()
+    }
+
+    #[test]
+    fn top_object_property_chain_is_exempt() {
+        let top: ObjectPropertyExpression =
+            Build::new().object_property(TOP_OBJECT_PROPERTY).into();
+        let report = check(&ontology(vec![
+            chain(&["r", "s"], top.clone()),
+            sub_object_property_of(top, op("r")),
+        ]));
+        assert!(report.is_dl(), "{}", report);
+    }
+
+    #[test]
+    fn non_simple_property_in_cardinality() {
+        let cardinality = Axiom::from(SubClassOf {
+            sub: class("A"),
+            sup: ClassExpression::ObjectMaxCardinality {
+                n: 1,
+                ope: op("p"),
+                bce: Box::new(class("B")),
+            },
+        });
+
+        // a subproperty of `p` is composite, which makes `p` non-simple
+        let report = check(&ontology(vec![
+            cardinality.clone(),
+            sub_object_property_of(op("q"), op("p")),
+            chain(&["r", "s"], op("q")),
+        ]));
+        assert_eq!(
+            report.violations(),
+            &[Violation::NonSimpleProperty {
+                property: op("p"),
+                axiom: cardinality.clone(),
+            }]
+        );
+
+        // a superproperty of a transitive property can still be simple
+        let report = check(&ontology(vec![
+            cardinality,
+            sub_object_property_of(op("p"), op("q")),
+            TransitiveObjectProperty(op("q")).into(),
+        ]));
+        assert!(report.is_dl(), "{}", report);
+    }
+
+    #[test]
+    fn non_simple_inverse_property_in_cardinality() {
+        let cardinality = Axiom::from(SubClassOf {
+            sub: class("A"),
+            sup: ClassExpression::ObjectMinCardinality {
+                n: 2,
+                ope: inverse_of("p"),
+                bce: Box::new(class("B")),
+            },
+        });
+        let report = check(&ontology(vec![
+            cardinality,
+            TransitiveObjectProperty(op("p")).into(),
+        ]));
+        assert_eq!(report.violations().len(), 1);
+    }
+
+    #[test]
+    fn irregular_chains() {
+        // `r ∘ s ⊑ s` requires `r < s` and `s ∘ r ⊑ r` requires `s < r`
+        let first = chain(&["r", "s"], op("s"));
+        let second = chain(&["s", "r"], op("r"));
+        let report = check(&ontology(vec![first.clone(), second.clone()]));
+        let axioms: Vec<&Axiom> = report.violations().iter().map(|v| v.axiom()).collect();
+        assert!(axioms.contains(&&first));
+        assert!(axioms.contains(&&second));
+        assert!(report
+            .violations()
+            .iter()
+            .all(|v| matches!(v, Violation::IrregularPropertyChain(_))));
+    }
+
+    #[test]
+    fn regular_chains() {
+        let report = check(&ontology(vec![
+            chain(&["r", "r"], op("r")),
+            chain(&["r", "s"], op("r")),
+            chain(&["t", "r"], op("r")),
+            chain(&["s", "t"], op("u")),
+        ]));
+        assert!(report.is_dl(), "{}", report);
+    }
+
+    #[test]
+    fn irregular_chain_through_hierarchy() {
+        // `r ∘ s ⊑ t` requires `s < t`, contradicted by `t ⊑ s`
+        let irregular = chain(&["r", "s"], op("t"));
+        let report = check(&ontology(vec![
+            irregular.clone(),
+            sub_object_property_of(op("t"), op("s")),
+        ]));
+        assert_eq!(
+            report.violations(),
+            &[Violation::IrregularPropertyChain(irregular)]
+        );
+    }
+
+    #[test]
+    fn anonymous_individual_cycles() {
+        let report = check(&ontology(vec![
+            anonymous_assertion("a", "b"),
+            anonymous_assertion("b", "c"),
+            anonymous_assertion("a", "d"),
+        ]));
+        assert!(report.is_dl(), "{}", report);
+
+        let report = check(&ontology(vec![
+            anonymous_assertion("a", "b"),
+            anonymous_assertion("b", "c"),
+            anonymous_assertion("c", "a"),
+        ]));
+        assert_eq!(report.violations().len(), 1);
+        assert!(matches!(
+            report.violations()[0],
+            Violation::AnonymousIndividualCycle(_)
+        ));
+    }
+}
@@ -0,0 +1,5 @@
+//! Conformance checks of ontologies against the OWL2 specification.
+
+pub mod dl;
+pub mod punning;
+pub mod reserved;
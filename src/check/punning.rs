@@ -0,0 +1,290 @@
+//! Detection of IRIs used as entities of different kinds.
+//!
+//! OWL2 allows the same IRI to be used as entities of different kinds, a
+//! practice known as *punning*, with the exceptions listed in
+//! [section 5.9](https://www.w3.org/TR/owl2-syntax/#Typing_Constraints_of_OWL_2_DL)
+//! of the structural specification: an IRI cannot be used for more than
+//! one kind of property, nor both as a class and as a datatype.
+//!
+//! # Example
+//!
+//! ```rust
+//! # extern crate horned_owl;
+//! # extern crate horned_visit;
+//! use horned_owl::model::*;
+//! use horned_owl::ontology::set::SetOntology;
+//!
+//! let b = Build::new();
+//! let mut ontology = SetOntology::new();
+//! ontology.declare(b.class("http://example.com/A"));
+//! ontology.declare(b.named_individual("http://example.com/A"));
+//! ontology.declare(b.object_property("http://example.com/p"));
+//! ontology.declare(b.data_property("http://example.com/p"));
+//!
+//! let report = horned_visit::check::punning::check(&ontology);
+//! assert_eq!(report.permitted().count(), 1);
+//! assert_eq!(report.illegal().count(), 1);
+//! ```
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+
+use horned_owl::model::*;
+
+use crate::Visit;
+
+/// The pairs of entity kinds that cannot share the same IRI.
+const ILLEGAL: &[(NamedEntityKind, NamedEntityKind)] = &[
+    (
+        NamedEntityKind::ObjectProperty,
+        NamedEntityKind::DataProperty,
+    ),
+    (
+        NamedEntityKind::ObjectProperty,
+        NamedEntityKind::AnnotationProperty,
+    ),
+    (
+        NamedEntityKind::DataProperty,
+        NamedEntityKind::AnnotationProperty,
+    ),
+    (NamedEntityKind::Class, NamedEntityKind::Datatype),
+];
+
+/// An IRI used as entities of different kinds.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Punning {
+    iri: IRI,
+    kinds: BTreeSet<NamedEntityKind>,
+}
+
+impl Punning {
+    /// Get the punned IRI.
+    pub fn iri(&self) -> &IRI {
+        &self.iri
+    }
+
+    /// Get the kinds of entities the IRI is used as.
+    pub fn kinds(&self) -> &BTreeSet<NamedEntityKind> {
+        &self.kinds
+    }
+
+    /// Get the pairs of kinds that cannot share the IRI.
+    pub fn conflicts(&self) -> Vec<(NamedEntityKind, NamedEntityKind)> {
+        ILLEGAL
+            .iter()
+            .filter(|(x, y)| self.kinds.contains(x) && self.kinds.contains(y))
+            .cloned()
+            .collect()
+    }
+
+    /// Check whether the punning is forbidden in OWL2 DL.
+    pub fn is_illegal(&self) -> bool {
+        !self.conflicts().is_empty()
+    }
+}
+
+impl Display for Punning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        if self.is_illegal() {
+            write!(f, "illegal punning: <{}> {:?}", self.iri, self.kinds)
+        } else {
+            write!(f, "punning: <{}> {:?}", self.iri, self.kinds)
+        }
+    }
+}
+
+/// The punned IRIs of an ontology.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PunningReport {
+    punnings: Vec<Punning>,
+}
+
+impl PunningReport {
+    /// Get all the punned IRIs.
+    pub fn punnings(&self) -> &[Punning] {
+        &self.punnings
+    }
+
+    /// Get the punned IRIs forbidden in OWL2 DL.
+    pub fn illegal(&self) -> impl Iterator<Item = &Punning> {
+        self.punnings.iter().filter(|p| p.is_illegal())
+    }
+
+    /// Get the punned IRIs allowed in OWL2 DL.
+    pub fn permitted(&self) -> impl Iterator<Item = &Punning> {
+        self.punnings.iter().filter(|p| !p.is_illegal())
+    }
+
+    /// Check whether the ontology is free of illegal punning.
+    pub fn is_valid(&self) -> bool {
+        self.illegal().next().is_none()
+    }
+}
+
+impl Display for PunningReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        for punning in self.illegal().chain(self.permitted()) {
+            writeln!(f, "{}", punning)?;
+        }
+        Ok(())
+    }
+}
+
+/// A visitor collecting the kinds of entities each IRI is used as.
+#[derive(Clone, Debug, Default)]
+pub struct PunningDetector {
+    kinds: BTreeMap<IRI, BTreeSet<NamedEntityKind>>,
+}
+
+impl PunningDetector {
+    /// Create a new detector.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the kinds of entities each visited IRI was used as.
+    pub fn kinds(&self) -> &BTreeMap<IRI, BTreeSet<NamedEntityKind>> {
+        &self.kinds
+    }
+
+    /// Consume the detector and return the punned IRIs.
+    pub fn into_report(self) -> PunningReport {
+        let punnings = self
+            .kinds
+            .into_iter()
+            .filter(|(_, kinds)| kinds.len() > 1)
+            .map(|(iri, kinds)| Punning { iri, kinds })
+            .collect();
+        PunningReport { punnings }
+    }
+}
+
+impl<'ast> Visit<'ast> for PunningDetector {
+    fn visit_entity(&mut self, kind: NamedEntityKind, iri: &'ast IRI) {
+        self.kinds.entry(iri.clone()).or_default().insert(kind);
+    }
+}
+
+/// Detect the punned IRIs of an ontology.
+pub fn check<'a, I>(axioms: I) -> PunningReport
+where
+    I: IntoIterator<Item = &'a AnnotatedAxiom>,
+{
+    let mut detector = PunningDetector::new();
+    for aa in axioms.into_iter() {
+        detector.visit_annotated_axiom(aa);
+    }
+    detector.into_report()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::fixtures::*;
+
+    fn report(axioms: Vec<Axiom>) -> PunningReport {
+        check(&ontology(axioms))
+    }
+
+    #[test]
+    fn no_punning() {
+        let report = report(vec![
+            sub_class_of(class("A"), some("p", class("B"))),
+            DeclareNamedIndividual(NamedIndividual(iri("a"))).into(),
+        ]);
+        assert!(report.punnings().is_empty());
+        assert!(report.is_valid());
+        assert_eq!(report.to_string(), "");
+    }
+
+    #[test]
+    fn permitted() {
+        let report = report(vec![
+            DeclareClass(named_class("A")).into(),
+            DeclareNamedIndividual(NamedIndividual(iri("A"))).into(),
+            DeclareObjectProperty(ObjectProperty(iri("A"))).into(),
+        ]);
+        assert_eq!(report.punnings().len(), 1);
+        let punning = &report.punnings()[0];
+        assert_eq!(punning.iri(), &iri("A"));
+        assert_eq!(punning.kinds().len(), 3);
+        assert!(punning.conflicts().is_empty());
+        assert!(report.is_valid());
+    }
+
+    #[test]
+    fn class_and_datatype() {
+        let report = report(vec![
+            DeclareClass(named_class("A")).into(),
+            DeclareDatatype(Datatype(iri("A"))).into(),
+        ]);
+        assert!(!report.is_valid());
+        assert_eq!(
+            report.punnings()[0].conflicts(),
+            vec![(NamedEntityKind::Class, NamedEntityKind::Datatype)]
+        );
+    }
+
+    #[test]
+    fn all_property_kinds() {
+        let report = report(vec![
+            DeclareObjectProperty(ObjectProperty(iri("p"))).into(),
+            DeclareDataProperty(DataProperty(iri("p"))).into(),
+            DeclareAnnotationProperty(AnnotationProperty(iri("p"))).into(),
+        ]);
+        assert_eq!(report.illegal().count(), 1);
+        assert_eq!(
+            report.punnings()[0].conflicts(),
+            vec![
+                (
+                    NamedEntityKind::ObjectProperty,
+                    NamedEntityKind::DataProperty
+                ),
+                (
+                    NamedEntityKind::ObjectProperty,
+                    NamedEntityKind::AnnotationProperty
+                ),
+                (
+                    NamedEntityKind::DataProperty,
+                    NamedEntityKind::AnnotationProperty
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn usage_in_axiom_annotations() {
+        let annotation = Annotation {
+            ap: AnnotationProperty(iri("p")),
+            av: AnnotationValue::IRI(iri("v")),
+        };
+        let axioms = vec![AnnotatedAxiom::new(
+            sub_class_of(class("A"), some("p", class("B"))),
+            vec![annotation].into_iter().collect(),
+        )];
+        let report = check(&axioms);
+        assert_eq!(
+            report.illegal().map(Punning::iri).collect::<Vec<_>>(),
+            vec![&iri("p")]
+        );
+    }
+
+    #[test]
+    fn illegal_punnings_are_displayed_first() {
+        let report = report(vec![
+            DeclareClass(named_class("A")).into(),
+            DeclareNamedIndividual(NamedIndividual(iri("A"))).into(),
+            DeclareClass(named_class("B")).into(),
+            DeclareDatatype(Datatype(iri("B"))).into(),
+        ]);
+        let lines = report.to_string();
+        let lines = lines.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("illegal punning: <http://example.com/B>"));
+        assert!(lines[1].starts_with("punning: <http://example.com/A>"));
+    }
+}
@@ -0,0 +1,376 @@
+//! Checks of the usage of the reserved vocabulary of OWL2.
+//!
+//! IRIs in the `owl:`, `rdf:`, `rdfs:` and `xsd:` namespaces are reserved
+//! by [section 2.4](https://www.w3.org/TR/owl2-syntax/#IRIs) of the OWL2
+//! structural specification, and can only be used as the built-in entities
+//! of the following kinds:
+//!
+//! - `owl:Thing` and `owl:Nothing` as classes;
+//! - `owl:topObjectProperty` and `owl:bottomObjectProperty` as object
+//!   properties;
+//! - `owl:topDataProperty` and `owl:bottomDataProperty` as data properties;
+//! - the datatypes of the [OWL2 datatype map](https://www.w3.org/TR/owl2-syntax/#Datatype_Maps)
+//!   as datatypes;
+//! - the built-in annotation properties, such as `rdfs:label` or
+//!   `owl:deprecated`, as annotation properties.
+//!
+//! The [`ReservedChecker`] reports every other usage of a reserved IRI as
+//! an entity, such as `owl:Thing` used as an object property or `rdf:type`
+//! used as a class.
+//!
+//! # Example
+//!
+//! ```rust
+//! # extern crate horned_owl;
+//! # extern crate horned_visit;
+//! use horned_owl::model::*;
+//! use horned_owl::ontology::set::SetOntology;
+//!
+//! let b = Build::new();
+//! let mut ontology = SetOntology::new();
+//! ontology.declare(b.class("http://www.w3.org/2002/07/owl#Thing"));
+//! ontology.declare(b.object_property("http://www.w3.org/2002/07/owl#Thing"));
+//!
+//! let report = horned_visit::check::reserved::check(&ontology);
+//! assert_eq!(report.violations().len(), 1);
+//! ```
+
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+
+use horned_owl::model::*;
+use horned_owl::vocab::is_annotation_builtin;
+use horned_owl::vocab::Namespace;
+use horned_owl::vocab::WithIRI;
+
+use crate::display::Functional;
+use crate::signature::Entity;
+use crate::visit;
+use crate::Visit;
+
+/// The built-in object properties, besides `owl:topObjectProperty`.
+const OBJECT_PROPERTIES: &[&str] = &["http://www.w3.org/2002/07/owl#bottomObjectProperty"];
+
+/// The built-in data properties.
+const DATA_PROPERTIES: &[&str] = &[
+    "http://www.w3.org/2002/07/owl#topDataProperty",
+    "http://www.w3.org/2002/07/owl#bottomDataProperty",
+];
+
+/// The datatypes of the OWL2 datatype map.
+const DATATYPES: &[&str] = &[
+    "http://www.w3.org/2000/01/rdf-schema#Literal",
+    "http://www.w3.org/1999/02/22-rdf-syntax-ns#PlainLiteral",
+    "http://www.w3.org/1999/02/22-rdf-syntax-ns#XMLLiteral",
+    "http://www.w3.org/1999/02/22-rdf-syntax-ns#langString",
+    "http://www.w3.org/2002/07/owl#real",
+    "http://www.w3.org/2002/07/owl#rational",
+    "http://www.w3.org/2001/XMLSchema#decimal",
+    "http://www.w3.org/2001/XMLSchema#integer",
+    "http://www.w3.org/2001/XMLSchema#nonNegativeInteger",
+    "http://www.w3.org/2001/XMLSchema#nonPositiveInteger",
+    "http://www.w3.org/2001/XMLSchema#positiveInteger",
+    "http://www.w3.org/2001/XMLSchema#negativeInteger",
+    "http://www.w3.org/2001/XMLSchema#long",
+    "http://www.w3.org/2001/XMLSchema#int",
+    "http://www.w3.org/2001/XMLSchema#short",
+    "http://www.w3.org/2001/XMLSchema#byte",
+    "http://www.w3.org/2001/XMLSchema#unsignedLong",
+    "http://www.w3.org/2001/XMLSchema#unsignedInt",
+    "http://www.w3.org/2001/XMLSchema#unsignedShort",
+    "http://www.w3.org/2001/XMLSchema#unsignedByte",
+    "http://www.w3.org/2001/XMLSchema#double",
+    "http://www.w3.org/2001/XMLSchema#float",
+    "http://www.w3.org/2001/XMLSchema#string",
+    "http://www.w3.org/2001/XMLSchema#normalizedString",
+    "http://www.w3.org/2001/XMLSchema#token",
+    "http://www.w3.org/2001/XMLSchema#language",
+    "http://www.w3.org/2001/XMLSchema#Name",
+    "http://www.w3.org/2001/XMLSchema#NCName",
+    "http://www.w3.org/2001/XMLSchema#NMTOKEN",
+    "http://www.w3.org/2001/XMLSchema#boolean",
+    "http://www.w3.org/2001/XMLSchema#hexBinary",
+    "http://www.w3.org/2001/XMLSchema#base64Binary",
+    "http://www.w3.org/2001/XMLSchema#anyURI",
+    "http://www.w3.org/2001/XMLSchema#dateTime",
+    "http://www.w3.org/2001/XMLSchema#dateTimeStamp",
+];
+
+/// Check whether an IRI is in one of the reserved namespaces.
+pub fn is_reserved(iri: &IRI) -> bool {
+    [
+        Namespace::OWL,
+        Namespace::RDF,
+        Namespace::RDFS,
+        Namespace::XSD,
+    ]
+    .iter()
+    .any(|ns| iri.starts_with(ns.iri_str()))
+}
+
+/// A misuse of the reserved vocabulary.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Violation {
+    /// A reserved IRI is used as an entity which is not built-in.
+    ReservedIri { entity: Entity, axiom: Axiom },
+    /// A reserved IRI is used as a datatype outside the OWL2 datatype map.
+    UnsupportedDatatype { datatype: Datatype, axiom: Axiom },
+}
+
+impl Violation {
+    /// Get the offending axiom.
+    pub fn axiom(&self) -> &Axiom {
+        use self::Violation::*;
+        match self {
+            ReservedIri { axiom, .. } => axiom,
+            UnsupportedDatatype { axiom, .. } => axiom,
+        }
+    }
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        use self::Violation::*;
+        match self {
+            ReservedIri { entity, axiom } => write!(
+                f,
+                "reserved IRI used as {:?}: <{}> in {}",
+                entity.kind,
+                entity.iri,
+                Functional(axiom)
+            ),
+            UnsupportedDatatype { datatype, axiom } => write!(
+                f,
+                "unsupported datatype: <{}> in {}",
+                datatype.0,
+                Functional(axiom)
+            ),
+        }
+    }
+}
+
+/// The misuses of the reserved vocabulary in an ontology.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ReservedReport {
+    violations: Vec<Violation>,
+}
+
+impl ReservedReport {
+    /// Get all the violations found in the ontology.
+    pub fn violations(&self) -> &[Violation] {
+        &self.violations
+    }
+
+    /// Check whether the reserved vocabulary is used correctly.
+    pub fn is_valid(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+impl Display for ReservedReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        for violation in self.violations.iter() {
+            writeln!(f, "{}", violation)?;
+        }
+        Ok(())
+    }
+}
+
+/// A visitor checking the usage of the reserved vocabulary.
+///
+/// Built-in classes and object properties are recognized with the
+/// [`Visit::visit_thing`], [`Visit::visit_nothing`] and
+/// [`Visit::visit_top_object_property`] hooks, which do not forward them to
+/// [`Visit::visit_entity`]; every other entity is checked there.
+#[derive(Debug, Default)]
+pub struct ReservedChecker<'ast> {
+    axiom: Option<&'ast Axiom>,
+    report: ReservedReport,
+}
+
+impl<'ast> ReservedChecker<'ast> {
+    /// Create a new checker.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the report of the violations found so far.
+    pub fn report(&self) -> &ReservedReport {
+        &self.report
+    }
+
+    /// Consume the checker and return the report of the violations found.
+    pub fn into_report(self) -> ReservedReport {
+        self.report
+    }
+}
+
+impl<'ast> Visit<'ast> for ReservedChecker<'ast> {
+    fn visit_annotated_axiom(&mut self, annotated_axiom: &'ast AnnotatedAxiom) {
+        let outer = self.axiom.replace(&annotated_axiom.axiom);
+        visit::visit_annotated_axiom(self, annotated_axiom);
+        self.axiom = outer;
+    }
+
+    fn visit_axiom(&mut self, axiom: &'ast Axiom) {
+        let outer = self.axiom.replace(axiom);
+        visit::visit_axiom(self, axiom);
+        self.axiom = outer;
+    }
+
+    fn visit_entity(&mut self, kind: NamedEntityKind, iri: &'ast IRI) {
+        let axiom = match self.axiom {
+            Some(axiom) if is_reserved(iri) => axiom.clone(),
+            _ => return,
+        };
+        let violation = match kind {
+            NamedEntityKind::AnnotationProperty if is_annotation_builtin(&iri.to_string()) => {
+                return
+            }
+            NamedEntityKind::ObjectProperty if OBJECT_PROPERTIES.contains(&iri.as_ref()) => return,
+            NamedEntityKind::DataProperty if DATA_PROPERTIES.contains(&iri.as_ref()) => return,
+            NamedEntityKind::Datatype if DATATYPES.contains(&iri.as_ref()) => return,
+            NamedEntityKind::Datatype => Violation::UnsupportedDatatype {
+                datatype: Datatype(iri.clone()),
+                axiom,
+            },
+            _ => Violation::ReservedIri {
+                entity: Entity::new(kind, iri.clone()),
+                axiom,
+            },
+        };
+        self.report.violations.push(violation);
+    }
+
+    fn visit_thing(&mut self, _thing: &'ast Class) {}
+
+    fn visit_nothing(&mut self, _nothing: &'ast Class) {}
+
+    fn visit_top_object_property(&mut self, _top_object_property: &'ast ObjectProperty) {}
+}
+
+/// Check the usage of the reserved vocabulary in an ontology.
+pub fn check<'a, I>(axioms: I) -> ReservedReport
+where
+    I: IntoIterator<Item = &'a AnnotatedAxiom>,
+{
+    let mut checker = ReservedChecker::new();
+    for aa in axioms.into_iter() {
+        checker.visit_annotated_axiom(aa);
+    }
+    checker.into_report()
+}
+
+#[cfg(test)]
+mod tests {
+    use horned_owl::ontology::set::SetOntology;
+    use horned_owl::vocab::AnnotationBuiltIn;
+
+    use super::*;
+
+    const OWL: &str = "http://www.w3.org/2002/07/owl#";
+
+    fn check_axioms(axioms: Vec<AnnotatedAxiom>) -> ReservedReport {
+        let ontology: SetOntology = axioms.into_iter().collect();
+        check(&ontology)
+    }
+
+    #[test]
+    fn builtin_entities() {
+        let b = Build::new();
+        let report = check_axioms(vec![
+            SubClassOf {
+                sub: b.class(format!("{}Nothing", OWL)).into(),
+                sup: b.class(format!("{}Thing", OWL)).into(),
+            }
+            .into(),
+            SubObjectPropertyOf {
+                sub: SubObjectPropertyExpression::ObjectPropertyExpression(
+                    b.object_property(format!("{}bottomObjectProperty", OWL))
+                        .into(),
+                ),
+                sup: b
+                    .object_property(format!("{}topObjectProperty", OWL))
+                    .into(),
+            }
+            .into(),
+            DataPropertyRange {
+                dp: b.data_property(format!("{}topDataProperty", OWL)),
+                dr: b
+                    .datatype("http://www.w3.org/2001/XMLSchema#integer")
+                    .into(),
+            }
+            .into(),
+            DeclareAnnotationProperty(b.annotation_property(AnnotationBuiltIn::LABEL.iri_str()))
+                .into(),
+        ]);
+        assert!(report.is_valid(), "{}", report);
+    }
+
+    #[test]
+    fn reserved_iri_with_wrong_kind() {
+        let b = Build::new();
+        let report = check_axioms(vec![
+            DeclareObjectProperty(b.object_property(format!("{}Thing", OWL))).into(),
+            DeclareClass(b.class(format!("{}topObjectProperty", OWL))).into(),
+            DeclareNamedIndividual(b.named_individual(format!("{}Nothing", OWL))).into(),
+        ]);
+        let entities: Vec<Entity> = report
+            .violations()
+            .iter()
+            .filter_map(|violation| match violation {
+                Violation::ReservedIri { entity, .. } => Some(entity.clone()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(entities.len(), 3);
+        assert!(entities.contains(&Entity::new(
+            NamedEntityKind::ObjectProperty,
+            b.iri(format!("{}Thing", OWL))
+        )));
+        assert!(entities.contains(&Entity::new(
+            NamedEntityKind::Class,
+            b.iri(format!("{}topObjectProperty", OWL))
+        )));
+    }
+
+    #[test]
+    fn unsupported_datatype() {
+        let b = Build::new();
+        let report = check_axioms(vec![DeclareDatatype(
+            b.datatype("http://www.w3.org/2001/XMLSchema#gYear"),
+        )
+        .into()]);
+        assert_eq!(report.violations().len(), 1);
+        assert!(matches!(
+            report.violations()[0],
+            Violation::UnsupportedDatatype { .. }
+        ));
+    }
+
+    #[test]
+    fn reserved_iri_in_axiom_annotations() {
+        let b = Build::new();
+        let axiom = Axiom::from(DeclareClass(b.class("http://example.com/A")));
+        let report = check_axioms(vec![AnnotatedAxiom::new(
+            axiom.clone(),
+            vec![Annotation {
+                ap: b.annotation_property(format!("{}Thing", OWL)),
+                av: AnnotationValue::IRI(b.iri("http://example.com/B")),
+            }]
+            .into_iter()
+            .collect(),
+        )]);
+        assert_eq!(
+            report.violations(),
+            &[Violation::ReservedIri {
+                entity: Entity::new(
+                    NamedEntityKind::AnnotationProperty,
+                    b.iri(format!("{}Thing", OWL))
+                ),
+                axiom,
+            }]
+        );
+    }
+}
@@ -0,0 +1,509 @@
+//! Compatibility checks between two releases of an ontology.
+//!
+//! The changes between two versions of an ontology are classified in the
+//! spirit of [Semantic Versioning](https://semver.org/):
+//!
+//! - *major* changes break downstream users: an entity was removed or
+//!   changed kind, a logical axiom was removed, or an entity was made
+//!   obsolete without a replacement;
+//! - *minor* changes are additive: new entities, new logical axioms, or
+//!   entities made obsolete with a replacement;
+//! - *patch* changes only concern non-logical content, such as annotations
+//!   or declarations of entities that are still in use.
+//!
+//! # Example
+//!
+//! ```rust
+//! # extern crate horned_owl;
+//! # extern crate horned_visit;
+//! use horned_owl::model::*;
+//! use horned_owl::ontology::set::SetOntology;
+//! use horned_visit::compat::Compatibility;
+//!
+//! let b = Build::new();
+//! let mut old = SetOntology::new();
+//! old.declare(b.class("http://example.com/A"));
+//!
+//! let mut new = old.clone();
+//! new.declare(b.class("http://example.com/B"));
+//!
+//! let report = horned_visit::compat::check(&old, &new);
+//! assert_eq!(report.verdict(), Compatibility::Minor);
+//! ```
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+
+use horned_owl::model::*;
+use horned_owl::vocab::AnnotationBuiltIn;
+use horned_owl::vocab::WithIRI;
+
+use super::diff::diff;
+use super::display::Functional;
+use super::signature::Entity;
+use super::signature::SignatureCollector;
+use super::visit;
+use super::Visit;
+
+/// The IRI of the `IAO:0100001` (*term replaced by*) annotation property.
+pub const REPLACED_BY: &str = "http://purl.obolibrary.org/obo/IAO_0100001";
+
+/// The compatibility level of a change between two ontology releases.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Compatibility {
+    /// The two releases are structurally identical.
+    Identical,
+    /// Only non-logical content changed.
+    Patch,
+    /// The new release only adds content.
+    Minor,
+    /// The new release breaks compatibility with the old one.
+    Major,
+}
+
+/// A single change between two ontology releases.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Change {
+    /// An entity is not referenced anymore in the new release.
+    EntityRemoved(Entity),
+    /// An IRI is used with different entity kinds in the new release.
+    EntityKindChanged {
+        iri: IRI,
+        old: BTreeSet<NamedEntityKind>,
+        new: BTreeSet<NamedEntityKind>,
+    },
+    /// A logical axiom was removed.
+    LogicalAxiomRemoved(AnnotatedAxiom),
+    /// An entity was made obsolete without a replacement.
+    ObsoletedWithoutReplacement(IRI),
+    /// A new entity is referenced in the new release.
+    EntityAdded(Entity),
+    /// A logical axiom was added.
+    LogicalAxiomAdded(AnnotatedAxiom),
+    /// An entity was made obsolete with a replacement.
+    Obsoleted(IRI),
+    /// A non-logical axiom was removed.
+    NonLogicalAxiomRemoved(AnnotatedAxiom),
+    /// A non-logical axiom was added.
+    NonLogicalAxiomAdded(AnnotatedAxiom),
+    /// The annotations of an axiom changed.
+    AnnotationsChanged(Axiom),
+}
+
+impl Change {
+    /// Get the compatibility level of the change.
+    pub fn compatibility(&self) -> Compatibility {
+        use self::Change::*;
+        match self {
+            EntityRemoved(_)
+            | EntityKindChanged { .. }
+            | LogicalAxiomRemoved(_)
+            | ObsoletedWithoutReplacement(_) => Compatibility::Major,
+            EntityAdded(_) | LogicalAxiomAdded(_) | Obsoleted(_) => Compatibility::Minor,
+            NonLogicalAxiomRemoved(_) | NonLogicalAxiomAdded(_) | AnnotationsChanged(_) => {
+                Compatibility::Patch
+            }
+        }
+    }
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        use self::Change::*;
+        match self {
+            EntityRemoved(e) => write!(f, "entity removed: {:?} <{}>", e.kind, e.iri),
+            EntityKindChanged { iri, old, new } => {
+                write!(f, "entity kind changed: <{}> {:?} -> {:?}", iri, old, new)
+            }
+            LogicalAxiomRemoved(aa) => write!(f, "logical axiom removed: {}", Functional(aa)),
+            ObsoletedWithoutReplacement(iri) => {
+                write!(f, "entity obsoleted without replacement: <{}>", iri)
+            }
+            EntityAdded(e) => write!(f, "entity added: {:?} <{}>", e.kind, e.iri),
+            LogicalAxiomAdded(aa) => write!(f, "logical axiom added: {}", Functional(aa)),
+            Obsoleted(iri) => write!(f, "entity obsoleted: <{}>", iri),
+            NonLogicalAxiomRemoved(aa) => {
+                write!(f, "non-logical axiom removed: {}", Functional(aa))
+            }
+            NonLogicalAxiomAdded(aa) => write!(f, "non-logical axiom added: {}", Functional(aa)),
+            AnnotationsChanged(axiom) => write!(f, "annotations changed: {}", Functional(axiom)),
+        }
+    }
+}
+
+/// The changes between two releases of an ontology.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CompatibilityReport {
+    changes: Vec<Change>,
+}
+
+impl CompatibilityReport {
+    /// Get all the changes between the two releases.
+    pub fn changes(&self) -> &[Change] {
+        &self.changes
+    }
+
+    /// Get the changes breaking compatibility with the old release.
+    pub fn breaking(&self) -> impl Iterator<Item = &Change> {
+        self.changes
+            .iter()
+            .filter(|c| c.compatibility() == Compatibility::Major)
+    }
+
+    /// Check whether the new release breaks compatibility with the old one.
+    pub fn is_breaking(&self) -> bool {
+        self.breaking().next().is_some()
+    }
+
+    /// Get the overall compatibility level of the new release.
+    pub fn verdict(&self) -> Compatibility {
+        self.changes
+            .iter()
+            .map(Change::compatibility)
+            .max()
+            .unwrap_or(Compatibility::Identical)
+    }
+}
+
+impl Display for CompatibilityReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        writeln!(f, "verdict: {:?}", self.verdict())?;
+        for change in self.changes.iter() {
+            writeln!(f, "[{:?}] {}", change.compatibility(), change)?;
+        }
+        Ok(())
+    }
+}
+
+/// A visitor collecting the entities and obsoletions of an ontology.
+#[derive(Debug, Default)]
+struct Inventory<'ast> {
+    signature: SignatureCollector,
+    deprecated: BTreeSet<&'ast IRI>,
+    replaced: BTreeSet<&'ast IRI>,
+}
+
+impl<'ast> Inventory<'ast> {
+    fn kinds(&self) -> BTreeMap<&IRI, BTreeSet<NamedEntityKind>> {
+        let mut kinds = BTreeMap::<&IRI, BTreeSet<NamedEntityKind>>::new();
+        for entity in self.signature.entities() {
+            kinds.entry(&entity.iri).or_default().insert(entity.kind);
+        }
+        kinds
+    }
+}
+
+impl<'ast> Visit<'ast> for Inventory<'ast> {
+    fn visit_annotation_assertion(&mut self, annotation_assertion: &'ast AnnotationAssertion) {
+        if let AnnotationSubject::IRI(iri) = &annotation_assertion.subject {
+            let ann = &annotation_assertion.ann;
+            if ann.ap.0.as_ref() == AnnotationBuiltIn::DEPRECATED.iri_str() && is_true(&ann.av) {
+                self.deprecated.insert(iri);
+            } else if ann.ap.0.as_ref() == REPLACED_BY {
+                self.replaced.insert(iri);
+            }
+        }
+        visit::visit_annotation_assertion(self, annotation_assertion);
+    }
+
+    fn visit_entity(&mut self, kind: NamedEntityKind, iri: &'ast IRI) {
+        self.signature.visit_entity(kind, iri);
+    }
+}
+
+/// Check whether an annotation value is the `true` boolean literal.
+fn is_true(value: &AnnotationValue) -> bool {
+    match value {
+        AnnotationValue::Literal(lit) => matches!(lit.literal().as_str(), "true" | "1"),
+        AnnotationValue::IRI(_) => false,
+    }
+}
+
+/// Check whether an axiom is part of the logical content of an ontology.
+fn is_logical(axiom: &Axiom) -> bool {
+    !matches!(
+        axiom,
+        Axiom::OntologyAnnotation(_)
+            | Axiom::Import(_)
+            | Axiom::DeclareClass(_)
+            | Axiom::DeclareObjectProperty(_)
+            | Axiom::DeclareAnnotationProperty(_)
+            | Axiom::DeclareDataProperty(_)
+            | Axiom::DeclareNamedIndividual(_)
+            | Axiom::DeclareDatatype(_)
+            | Axiom::AnnotationAssertion(_)
+            | Axiom::SubAnnotationPropertyOf(_)
+            | Axiom::AnnotationPropertyDomain(_)
+            | Axiom::AnnotationPropertyRange(_)
+    )
+}
+
+/// Check the compatibility of a new ontology release with an old one.
+pub fn check<'a, A, B>(old: A, new: B) -> CompatibilityReport
+where
+    A: IntoIterator<Item = &'a AnnotatedAxiom>,
+    B: IntoIterator<Item = &'a AnnotatedAxiom>,
+{
+    let old = old.into_iter().collect::<Vec<_>>();
+    let new = new.into_iter().collect::<Vec<_>>();
+
+    let mut old_inventory = Inventory::default();
+    old.iter()
+        .for_each(|aa| old_inventory.visit_annotated_axiom(aa));
+    let mut new_inventory = Inventory::default();
+    new.iter()
+        .for_each(|aa| new_inventory.visit_annotated_axiom(aa));
+
+    let mut changes = Vec::new();
+
+    // compare the entities of both releases
+    let old_kinds = old_inventory.kinds();
+    let new_kinds = new_inventory.kinds();
+    for (iri, old_k) in old_kinds.iter() {
+        match new_kinds.get(iri) {
+            None => changes.extend(
+                old_k
+                    .iter()
+                    .map(|k| Change::EntityRemoved(Entity::new(*k, (*iri).clone()))),
+            ),
+            Some(new_k) if !old_k.is_subset(new_k) => changes.push(Change::EntityKindChanged {
+                iri: (*iri).clone(),
+                old: old_k.clone(),
+                new: new_k.clone(),
+            }),
+            Some(new_k) => changes.extend(
+                new_k
+                    .difference(old_k)
+                    .map(|k| Change::EntityAdded(Entity::new(*k, (*iri).clone()))),
+            ),
+        }
+    }
+    for (iri, new_k) in new_kinds.iter() {
+        if !old_kinds.contains_key(iri) {
+            changes.extend(
+                new_k
+                    .iter()
+                    .map(|k| Change::EntityAdded(Entity::new(*k, (*iri).clone()))),
+            );
+        }
+    }
+
+    // compare the obsoletions of both releases
+    for iri in new_inventory
+        .deprecated
+        .difference(&old_inventory.deprecated)
+    {
+        if new_inventory.replaced.contains(iri) {
+            changes.push(Change::Obsoleted((*iri).clone()));
+        } else {
+            changes.push(Change::ObsoletedWithoutReplacement((*iri).clone()));
+        }
+    }
+
+    // compare the axioms of both releases
+    let diff = diff(old.iter().cloned(), new.iter().cloned());
+    for aa in diff.removed().iter() {
+        if is_logical(&aa.axiom) {
+            changes.push(Change::LogicalAxiomRemoved(aa.clone()));
+        } else {
+            changes.push(Change::NonLogicalAxiomRemoved(aa.clone()));
+        }
+    }
+    for aa in diff.added().iter() {
+        if is_logical(&aa.axiom) {
+            changes.push(Change::LogicalAxiomAdded(aa.clone()));
+        } else {
+            changes.push(Change::NonLogicalAxiomAdded(aa.clone()));
+        }
+    }
+    for change in diff.annotation_changes().iter() {
+        changes.push(Change::AnnotationsChanged(change.axiom().clone()));
+    }
+
+    CompatibilityReport { changes }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::fixtures::*;
+
+    fn annotation_assertion(subject: &str, property: &str, value: &str) -> AnnotatedAxiom {
+        AnnotationAssertion {
+            subject: AnnotationSubject::IRI(iri(subject)),
+            ann: Annotation {
+                ap: Build::new().annotation_property(property),
+                av: AnnotationValue::Literal(Literal::Simple {
+                    literal: value.into(),
+                }),
+            },
+        }
+        .into()
+    }
+
+    fn deprecated(subject: &str, value: &str) -> AnnotatedAxiom {
+        annotation_assertion(subject, AnnotationBuiltIn::DEPRECATED.iri_str(), value)
+    }
+
+    #[test]
+    fn levels_are_ordered() {
+        assert!(Compatibility::Identical < Compatibility::Patch);
+        assert!(Compatibility::Patch < Compatibility::Minor);
+        assert!(Compatibility::Minor < Compatibility::Major);
+    }
+
+    #[test]
+    fn identical() {
+        let old = ontology(vec![sub_class_of(class("A"), class("B"))]);
+        let report = check(&old, &old.clone());
+        assert!(report.changes().is_empty());
+        assert!(!report.is_breaking());
+        assert_eq!(report.verdict(), Compatibility::Identical);
+    }
+
+    #[test]
+    fn patch() {
+        let axiom = sub_class_of(class("A"), class("B"));
+        let comment = annotation_assertion("A", AnnotationBuiltIn::COMMENT.iri_str(), "A");
+        let old = vec![AnnotatedAxiom::from(axiom.clone()), comment.clone()];
+        let annotation = Annotation {
+            ap: Build::new().annotation_property(AnnotationBuiltIn::COMMENT.iri_str()),
+            av: AnnotationValue::IRI(iri("C")),
+        };
+        let new = vec![
+            AnnotatedAxiom::new(axiom.clone(), vec![annotation].into_iter().collect()),
+            comment,
+        ];
+        let report = check(&old, &new);
+        assert_eq!(report.changes(), &[Change::AnnotationsChanged(axiom)]);
+        assert_eq!(report.verdict(), Compatibility::Patch);
+    }
+
+    #[test]
+    fn minor() {
+        let old = ontology(vec![sub_class_of(class("A"), class("B"))]);
+        let mut new = old.clone();
+        new.insert(sub_class_of(class("C"), class("B")));
+        let report = check(&old, &new);
+        assert!(report.changes().contains(&Change::EntityAdded(Entity::new(
+            NamedEntityKind::Class,
+            iri("C")
+        ))));
+        assert!(report.changes().contains(&Change::LogicalAxiomAdded(
+            sub_class_of(class("C"), class("B")).into()
+        )));
+        assert!(!report.is_breaking());
+        assert_eq!(report.verdict(), Compatibility::Minor);
+    }
+
+    #[test]
+    fn major() {
+        let old = ontology(vec![
+            sub_class_of(class("A"), class("B")),
+            sub_class_of(class("C"), class("B")),
+        ]);
+        let new = ontology(vec![sub_class_of(class("A"), class("B"))]);
+        let report = check(&old, &new);
+        let breaking = report.breaking().cloned().collect::<Vec<_>>();
+        assert_eq!(breaking.len(), 2);
+        assert!(breaking.contains(&Change::EntityRemoved(Entity::new(
+            NamedEntityKind::Class,
+            iri("C")
+        ))));
+        assert!(breaking.contains(&Change::LogicalAxiomRemoved(
+            sub_class_of(class("C"), class("B")).into()
+        )));
+        assert_eq!(report.verdict(), Compatibility::Major);
+    }
+
+    #[test]
+    fn entity_kind_changed() {
+        let old = vec![AnnotatedAxiom::from(DeclareClass(named_class("A")))];
+        let new = vec![AnnotatedAxiom::from(DeclareNamedIndividual(
+            NamedIndividual(iri("A")),
+        ))];
+        let report = check(&old, &new);
+        assert!(report.changes().contains(&Change::EntityKindChanged {
+            iri: iri("A"),
+            old: vec![NamedEntityKind::Class].into_iter().collect(),
+            new: vec![NamedEntityKind::NamedIndividual].into_iter().collect(),
+        }));
+        assert_eq!(report.verdict(), Compatibility::Major);
+    }
+
+    #[test]
+    fn punning_is_additive() {
+        let old = vec![AnnotatedAxiom::from(DeclareClass(named_class("A")))];
+        let mut new = old.clone();
+        new.push(DeclareNamedIndividual(NamedIndividual(iri("A"))).into());
+        let report = check(&old, &new);
+        assert!(report.changes().contains(&Change::EntityAdded(Entity::new(
+            NamedEntityKind::NamedIndividual,
+            iri("A")
+        ))));
+        assert_eq!(report.verdict(), Compatibility::Minor);
+    }
+
+    #[test]
+    fn obsoleted_without_replacement() {
+        let old = vec![AnnotatedAxiom::from(DeclareClass(named_class("A")))];
+        let mut new = old.clone();
+        new.push(deprecated("A", "true"));
+        let report = check(&old, &new);
+        assert!(report
+            .changes()
+            .contains(&Change::ObsoletedWithoutReplacement(iri("A"))));
+        assert!(!report.changes().contains(&Change::Obsoleted(iri("A"))));
+        assert_eq!(report.verdict(), Compatibility::Major);
+    }
+
+    #[test]
+    fn obsoleted_with_replacement() {
+        let old = vec![AnnotatedAxiom::from(DeclareClass(named_class("A")))];
+        let mut new = old.clone();
+        new.push(deprecated("A", "1"));
+        new.push(annotation_assertion(
+            "A",
+            REPLACED_BY,
+            "http://example.com/B",
+        ));
+        let report = check(&old, &new);
+        assert!(report.changes().contains(&Change::Obsoleted(iri("A"))));
+        assert!(!report
+            .changes()
+            .contains(&Change::ObsoletedWithoutReplacement(iri("A"))));
+        assert_eq!(report.verdict(), Compatibility::Minor);
+    }
+
+    #[test]
+    fn obsoletion_is_only_reported_once() {
+        let old = vec![
+            AnnotatedAxiom::from(DeclareClass(named_class("A"))),
+            deprecated("A", "true"),
+        ];
+        let mut new = old.clone();
+        new.push(DeclareClass(named_class("B")).into());
+        let report = check(&old, &new);
+        assert!(!report
+            .changes()
+            .contains(&Change::ObsoletedWithoutReplacement(iri("A"))));
+        assert_eq!(report.verdict(), Compatibility::Minor);
+    }
+
+    #[test]
+    fn deprecated_false_is_not_an_obsoletion() {
+        let old = vec![AnnotatedAxiom::from(DeclareClass(named_class("A")))];
+        let mut new = old.clone();
+        new.push(deprecated("A", "false"));
+        let report = check(&old, &new);
+        assert!(!report
+            .changes()
+            .iter()
+            .any(|c| matches!(c, Change::ObsoletedWithoutReplacement(_))));
+        assert!(!report.is_breaking());
+    }
+}
@@ -0,0 +1,357 @@
+//! Structural differences between two versions of an ontology.
+//!
+//! Two ontologies are compared axiom by axiom: an axiom is *removed* or
+//! *added* when its logical part ([`Axiom`]) only appears in one of the
+//! ontologies, and its annotations are *changed* when the same logical
+//! axiom appears in both ontologies with different annotations.
+//!
+//! A [`Diff`] can be rendered as plain text or Markdown, grouped by the
+//! entities each axiom refers to, or turned into a [`Patch`] that can be
+//! written to a file, read back, and applied to the old ontology to obtain
+//! the new one.
+//!
+//! # Example
+//!
+//! ```rust
+//! # extern crate horned_owl;
+//! # extern crate horned_visit;
+//! use horned_owl::model::*;
+//! use horned_owl::ontology::set::SetOntology;
+//!
+//! let b = Build::new();
+//! let mut old = SetOntology::new();
+//! old.insert(SubClassOf {
+//!     sub: b.class("http://example.com/A").into(),
+//!     sup: b.class("http://example.com/B").into(),
+//! });
+//!
+//! let mut new = SetOntology::new();
+//! new.insert(SubClassOf {
+//!     sub: b.class("http://example.com/A").into(),
+//!     sup: b.class("http://example.com/C").into(),
+//! });
+//!
+//! let diff = horned_visit::diff::diff(&old, &new);
+//! assert_eq!(diff.removed().len(), 1);
+//! assert_eq!(diff.added().len(), 1);
+//!
+//! diff.apply(&mut old);
+//! assert_eq!(old, new);
+//! ```
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+
+use horned_owl::model::*;
+
+use super::display::Functional;
+use super::patch::Patch;
+use super::signature::signature;
+
+/// A change in the annotations of a logical axiom present in both ontologies.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AnnotationChange {
+    axiom: Axiom,
+    removed: BTreeSet<AnnotatedAxiom>,
+    added: BTreeSet<AnnotatedAxiom>,
+}
+
+impl AnnotationChange {
+    /// Get the logical axiom whose annotations changed.
+    pub fn axiom(&self) -> &Axiom {
+        &self.axiom
+    }
+
+    /// Get the annotated versions of the axiom only found in the old ontology.
+    pub fn removed(&self) -> &BTreeSet<AnnotatedAxiom> {
+        &self.removed
+    }
+
+    /// Get the annotated versions of the axiom only found in the new ontology.
+    pub fn added(&self) -> &BTreeSet<AnnotatedAxiom> {
+        &self.added
+    }
+
+    /// Get the annotations of the axiom only found in the old ontology.
+    pub fn removed_annotations(&self) -> BTreeSet<&Annotation> {
+        let old = self
+            .removed
+            .iter()
+            .flat_map(|aa| aa.ann.iter())
+            .collect::<BTreeSet<_>>();
+        let new = self
+            .added
+            .iter()
+            .flat_map(|aa| aa.ann.iter())
+            .collect::<BTreeSet<_>>();
+        old.difference(&new).cloned().collect()
+    }
+
+    /// Get the annotations of the axiom only found in the new ontology.
+    pub fn added_annotations(&self) -> BTreeSet<&Annotation> {
+        let old = self
+            .removed
+            .iter()
+            .flat_map(|aa| aa.ann.iter())
+            .collect::<BTreeSet<_>>();
+        let new = self
+            .added
+            .iter()
+            .flat_map(|aa| aa.ann.iter())
+            .collect::<BTreeSet<_>>();
+        new.difference(&old).cloned().collect()
+    }
+}
+
+/// The changes of a [`Diff`] that concern a single entity.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EntityDiff<'d> {
+    pub removed: Vec<&'d AnnotatedAxiom>,
+    pub added: Vec<&'d AnnotatedAxiom>,
+    pub annotation_changes: Vec<&'d AnnotationChange>,
+}
+
+/// The structural differences between two ontologies.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Diff {
+    removed: BTreeSet<AnnotatedAxiom>,
+    added: BTreeSet<AnnotatedAxiom>,
+    annotation_changes: Vec<AnnotationChange>,
+}
+
+impl Diff {
+    /// Get the axioms whose logical part is only in the old ontology.
+    pub fn removed(&self) -> &BTreeSet<AnnotatedAxiom> {
+        &self.removed
+    }
+
+    /// Get the axioms whose logical part is only in the new ontology.
+    pub fn added(&self) -> &BTreeSet<AnnotatedAxiom> {
+        &self.added
+    }
+
+    /// Get the axioms found in both ontologies with different annotations.
+    pub fn annotation_changes(&self) -> &[AnnotationChange] {
+        &self.annotation_changes
+    }
+
+    /// Check whether the two compared ontologies are structurally equal.
+    pub fn is_empty(&self) -> bool {
+        self.removed.is_empty() && self.added.is_empty() && self.annotation_changes.is_empty()
+    }
+
+    /// Group the changes by the entity they refer to.
+    ///
+    /// Annotation assertions are grouped under their subject, other axioms
+    /// under every entity of their signature. Axioms without any entity,
+    /// such as ontology annotations and imports, are grouped under `None`.
+    pub fn by_entity(&self) -> BTreeMap<Option<IRI>, EntityDiff<'_>> {
+        let mut groups = BTreeMap::<Option<IRI>, EntityDiff<'_>>::new();
+        for aa in self.removed.iter() {
+            for key in group_keys(&aa.axiom) {
+                groups.entry(key).or_default().removed.push(aa);
+            }
+        }
+        for aa in self.added.iter() {
+            for key in group_keys(&aa.axiom) {
+                groups.entry(key).or_default().added.push(aa);
+            }
+        }
+        for change in self.annotation_changes.iter() {
+            for key in group_keys(&change.axiom) {
+                groups
+                    .entry(key)
+                    .or_default()
+                    .annotation_changes
+                    .push(change);
+            }
+        }
+        groups
+    }
+
+    /// Get the patch turning the old ontology into the new ontology.
+    pub fn patch(&self) -> Patch {
+        let mut patch = Patch::new();
+        for aa in self.removed.iter() {
+            patch.remove(aa.clone());
+        }
+        for aa in self.added.iter() {
+            patch.insert(aa.clone());
+        }
+        for change in self.annotation_changes.iter() {
+            change
+                .removed
+                .iter()
+                .for_each(|aa| patch.remove(aa.clone()));
+            change.added.iter().for_each(|aa| patch.insert(aa.clone()));
+        }
+        patch
+    }
+
+    /// Apply the changes to an ontology.
+    pub fn apply<O: MutableOntology + ?Sized>(&self, ontology: &mut O) {
+        self.patch().apply(ontology)
+    }
+
+    /// Get a plain-text report of the changes.
+    ///
+    /// Removed axioms are prefixed with `-`, added axioms with `+`, and
+    /// axioms with changed annotations with `~`.
+    pub fn text(&self) -> Text<'_> {
+        Text(self)
+    }
+
+    /// Get a Markdown report of the changes, grouped by entity.
+    pub fn markdown(&self) -> Markdown<'_> {
+        Markdown(self)
+    }
+}
+
+/// Compare two ontologies.
+pub fn diff<'a, A, B>(old: A, new: B) -> Diff
+where
+    A: IntoIterator<Item = &'a AnnotatedAxiom>,
+    B: IntoIterator<Item = &'a AnnotatedAxiom>,
+{
+    let mut old_axioms = BTreeMap::<&Axiom, BTreeSet<&AnnotatedAxiom>>::new();
+    for aa in old.into_iter() {
+        old_axioms.entry(&aa.axiom).or_default().insert(aa);
+    }
+    let mut new_axioms = BTreeMap::<&Axiom, BTreeSet<&AnnotatedAxiom>>::new();
+    for aa in new.into_iter() {
+        new_axioms.entry(&aa.axiom).or_default().insert(aa);
+    }
+
+    let mut diff = Diff::default();
+    for (axiom, old_versions) in old_axioms.iter() {
+        match new_axioms.get(axiom) {
+            None => diff.removed.extend(old_versions.iter().cloned().cloned()),
+            Some(new_versions) if new_versions != old_versions => {
+                diff.annotation_changes.push(AnnotationChange {
+                    axiom: (*axiom).clone(),
+                    removed: old_versions
+                        .difference(new_versions)
+                        .cloned()
+                        .cloned()
+                        .collect(),
+                    added: new_versions
+                        .difference(old_versions)
+                        .cloned()
+                        .cloned()
+                        .collect(),
+                });
+            }
+            Some(_) => (),
+        }
+    }
+    for (axiom, new_versions) in new_axioms.iter() {
+        if !old_axioms.contains_key(axiom) {
+            diff.added.extend(new_versions.iter().cloned().cloned());
+        }
+    }
+
+    diff
+}
+
+/// Get the keys under which the changes to an axiom are grouped.
+fn group_keys(axiom: &Axiom) -> BTreeSet<Option<IRI>> {
+    let keys: BTreeSet<Option<IRI>> = match axiom {
+        Axiom::AnnotationAssertion(aa) => match &aa.subject {
+            AnnotationSubject::IRI(iri) => Some(Some(iri.clone())).into_iter().collect(),
+            AnnotationSubject::AnonymousIndividual(_) => BTreeSet::new(),
+        },
+        _ => signature(axiom).into_iter().map(|e| Some(e.iri)).collect(),
+    };
+    if keys.is_empty() {
+        Some(None).into_iter().collect()
+    } else {
+        keys
+    }
+}
+
+/// A plain-text report of a [`Diff`].
+#[derive(Clone, Copy, Debug)]
+pub struct Text<'d>(&'d Diff);
+
+impl Display for Text<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        for aa in self.0.removed.iter() {
+            writeln!(f, "- {}", Functional(aa))?;
+        }
+        for aa in self.0.added.iter() {
+            writeln!(f, "+ {}", Functional(aa))?;
+        }
+        for change in self.0.annotation_changes.iter() {
+            writeln!(f, "~ {}", Functional(&change.axiom))?;
+            for annotation in change.removed_annotations() {
+                writeln!(f, "  - {}", Functional(annotation))?;
+            }
+            for annotation in change.added_annotations() {
+                writeln!(f, "  + {}", Functional(annotation))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A Markdown report of a [`Diff`].
+#[derive(Clone, Copy, Debug)]
+pub struct Markdown<'d>(&'d Diff);
+
+impl Display for Markdown<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        writeln!(f, "# Ontology comparison")?;
+        writeln!(f)?;
+        writeln!(f, "## Summary")?;
+        writeln!(f)?;
+        writeln!(f, "- Removed axioms: {}", self.0.removed.len())?;
+        writeln!(f, "- Added axioms: {}", self.0.added.len())?;
+        writeln!(
+            f,
+            "- Axioms with changed annotations: {}",
+            self.0.annotation_changes.len()
+        )?;
+
+        for (key, group) in self.0.by_entity() {
+            writeln!(f)?;
+            match key {
+                Some(iri) => writeln!(f, "## <{}>", iri)?,
+                None => writeln!(f, "## Ontology")?,
+            }
+            if !group.removed.is_empty() {
+                writeln!(f)?;
+                writeln!(f, "### Removed")?;
+                writeln!(f)?;
+                for aa in group.removed {
+                    writeln!(f, "- `{}`", Functional(aa))?;
+                }
+            }
+            if !group.added.is_empty() {
+                writeln!(f)?;
+                writeln!(f, "### Added")?;
+                writeln!(f)?;
+                for aa in group.added {
+                    writeln!(f, "- `{}`", Functional(aa))?;
+                }
+            }
+            if !group.annotation_changes.is_empty() {
+                writeln!(f)?;
+                writeln!(f, "### Annotations changed")?;
+                writeln!(f)?;
+                for change in group.annotation_changes {
+                    writeln!(f, "- `{}`", Functional(&change.axiom))?;
+                    for annotation in change.removed_annotations() {
+                        writeln!(f, "  - Removed: `{}`", Functional(annotation))?;
+                    }
+                    for annotation in change.added_annotations() {
+                        writeln!(f, "  - Added: `{}`", Functional(annotation))?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,704 @@
+//! Render ontology elements in the OWL2 functional-style syntax.
+//!
+//! The [`Functional`] wrapper implements [`Display`](std::fmt::Display)
+//! for most elements of the [`horned_owl::model`], which is convenient to
+//! write human-readable reports:
+//!
+//! ```rust
+//! # extern crate horned_owl;
+//! # extern crate horned_visit;
+//! use horned_owl::model::*;
+//! use horned_visit::display::Functional;
+//!
+//! let b = Build::new();
+//! let axiom = Axiom::from(SubClassOf {
+//!     sub: b.class("http://example.com/A").into(),
+//!     sup: b.class("http://example.com/B").into(),
+//! });
+//! assert_eq!(
+//!     Functional(&axiom).to_string(),
+//!     "SubClassOf(<http://example.com/A> <http://example.com/B>)",
+//! );
+//! ```
+
+use std::collections::BTreeSet;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+use std::fmt::Write;
+
+use horned_owl::model::*;
+use horned_owl::vocab::WithIRI;
+
+use super::Visit;
+
+/// A wrapper to display an element in the OWL2 functional-style syntax.
+#[derive(Clone, Copy, Debug)]
+pub struct Functional<'a, T: ?Sized>(pub &'a T);
+
+macro_rules! impl_display {
+    ($($name:ident($type:ty),)*) => {
+        paste! {
+            $(
+                impl Display for Functional<'_, $type> {
+                    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+                        let mut writer = FunctionalWriter::new(f);
+                        writer.[<visit_ $name>](self.0);
+                        writer.result
+                    }
+                }
+            )*
+        }
+    }
+}
+
+impl_display! {
+    annotated_axiom(AnnotatedAxiom),
+    annotation(Annotation),
+    annotation_property(AnnotationProperty),
+    annotation_subject(AnnotationSubject),
+    annotation_value(AnnotationValue),
+    anonymous_individual(AnonymousIndividual),
+    axiom(Axiom),
+    class(Class),
+    class_expression(ClassExpression),
+    data_property(DataProperty),
+    data_range(DataRange),
+    datatype(Datatype),
+    individual(Individual),
+    iri(IRI),
+    literal(Literal),
+    named_individual(NamedIndividual),
+    object_property(ObjectProperty),
+    object_property_expression(ObjectPropertyExpression),
+}
+
+/// A visitor writing the visited elements to a formatter.
+struct FunctionalWriter<'f, 'a, 'ast> {
+    f: &'f mut Formatter<'a>,
+    result: FmtResult,
+    /// Whether a separator is needed before the next element.
+    separate: bool,
+    /// The annotations to write in the next axiom.
+    annotations: Option<&'ast BTreeSet<Annotation>>,
+}
+
+impl<'f, 'a, 'ast> FunctionalWriter<'f, 'a, 'ast> {
+    fn new(f: &'f mut Formatter<'a>) -> Self {
+        Self {
+            f,
+            result: Ok(()),
+            separate: false,
+            annotations: None,
+        }
+    }
+
+    fn write_str(&mut self, s: &str) {
+        if self.result.is_ok() {
+            self.result = self.f.write_str(s);
+        }
+    }
+
+    fn separator(&mut self) {
+        if self.separate {
+            self.write_str(" ");
+        }
+    }
+
+    fn atom(&mut self, s: &str) {
+        self.separator();
+        self.write_str(s);
+        self.separate = true;
+    }
+
+    fn quoted(&mut self, s: &str) {
+        self.separator();
+        self.write_str("\"");
+        for c in s.chars() {
+            if self.result.is_ok() {
+                self.result = match c {
+                    '"' => self.f.write_str("\\\""),
+                    '\\' => self.f.write_str("\\\\"),
+                    c => self.f.write_char(c),
+                };
+            }
+        }
+        self.write_str("\"");
+        self.separate = true;
+    }
+
+    fn iri_ref(&mut self, iri: &str) {
+        self.separator();
+        self.write_str("<");
+        self.write_str(iri);
+        self.write_str(">");
+        self.separate = true;
+    }
+
+    fn open(&mut self, name: &str) {
+        self.separator();
+        self.write_str(name);
+        self.write_str("(");
+        self.separate = false;
+    }
+
+    fn close(&mut self) {
+        self.write_str(")");
+        self.separate = true;
+    }
+
+    /// Open an axiom, writing the pending axiom annotations if any.
+    fn open_axiom(&mut self, name: &str) {
+        self.open(name);
+        if let Some(annotations) = self.annotations.take() {
+            for annotation in annotations.iter() {
+                self.visit_annotation(annotation);
+            }
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for FunctionalWriter<'_, '_, 'ast> {
+    fn visit_annotated_axiom(&mut self, annotated_axiom: &'ast AnnotatedAxiom) {
+        self.annotations = Some(&annotated_axiom.ann);
+        self.visit_axiom(&annotated_axiom.axiom);
+    }
+
+    fn visit_annotation(&mut self, annotation: &'ast Annotation) {
+        self.open("Annotation");
+        self.visit_annotation_property(&annotation.ap);
+        self.visit_annotation_value(&annotation.av);
+        self.close();
+    }
+
+    fn visit_annotation_assertion(&mut self, annotation_assertion: &'ast AnnotationAssertion) {
+        self.open_axiom("AnnotationAssertion");
+        self.visit_annotation_property(&annotation_assertion.ann.ap);
+        self.visit_annotation_subject(&annotation_assertion.subject);
+        self.visit_annotation_value(&annotation_assertion.ann.av);
+        self.close();
+    }
+
+    fn visit_annotation_property_domain(&mut self, apd: &'ast AnnotationPropertyDomain) {
+        self.open_axiom("AnnotationPropertyDomain");
+        self.visit_annotation_property(&apd.ap);
+        self.visit_iri(&apd.iri);
+        self.close();
+    }
+
+    fn visit_annotation_property_range(&mut self, apr: &'ast AnnotationPropertyRange) {
+        self.open_axiom("AnnotationPropertyRange");
+        self.visit_annotation_property(&apr.ap);
+        self.visit_iri(&apr.iri);
+        self.close();
+    }
+
+    fn visit_anonymous_individual(&mut self, anonymous_individual: &'ast AnonymousIndividual) {
+        self.separator();
+        self.write_str("_:");
+        self.write_str(anonymous_individual);
+        self.separate = true;
+    }
+
+    fn visit_asymmetric_object_property(&mut self, aop: &'ast AsymmetricObjectProperty) {
+        self.open_axiom("AsymmetricObjectProperty");
+        self.visit_object_property_expression(&aop.0);
+        self.close();
+    }
+
+    fn visit_class_assertion(&mut self, class_assertion: &'ast ClassAssertion) {
+        self.open_axiom("ClassAssertion");
+        self.visit_class_expression(&class_assertion.ce);
+        self.visit_individual(&class_assertion.i);
+        self.close();
+    }
+
+    fn visit_class_expression(&mut self, class_expression: &'ast ClassExpression) {
+        use self::ClassExpression::*;
+        match class_expression {
+            Class(c) => self.visit_class(c),
+            ObjectIntersectionOf(ces) => {
+                self.open("ObjectIntersectionOf");
+                ces.iter().for_each(|ce| self.visit_class_expression(ce));
+                self.close();
+            }
+            ObjectUnionOf(ces) => {
+                self.open("ObjectUnionOf");
+                ces.iter().for_each(|ce| self.visit_class_expression(ce));
+                self.close();
+            }
+            ObjectComplementOf(ce) => {
+                self.open("ObjectComplementOf");
+                self.visit_class_expression(ce);
+                self.close();
+            }
+            ObjectOneOf(is) => {
+                self.open("ObjectOneOf");
+                is.iter().for_each(|i| self.visit_individual(i));
+                self.close();
+            }
+            ObjectSomeValuesFrom { ope, bce } => {
+                self.open("ObjectSomeValuesFrom");
+                self.visit_object_property_expression(ope);
+                self.visit_class_expression(bce);
+                self.close();
+            }
+            ObjectAllValuesFrom { ope, bce } => {
+                self.open("ObjectAllValuesFrom");
+                self.visit_object_property_expression(ope);
+                self.visit_class_expression(bce);
+                self.close();
+            }
+            ObjectHasValue { ope, i } => {
+                self.open("ObjectHasValue");
+                self.visit_object_property_expression(ope);
+                self.visit_individual(i);
+                self.close();
+            }
+            ObjectHasSelf(ope) => {
+                self.open("ObjectHasSelf");
+                self.visit_object_property_expression(ope);
+                self.close();
+            }
+            ObjectMinCardinality { n, ope, bce } => {
+                self.open("ObjectMinCardinality");
+                self.atom(&n.to_string());
+                self.visit_object_property_expression(ope);
+                self.visit_class_expression(bce);
+                self.close();
+            }
+            ObjectMaxCardinality { n, ope, bce } => {
+                self.open("ObjectMaxCardinality");
+                self.atom(&n.to_string());
+                self.visit_object_property_expression(ope);
+                self.visit_class_expression(bce);
+                self.close();
+            }
+            ObjectExactCardinality { n, ope, bce } => {
+                self.open("ObjectExactCardinality");
+                self.atom(&n.to_string());
+                self.visit_object_property_expression(ope);
+                self.visit_class_expression(bce);
+                self.close();
+            }
+            DataSomeValuesFrom { dp, dr } => {
+                self.open("DataSomeValuesFrom");
+                self.visit_data_property(dp);
+                self.visit_data_range(dr);
+                self.close();
+            }
+            DataAllValuesFrom { dp, dr } => {
+                self.open("DataAllValuesFrom");
+                self.visit_data_property(dp);
+                self.visit_data_range(dr);
+                self.close();
+            }
+            DataHasValue { dp, l } => {
+                self.open("DataHasValue");
+                self.visit_data_property(dp);
+                self.visit_literal(l);
+                self.close();
+            }
+            DataMinCardinality { n, dp, dr } => {
+                self.open("DataMinCardinality");
+                self.atom(&n.to_string());
+                self.visit_data_property(dp);
+                self.visit_data_range(dr);
+                self.close();
+            }
+            DataMaxCardinality { n, dp, dr } => {
+                self.open("DataMaxCardinality");
+                self.atom(&n.to_string());
+                self.visit_data_property(dp);
+                self.visit_data_range(dr);
+                self.close();
+            }
+            DataExactCardinality { n, dp, dr } => {
+                self.open("DataExactCardinality");
+                self.atom(&n.to_string());
+                self.visit_data_property(dp);
+                self.visit_data_range(dr);
+                self.close();
+            }
+        }
+    }
+
+    fn visit_data_property_assertion(&mut self, dpa: &'ast DataPropertyAssertion) {
+        self.open_axiom("DataPropertyAssertion");
+        self.visit_data_property(&dpa.dp);
+        self.visit_individual(&dpa.from);
+        self.visit_literal(&dpa.to);
+        self.close();
+    }
+
+    fn visit_data_property_domain(&mut self, dpd: &'ast DataPropertyDomain) {
+        self.open_axiom("DataPropertyDomain");
+        self.visit_data_property(&dpd.dp);
+        self.visit_class_expression(&dpd.ce);
+        self.close();
+    }
+
+    fn visit_data_property_range(&mut self, dpr: &'ast DataPropertyRange) {
+        self.open_axiom("DataPropertyRange");
+        self.visit_data_property(&dpr.dp);
+        self.visit_data_range(&dpr.dr);
+        self.close();
+    }
+
+    fn visit_data_range(&mut self, data_range: &'ast DataRange) {
+        use self::DataRange::*;
+        match data_range {
+            Datatype(dt) => self.visit_datatype(dt),
+            DataIntersectionOf(drs) => {
+                self.open("DataIntersectionOf");
+                drs.iter().for_each(|dr| self.visit_data_range(dr));
+                self.close();
+            }
+            DataUnionOf(drs) => {
+                self.open("DataUnionOf");
+                drs.iter().for_each(|dr| self.visit_data_range(dr));
+                self.close();
+            }
+            DataComplementOf(dr) => {
+                self.open("DataComplementOf");
+                self.visit_data_range(dr);
+                self.close();
+            }
+            DataOneOf(lits) => {
+                self.open("DataOneOf");
+                lits.iter().for_each(|lit| self.visit_literal(lit));
+                self.close();
+            }
+            DatatypeRestriction(dt, frs) => {
+                self.open("DatatypeRestriction");
+                self.visit_datatype(dt);
+                frs.iter().for_each(|fr| self.visit_facet_restriction(fr));
+                self.close();
+            }
+        }
+    }
+
+    fn visit_datatype_definition(&mut self, dd: &'ast DatatypeDefinition) {
+        self.open_axiom("DatatypeDefinition");
+        self.visit_datatype(&dd.kind);
+        self.visit_data_range(&dd.range);
+        self.close();
+    }
+
+    fn visit_declare_annotation_property(&mut self, dap: &'ast DeclareAnnotationProperty) {
+        self.open_axiom("Declaration");
+        self.open("AnnotationProperty");
+        self.visit_annotation_property(&dap.0);
+        self.close();
+        self.close();
+    }
+
+    fn visit_declare_class(&mut self, dc: &'ast DeclareClass) {
+        self.open_axiom("Declaration");
+        self.open("Class");
+        self.visit_class(&dc.0);
+        self.close();
+        self.close();
+    }
+
+    fn visit_declare_datatype(&mut self, ddt: &'ast DeclareDatatype) {
+        self.open_axiom("Declaration");
+        self.open("Datatype");
+        self.visit_datatype(&ddt.0);
+        self.close();
+        self.close();
+    }
+
+    fn visit_declare_data_property(&mut self, ddp: &'ast DeclareDataProperty) {
+        self.open_axiom("Declaration");
+        self.open("DataProperty");
+        self.visit_data_property(&ddp.0);
+        self.close();
+        self.close();
+    }
+
+    fn visit_declare_named_individual(&mut self, dni: &'ast DeclareNamedIndividual) {
+        self.open_axiom("Declaration");
+        self.open("NamedIndividual");
+        self.visit_named_individual(&dni.0);
+        self.close();
+        self.close();
+    }
+
+    fn visit_declare_object_property(&mut self, dop: &'ast DeclareObjectProperty) {
+        self.open_axiom("Declaration");
+        self.open("ObjectProperty");
+        self.visit_object_property(&dop.0);
+        self.close();
+        self.close();
+    }
+
+    fn visit_different_individuals(&mut self, di: &'ast DifferentIndividuals) {
+        self.open_axiom("DifferentIndividuals");
+        di.0.iter().for_each(|i| self.visit_individual(i));
+        self.close();
+    }
+
+    fn visit_disjoint_classes(&mut self, dc: &'ast DisjointClasses) {
+        self.open_axiom("DisjointClasses");
+        dc.0.iter().for_each(|ce| self.visit_class_expression(ce));
+        self.close();
+    }
+
+    fn visit_disjoint_data_properties(&mut self, ddp: &'ast DisjointDataProperties) {
+        self.open_axiom("DisjointDataProperties");
+        ddp.0.iter().for_each(|dp| self.visit_data_property(dp));
+        self.close();
+    }
+
+    fn visit_disjoint_object_properties(&mut self, dop: &'ast DisjointObjectProperties) {
+        self.open_axiom("DisjointObjectProperties");
+        dop.0
+            .iter()
+            .for_each(|ope| self.visit_object_property_expression(ope));
+        self.close();
+    }
+
+    fn visit_disjoint_union(&mut self, du: &'ast DisjointUnion) {
+        self.open_axiom("DisjointUnion");
+        self.visit_class(&du.0);
+        du.1.iter().for_each(|ce| self.visit_class_expression(ce));
+        self.close();
+    }
+
+    fn visit_equivalent_classes(&mut self, ec: &'ast EquivalentClasses) {
+        self.open_axiom("EquivalentClasses");
+        ec.0.iter().for_each(|ce| self.visit_class_expression(ce));
+        self.close();
+    }
+
+    fn visit_equivalent_data_properties(&mut self, edp: &'ast EquivalentDataProperties) {
+        self.open_axiom("EquivalentDataProperties");
+        edp.0.iter().for_each(|dp| self.visit_data_property(dp));
+        self.close();
+    }
+
+    fn visit_equivalent_object_properties(&mut self, eop: &'ast EquivalentObjectProperties) {
+        self.open_axiom("EquivalentObjectProperties");
+        eop.0
+            .iter()
+            .for_each(|ope| self.visit_object_property_expression(ope));
+        self.close();
+    }
+
+    fn visit_facet(&mut self, facet: &'ast Facet) {
+        self.iri_ref(facet.iri_str());
+    }
+
+    fn visit_functional_data_property(&mut self, fdp: &'ast FunctionalDataProperty) {
+        self.open_axiom("FunctionalDataProperty");
+        self.visit_data_property(&fdp.0);
+        self.close();
+    }
+
+    fn visit_functional_object_property(&mut self, fop: &'ast FunctionalObjectProperty) {
+        self.open_axiom("FunctionalObjectProperty");
+        self.visit_object_property_expression(&fop.0);
+        self.close();
+    }
+
+    fn visit_has_key(&mut self, has_key: &'ast HasKey) {
+        self.open_axiom("HasKey");
+        self.visit_class_expression(&has_key.ce);
+        self.open("");
+        for pe in has_key.vpe.iter() {
+            if let PropertyExpression::ObjectPropertyExpression(ope) = pe {
+                self.visit_object_property_expression(ope);
+            }
+        }
+        self.close();
+        self.open("");
+        for pe in has_key.vpe.iter() {
+            if let PropertyExpression::DataProperty(dp) = pe {
+                self.visit_data_property(dp);
+            }
+        }
+        self.close();
+        self.close();
+    }
+
+    fn visit_import(&mut self, import: &'ast Import) {
+        self.open("Import");
+        self.visit_iri(&import.0);
+        self.close();
+    }
+
+    fn visit_inverse_functional_object_property(
+        &mut self,
+        ifop: &'ast InverseFunctionalObjectProperty,
+    ) {
+        self.open_axiom("InverseFunctionalObjectProperty");
+        self.visit_object_property_expression(&ifop.0);
+        self.close();
+    }
+
+    fn visit_inverse_object_properties(&mut self, iop: &'ast InverseObjectProperties) {
+        self.open_axiom("InverseObjectProperties");
+        self.visit_object_property(&iop.0);
+        self.visit_object_property(&iop.1);
+        self.close();
+    }
+
+    fn visit_iri(&mut self, iri: &'ast IRI) {
+        self.iri_ref(iri);
+    }
+
+    fn visit_irreflexive_object_property(&mut self, iop: &'ast IrreflexiveObjectProperty) {
+        self.open_axiom("IrreflexiveObjectProperty");
+        self.visit_object_property_expression(&iop.0);
+        self.close();
+    }
+
+    fn visit_literal(&mut self, literal: &'ast Literal) {
+        match literal {
+            Literal::Simple { literal } => {
+                self.quoted(literal);
+            }
+            Literal::Language { literal, lang } => {
+                self.quoted(literal);
+                self.write_str("@");
+                self.write_str(lang);
+            }
+            Literal::Datatype {
+                literal,
+                datatype_iri,
+            } => {
+                self.quoted(literal);
+                self.write_str("^^<");
+                self.write_str(datatype_iri);
+                self.write_str(">");
+            }
+        }
+    }
+
+    fn visit_negative_data_property_assertion(
+        &mut self,
+        ndpa: &'ast NegativeDataPropertyAssertion,
+    ) {
+        self.open_axiom("NegativeDataPropertyAssertion");
+        self.visit_data_property(&ndpa.dp);
+        self.visit_individual(&ndpa.from);
+        self.visit_literal(&ndpa.to);
+        self.close();
+    }
+
+    fn visit_negative_object_property_assertion(
+        &mut self,
+        nopa: &'ast NegativeObjectPropertyAssertion,
+    ) {
+        self.open_axiom("NegativeObjectPropertyAssertion");
+        self.visit_object_property_expression(&nopa.ope);
+        self.visit_individual(&nopa.from);
+        self.visit_individual(&nopa.to);
+        self.close();
+    }
+
+    fn visit_object_property_assertion(&mut self, opa: &'ast ObjectPropertyAssertion) {
+        self.open_axiom("ObjectPropertyAssertion");
+        self.visit_object_property_expression(&opa.ope);
+        self.visit_individual(&opa.from);
+        self.visit_individual(&opa.to);
+        self.close();
+    }
+
+    fn visit_object_property_domain(&mut self, opd: &'ast ObjectPropertyDomain) {
+        self.open_axiom("ObjectPropertyDomain");
+        self.visit_object_property_expression(&opd.ope);
+        self.visit_class_expression(&opd.ce);
+        self.close();
+    }
+
+    fn visit_object_property_expression(&mut self, ope: &'ast ObjectPropertyExpression) {
+        match ope {
+            ObjectPropertyExpression::ObjectProperty(op) => self.visit_object_property(op),
+            ObjectPropertyExpression::InverseObjectProperty(op) => {
+                self.open("ObjectInverseOf");
+                self.visit_object_property(op);
+                self.close();
+            }
+        }
+    }
+
+    fn visit_object_property_range(&mut self, opr: &'ast ObjectPropertyRange) {
+        self.open_axiom("ObjectPropertyRange");
+        self.visit_object_property_expression(&opr.ope);
+        self.visit_class_expression(&opr.ce);
+        self.close();
+    }
+
+    fn visit_ontology_annotation(&mut self, oa: &'ast OntologyAnnotation) {
+        self.annotations = None;
+        self.visit_annotation(&oa.0);
+    }
+
+    fn visit_reflexive_object_property(&mut self, rop: &'ast ReflexiveObjectProperty) {
+        self.open_axiom("ReflexiveObjectProperty");
+        self.visit_object_property_expression(&rop.0);
+        self.close();
+    }
+
+    fn visit_same_individual(&mut self, si: &'ast SameIndividual) {
+        self.open_axiom("SameIndividual");
+        si.0.iter().for_each(|i| self.visit_individual(i));
+        self.close();
+    }
+
+    fn visit_sub_annotation_property_of(&mut self, sapo: &'ast SubAnnotationPropertyOf) {
+        self.open_axiom("SubAnnotationPropertyOf");
+        self.visit_annotation_property(&sapo.sub);
+        self.visit_annotation_property(&sapo.sup);
+        self.close();
+    }
+
+    fn visit_sub_class_of(&mut self, sub_class_of: &'ast SubClassOf) {
+        self.open_axiom("SubClassOf");
+        self.visit_class_expression(&sub_class_of.sub);
+        self.visit_class_expression(&sub_class_of.sup);
+        self.close();
+    }
+
+    fn visit_sub_data_property_of(&mut self, sdpo: &'ast SubDataPropertyOf) {
+        self.open_axiom("SubDataPropertyOf");
+        self.visit_data_property(&sdpo.sub);
+        self.visit_data_property(&sdpo.sup);
+        self.close();
+    }
+
+    fn visit_sub_object_property_expression(&mut self, sope: &'ast SubObjectPropertyExpression) {
+        match sope {
+            SubObjectPropertyExpression::ObjectPropertyExpression(ope) => {
+                self.visit_object_property_expression(ope)
+            }
+            SubObjectPropertyExpression::ObjectPropertyChain(opes) => {
+                self.open("ObjectPropertyChain");
+                opes.iter()
+                    .for_each(|ope| self.visit_object_property_expression(ope));
+                self.close();
+            }
+        }
+    }
+
+    fn visit_sub_object_property_of(&mut self, sopo: &'ast SubObjectPropertyOf) {
+        self.open_axiom("SubObjectPropertyOf");
+        self.visit_sub_object_property_expression(&sopo.sub);
+        self.visit_object_property_expression(&sopo.sup);
+        self.close();
+    }
+
+    fn visit_symmetric_object_property(&mut self, sop: &'ast SymmetricObjectProperty) {
+        self.open_axiom("SymmetricObjectProperty");
+        self.visit_object_property_expression(&sop.0);
+        self.close();
+    }
+
+    fn visit_transitive_object_property(&mut self, top: &'ast TransitiveObjectProperty) {
+        self.open_axiom("TransitiveObjectProperty");
+        self.visit_object_property_expression(&top.0);
+        self.close();
+    }
+}
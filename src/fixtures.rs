@@ -0,0 +1,101 @@
+//! Fixtures shared by the unit tests of the crate.
+//!
+//! Entities are named by their local name in the `http://example.com/`
+//! namespace.
+
+use horned_owl::model::*;
+use horned_owl::ontology::set::SetOntology;
+use horned_owl::vocab::WithIRI;
+use horned_owl::vocab::OWL;
+
+/// Get the IRI with the given local name.
+pub fn iri(name: &str) -> IRI {
+    Build::new().iri(format!("http://example.com/{}", name))
+}
+
+/// Get the named class with the given local name.
+pub fn named_class(name: &str) -> Class {
+    Class(iri(name))
+}
+
+/// Get the named class with the given local name as a class expression.
+pub fn class(name: &str) -> ClassExpression {
+    named_class(name).into()
+}
+
+/// Get `owl:Nothing` as a class expression.
+pub fn nothing() -> ClassExpression {
+    Build::new().class(OWL::Nothing.iri_str()).into()
+}
+
+/// Get the object property with the given local name.
+pub fn op(name: &str) -> ObjectPropertyExpression {
+    ObjectProperty(iri(name)).into()
+}
+
+/// Get the inverse of the object property with the given local name.
+pub fn inverse_of(name: &str) -> ObjectPropertyExpression {
+    ObjectPropertyExpression::InverseObjectProperty(ObjectProperty(iri(name)))
+}
+
+/// Get the named individual with the given local name.
+pub fn individual(name: &str) -> Individual {
+    NamedIndividual(iri(name)).into()
+}
+
+/// Get the anonymous individual with the given identifier.
+pub fn anonymous(id: &str) -> Individual {
+    AnonymousIndividual::from(id.to_string()).into()
+}
+
+/// Build an `ObjectSomeValuesFrom` restriction.
+pub fn some<C: Into<ClassExpression>>(property: &str, filler: C) -> ClassExpression {
+    ClassExpression::ObjectSomeValuesFrom {
+        ope: op(property),
+        bce: Box::new(filler.into()),
+    }
+}
+
+/// Build a `SubClassOf` axiom.
+pub fn sub_class_of<A, B>(sub: A, sup: B) -> Axiom
+where
+    A: Into<ClassExpression>,
+    B: Into<ClassExpression>,
+{
+    SubClassOf {
+        sub: sub.into(),
+        sup: sup.into(),
+    }
+    .into()
+}
+
+/// Build a `SubObjectPropertyOf` axiom between two property expressions.
+pub fn sub_object_property_of(
+    sub: ObjectPropertyExpression,
+    sup: ObjectPropertyExpression,
+) -> Axiom {
+    SubObjectPropertyOf {
+        sub: SubObjectPropertyExpression::ObjectPropertyExpression(sub),
+        sup,
+    }
+    .into()
+}
+
+/// Build a `SubObjectPropertyOf` axiom with a chain of named properties.
+pub fn chain(links: &[&str], sup: ObjectPropertyExpression) -> Axiom {
+    SubObjectPropertyOf {
+        sub: SubObjectPropertyExpression::ObjectPropertyChain(
+            links.iter().map(|link| op(link)).collect(),
+        ),
+        sup,
+    }
+    .into()
+}
+
+/// Build an ontology from unannotated axioms.
+pub fn ontology<I>(axioms: I) -> SetOntology
+where
+    I: IntoIterator<Item = Axiom>,
+{
+    axioms.into_iter().map(AnnotatedAxiom::from).collect()
+}
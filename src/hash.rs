@@ -0,0 +1,426 @@
+//! Stable content-based identifiers for axioms.
+//!
+//! An [`AxiomId`] is the SHA-256 digest of an unambiguous serialization of
+//! an axiom, computed by visiting every element of its canonical form (see
+//! [`transform::canonical`](crate::transform::canonical)). It does not
+//! depend on the platform, the Rust version, or the order of the operands
+//! of n-ary constructs, and can therefore be used to cache results or to
+//! cross-reference axioms between ontology versions.
+//!
+//! # Example
+//!
+//! ```rust
+//! # extern crate horned_owl;
+//! # extern crate horned_visit;
+//! use horned_owl::model::*;
+//! use horned_visit::hash::AxiomId;
+//!
+//! let b = Build::new();
+//! let a: ClassExpression = b.class("http://example.com/A").into();
+//! let c: ClassExpression = b.class("http://example.com/B").into();
+//!
+//! let ac = AnnotatedAxiom::from(EquivalentClasses(vec![a.clone(), c.clone()]));
+//! let ca = AnnotatedAxiom::from(EquivalentClasses(vec![c.clone(), a.clone()]));
+//! assert_eq!(AxiomId::of(&ac), AxiomId::of(&ca));
+//! ```
+
+use std::collections::BTreeSet;
+use std::fmt::Debug;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+
+use horned_owl::model::*;
+use horned_owl::vocab::WithIRI;
+use sha2::Digest;
+use sha2::Sha256;
+
+use super::transform::canonical::Canonicalize;
+use super::visit;
+use super::Visit;
+
+/// A content-based identifier for an axiom.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AxiomId([u8; 32]);
+
+impl AxiomId {
+    /// Compute the identifier of an axiom, including its annotations.
+    pub fn of(axiom: &AnnotatedAxiom) -> Self {
+        AxiomHasher::new().hash(axiom)
+    }
+
+    /// Compute the identifier of the logical part of an axiom.
+    pub fn of_logical(axiom: &AnnotatedAxiom) -> Self {
+        AxiomHasher::new().annotations(false).hash(axiom)
+    }
+
+    /// Get the digest of the identifier as raw bytes.
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl From<[u8; 32]> for AxiomId {
+    fn from(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+}
+
+impl Display for AxiomId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        for byte in self.0.iter() {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+impl Debug for AxiomId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "AxiomId({})", self)
+    }
+}
+
+/// A configurable hasher computing [`AxiomId`] values.
+#[derive(Clone, Debug)]
+pub struct AxiomHasher {
+    annotations: bool,
+}
+
+impl Default for AxiomHasher {
+    fn default() -> Self {
+        Self { annotations: true }
+    }
+}
+
+impl AxiomHasher {
+    /// Create a new hasher, including annotations in the digest.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set whether axiom annotations are part of the digest.
+    pub fn annotations(mut self, annotations: bool) -> Self {
+        self.annotations = annotations;
+        self
+    }
+
+    /// Compute the identifier of an axiom.
+    pub fn hash(&self, axiom: &AnnotatedAxiom) -> AxiomId {
+        let canonical = axiom.canonical();
+        let mut feeder = Feeder::default();
+        if self.annotations {
+            feeder.visit_annotated_axiom(&canonical);
+        } else {
+            feeder.visit_axiom(&canonical.axiom);
+        }
+        AxiomId(feeder.sha.finalize().into())
+    }
+}
+
+/// A visitor feeding the serialization of the visited elements to a digest.
+///
+/// Every element is written as a tag followed by its content between
+/// delimiters, and every string is prefixed with its length, so that two
+/// different elements cannot have the same serialization.
+#[derive(Default)]
+struct Feeder {
+    sha: Sha256,
+}
+
+impl Feeder {
+    fn string(&mut self, s: &str) {
+        self.sha.update((s.len() as u64).to_be_bytes());
+        self.sha.update(s.as_bytes());
+    }
+
+    fn number(&mut self, n: u32) {
+        self.sha.update(n.to_be_bytes());
+    }
+
+    fn open(&mut self, tag: &str) {
+        self.sha.update(b"(");
+        self.string(tag);
+    }
+
+    fn close(&mut self) {
+        self.sha.update(b")");
+    }
+}
+
+macro_rules! impl_feed {
+    ($($name:ident($type:ty),)*) => {
+        paste! {
+            $(
+                fn [<visit_ $name>](&mut self, $name: &'ast $type) {
+                    self.open(stringify!($name));
+                    visit::[<visit_ $name>](self, $name);
+                    self.close();
+                }
+            )*
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for Feeder {
+    impl_feed! {
+        annotated_axiom(AnnotatedAxiom),
+        annotation(Annotation),
+        annotation_assertion(AnnotationAssertion),
+        annotation_property(AnnotationProperty),
+        annotation_property_domain(AnnotationPropertyDomain),
+        annotation_property_range(AnnotationPropertyRange),
+        annotation_subject(AnnotationSubject),
+        annotation_value(AnnotationValue),
+        asymmetric_object_property(AsymmetricObjectProperty),
+        axiom(Axiom),
+        class(Class),
+        class_assertion(ClassAssertion),
+        data_property(DataProperty),
+        data_property_assertion(DataPropertyAssertion),
+        data_property_domain(DataPropertyDomain),
+        data_property_range(DataPropertyRange),
+        datatype(Datatype),
+        datatype_definition(DatatypeDefinition),
+        declare_annotation_property(DeclareAnnotationProperty),
+        declare_class(DeclareClass),
+        declare_datatype(DeclareDatatype),
+        declare_data_property(DeclareDataProperty),
+        declare_named_individual(DeclareNamedIndividual),
+        declare_object_property(DeclareObjectProperty),
+        different_individuals(DifferentIndividuals),
+        disjoint_classes(DisjointClasses),
+        disjoint_data_properties(DisjointDataProperties),
+        disjoint_object_properties(DisjointObjectProperties),
+        disjoint_union(DisjointUnion),
+        equivalent_classes(EquivalentClasses),
+        equivalent_data_properties(EquivalentDataProperties),
+        equivalent_object_properties(EquivalentObjectProperties),
+        facet_restriction(FacetRestriction),
+        functional_data_property(FunctionalDataProperty),
+        functional_object_property(FunctionalObjectProperty),
+        has_key(HasKey),
+        import(Import),
+        individual(Individual),
+        inverse_functional_object_property(InverseFunctionalObjectProperty),
+        inverse_object_properties(InverseObjectProperties),
+        irreflexive_object_property(IrreflexiveObjectProperty),
+        named_individual(NamedIndividual),
+        negative_data_property_assertion(NegativeDataPropertyAssertion),
+        negative_object_property_assertion(NegativeObjectPropertyAssertion),
+        object_property(ObjectProperty),
+        object_property_assertion(ObjectPropertyAssertion),
+        object_property_domain(ObjectPropertyDomain),
+        object_property_range(ObjectPropertyRange),
+        ontology_annotation(OntologyAnnotation),
+        property_expression(PropertyExpression),
+        reflexive_object_property(ReflexiveObjectProperty),
+        same_individual(SameIndividual),
+        sub_class_of(SubClassOf),
+        sub_annotation_property_of(SubAnnotationPropertyOf),
+        sub_data_property_of(SubDataPropertyOf),
+        sub_object_property_of(SubObjectPropertyOf),
+        symmetric_object_property(SymmetricObjectProperty),
+        transitive_object_property(TransitiveObjectProperty),
+    }
+
+    fn visit_annotations(&mut self, annotations: &'ast BTreeSet<Annotation>) {
+        self.open("annotations");
+        visit::visit_annotations(self, annotations);
+        self.close();
+    }
+
+    fn visit_anonymous_individual(&mut self, anonymous_individual: &'ast AnonymousIndividual) {
+        self.open("anonymous_individual");
+        self.string(anonymous_individual);
+        self.close();
+    }
+
+    fn visit_class_expression(&mut self, class_expression: &'ast ClassExpression) {
+        use self::ClassExpression::*;
+        self.open("class_expression");
+        match class_expression {
+            Class(_) => self.string("Class"),
+            ObjectIntersectionOf(_) => self.string("ObjectIntersectionOf"),
+            ObjectUnionOf(_) => self.string("ObjectUnionOf"),
+            ObjectComplementOf(_) => self.string("ObjectComplementOf"),
+            ObjectOneOf(_) => self.string("ObjectOneOf"),
+            ObjectSomeValuesFrom { .. } => self.string("ObjectSomeValuesFrom"),
+            ObjectAllValuesFrom { .. } => self.string("ObjectAllValuesFrom"),
+            ObjectHasValue { .. } => self.string("ObjectHasValue"),
+            ObjectHasSelf(_) => self.string("ObjectHasSelf"),
+            ObjectMinCardinality { n, .. } => {
+                self.string("ObjectMinCardinality");
+                self.number(*n);
+            }
+            ObjectMaxCardinality { n, .. } => {
+                self.string("ObjectMaxCardinality");
+                self.number(*n);
+            }
+            ObjectExactCardinality { n, .. } => {
+                self.string("ObjectExactCardinality");
+                self.number(*n);
+            }
+            DataSomeValuesFrom { .. } => self.string("DataSomeValuesFrom"),
+            DataAllValuesFrom { .. } => self.string("DataAllValuesFrom"),
+            DataHasValue { .. } => self.string("DataHasValue"),
+            DataMinCardinality { n, .. } => {
+                self.string("DataMinCardinality");
+                self.number(*n);
+            }
+            DataMaxCardinality { n, .. } => {
+                self.string("DataMaxCardinality");
+                self.number(*n);
+            }
+            DataExactCardinality { n, .. } => {
+                self.string("DataExactCardinality");
+                self.number(*n);
+            }
+        }
+        visit::visit_class_expression(self, class_expression);
+        self.close();
+    }
+
+    fn visit_data_range(&mut self, data_range: &'ast DataRange) {
+        use self::DataRange::*;
+        self.open("data_range");
+        self.string(match data_range {
+            Datatype(_) => "Datatype",
+            DataIntersectionOf(_) => "DataIntersectionOf",
+            DataUnionOf(_) => "DataUnionOf",
+            DataComplementOf(_) => "DataComplementOf",
+            DataOneOf(_) => "DataOneOf",
+            DatatypeRestriction(_, _) => "DatatypeRestriction",
+        });
+        visit::visit_data_range(self, data_range);
+        self.close();
+    }
+
+    fn visit_facet(&mut self, facet: &'ast Facet) {
+        self.open("facet");
+        self.string(facet.iri_str());
+        self.close();
+    }
+
+    fn visit_iri(&mut self, iri: &'ast IRI) {
+        self.open("iri");
+        self.string(iri);
+        self.close();
+    }
+
+    fn visit_literal(&mut self, literal: &'ast Literal) {
+        self.open("literal");
+        match literal {
+            Literal::Simple { literal } => {
+                self.string("Simple");
+                self.string(literal);
+            }
+            Literal::Language { literal, lang } => {
+                self.string("Language");
+                self.string(literal);
+                self.string(lang);
+            }
+            Literal::Datatype {
+                literal,
+                datatype_iri,
+            } => {
+                self.string("Datatype");
+                self.string(literal);
+                self.string(datatype_iri);
+            }
+        }
+        self.close();
+    }
+
+    fn visit_object_property_expression(&mut self, ope: &'ast ObjectPropertyExpression) {
+        self.open("object_property_expression");
+        self.string(match ope {
+            ObjectPropertyExpression::ObjectProperty(_) => "ObjectProperty",
+            ObjectPropertyExpression::InverseObjectProperty(_) => "InverseObjectProperty",
+        });
+        visit::visit_object_property_expression(self, ope);
+        self.close();
+    }
+
+    fn visit_sub_object_property_expression(&mut self, sope: &'ast SubObjectPropertyExpression) {
+        self.open("sub_object_property_expression");
+        self.string(match sope {
+            SubObjectPropertyExpression::ObjectPropertyChain(_) => "ObjectPropertyChain",
+            SubObjectPropertyExpression::ObjectPropertyExpression(_) => "ObjectPropertyExpression",
+        });
+        visit::visit_sub_object_property_expression(self, sope);
+        self.close();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use horned_owl::vocab::AnnotationBuiltIn;
+
+    use super::*;
+
+    fn sub_class_of() -> AnnotatedAxiom {
+        let b = Build::new();
+        AnnotatedAxiom::from(SubClassOf {
+            sub: b.class("http://example.com/A").into(),
+            sup: ClassExpression::ObjectSomeValuesFrom {
+                ope: b.object_property("http://example.com/r").into(),
+                bce: Box::new(b.class("http://example.com/B").into()),
+            },
+        })
+    }
+
+    fn annotated() -> AnnotatedAxiom {
+        let b = Build::new();
+        let mut axiom = sub_class_of();
+        axiom.ann.insert(Annotation {
+            ap: b.annotation_property(AnnotationBuiltIn::COMMENT.iri_str()),
+            av: AnnotationValue::Literal(Literal::Language {
+                literal: "A has an r to a B".into(),
+                lang: "en".into(),
+            }),
+        });
+        axiom
+    }
+
+    fn data_property_assertion() -> AnnotatedAxiom {
+        let b = Build::new();
+        AnnotatedAxiom::from(DataPropertyAssertion {
+            dp: b.data_property("http://example.com/age"),
+            from: b.named_individual("http://example.com/a").into(),
+            to: Literal::Datatype {
+                literal: "42".into(),
+                datatype_iri: b.iri("http://www.w3.org/2001/XMLSchema#integer"),
+            },
+        })
+    }
+
+    #[test]
+    fn golden_digests() {
+        assert_eq!(
+            AxiomId::of(&sub_class_of()).to_string(),
+            "b1b67a3adbfb0bb9000183fb33fd0e3d8a82c506ac7268bbca60cc630d5d401a"
+        );
+        assert_eq!(
+            AxiomId::of(&annotated()).to_string(),
+            "8808897c7b7f557e9e1114a37c58c85daa81e11fbf3c2a626b9e47e96db76f42"
+        );
+        assert_eq!(
+            AxiomId::of(&data_property_assertion()).to_string(),
+            "6906cbe56123376cffee2c62fd41b0c0fb279bf5745d4311a1ba7aea8c6f7f00"
+        );
+    }
+
+    #[test]
+    fn logical_digest() {
+        assert_ne!(AxiomId::of(&annotated()), AxiomId::of(&sub_class_of()));
+        assert_eq!(
+            AxiomId::of_logical(&annotated()),
+            AxiomId::of_logical(&sub_class_of())
+        );
+        assert_eq!(
+            AxiomId::of_logical(&annotated()).to_string(),
+            "d649708bc4ca0a77c2f662b560b20823dcdb7608a853b934e36c77d2cbc21b02",
+        );
+    }
+}
@@ -0,0 +1,600 @@
+//! Told hierarchies of the named entities of an ontology.
+//!
+//! The [`HierarchyBuilder`] visitor collects the subsumptions asserted
+//! between named classes and between named properties, without performing
+//! any reasoning. Each hierarchy is stored as a directed graph from a node
+//! to its parents, along with the equivalences asserted between nodes. The
+//! subsumptions are expected to be acyclic but may contain cycles if the
+//! ontology asserts them; these can be detected with [`Hierarchy::cycles`],
+//! which does not report equivalent nodes as cycles.
+//!
+//! # Example
+//!
+//! ```rust
+//! # extern crate horned_owl;
+//! # extern crate horned_visit;
+//! # use std::fs::File;
+//! # use std::io::BufReader;
+//! use horned_owl::model::*;
+//! use horned_owl::ontology::set::SetOntology;
+//!
+//! let mut f = File::open("tests/data/bfo.owl").map(BufReader::new).unwrap();
+//! let ontology: SetOntology = horned_owl::io::rdf::reader::read(&mut f).unwrap().0.into();
+//! let hierarchies = horned_visit::hierarchy::hierarchies(&ontology);
+//!
+//! let b = Build::new();
+//! let entity = b.class("http://purl.obolibrary.org/obo/BFO_0000001");
+//! let continuant = b.class("http://purl.obolibrary.org/obo/BFO_0000002");
+//! let material_entity = b.class("http://purl.obolibrary.org/obo/BFO_0000040");
+//!
+//! assert!(hierarchies.classes.is_acyclic());
+//! assert!(hierarchies.classes.roots().contains(&entity));
+//! assert!(hierarchies.classes.ancestors(&material_entity).contains(&continuant));
+//! ```
+
+use std::cmp::min;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use horned_owl::model::*;
+
+use super::visit;
+use super::Visit;
+
+/// A directed graph from nodes to their parents.
+///
+/// Equivalences between nodes are stored apart from the subsumption edges,
+/// but an equivalence is traversed as an edge in both directions by the
+/// methods querying the neighbours of a node.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Hierarchy<T: Ord> {
+    parents: BTreeMap<T, BTreeSet<T>>,
+    children: BTreeMap<T, BTreeSet<T>>,
+    equivalents: BTreeMap<T, BTreeSet<T>>,
+}
+
+impl<T: Ord> Default for Hierarchy<T> {
+    fn default() -> Self {
+        Self {
+            parents: BTreeMap::new(),
+            children: BTreeMap::new(),
+            equivalents: BTreeMap::new(),
+        }
+    }
+}
+
+impl<T: Ord + Clone> Hierarchy<T> {
+    /// Create a new empty hierarchy.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a node to the hierarchy, without any edge.
+    pub fn add_node(&mut self, node: T) {
+        self.children.entry(node.clone()).or_default();
+        self.parents.entry(node).or_default();
+    }
+
+    /// Add an edge from a node to one of its parents.
+    pub fn add_edge(&mut self, child: T, parent: T) {
+        self.add_node(child.clone());
+        self.add_node(parent.clone());
+        self.children
+            .get_mut(&parent)
+            .unwrap()
+            .insert(child.clone());
+        self.parents.get_mut(&child).unwrap().insert(parent);
+    }
+
+    /// Add an equivalence between two nodes.
+    pub fn add_equivalence(&mut self, node: T, other: T) {
+        self.add_node(node.clone());
+        self.add_node(other.clone());
+        if node != other {
+            self.equivalents
+                .entry(node.clone())
+                .or_default()
+                .insert(other.clone());
+            self.equivalents.entry(other).or_default().insert(node);
+        }
+    }
+
+    /// Remove the edge from a node to one of its parents, if any.
+    ///
+    /// Equivalences are not affected.
+    pub fn remove_edge(&mut self, child: &T, parent: &T) -> bool {
+        if let Some(children) = self.children.get_mut(parent) {
+            children.remove(child);
+        }
+        self.parents
+            .get_mut(child)
+            .map(|parents| parents.remove(parent))
+            .unwrap_or(false)
+    }
+
+    /// Check whether the hierarchy contains a node.
+    pub fn contains(&self, node: &T) -> bool {
+        self.parents.contains_key(node)
+    }
+
+    /// Check whether the hierarchy contains an edge from a node to a parent.
+    ///
+    /// Two equivalent nodes are connected by an edge in both directions.
+    pub fn contains_edge(&self, child: &T, parent: &T) -> bool {
+        let related = |graph: &BTreeMap<T, BTreeSet<T>>| {
+            graph
+                .get(child)
+                .map(|nodes| nodes.contains(parent))
+                .unwrap_or(false)
+        };
+        related(&self.parents) || related(&self.equivalents)
+    }
+
+    /// Iterate over the nodes of the hierarchy.
+    pub fn nodes(&self) -> impl Iterator<Item = &T> {
+        self.parents.keys()
+    }
+
+    /// Iterate over the edges of the hierarchy, as `(child, parent)` pairs.
+    ///
+    /// Every equivalence is iterated as an edge in both directions.
+    pub fn edges(&self) -> impl Iterator<Item = (&T, &T)> {
+        self.parents
+            .iter()
+            .chain(self.equivalents.iter())
+            .flat_map(|(child, parents)| parents.iter().map(move |parent| (child, parent)))
+    }
+
+    /// Get the direct parents of a node, including its equivalent nodes.
+    pub fn parents(&self, node: &T) -> BTreeSet<&T> {
+        self.parents
+            .get(node)
+            .into_iter()
+            .chain(self.equivalents.get(node))
+            .flatten()
+            .collect()
+    }
+
+    /// Get the direct children of a node, including its equivalent nodes.
+    pub fn children(&self, node: &T) -> BTreeSet<&T> {
+        self.children
+            .get(node)
+            .into_iter()
+            .chain(self.equivalents.get(node))
+            .flatten()
+            .collect()
+    }
+
+    /// Get the nodes asserted to be equivalent to a node.
+    pub fn equivalents(&self, node: &T) -> BTreeSet<&T> {
+        self.equivalents.get(node).into_iter().flatten().collect()
+    }
+
+    /// Get the transitive parents of a node.
+    ///
+    /// The node itself is only included if it is part of a cycle or
+    /// equivalent to another node.
+    pub fn ancestors(&self, node: &T) -> BTreeSet<&T> {
+        self.closure(&self.parents, node)
+    }
+
+    /// Get the transitive children of a node.
+    ///
+    /// The node itself is only included if it is part of a cycle or
+    /// equivalent to another node.
+    pub fn descendants(&self, node: &T) -> BTreeSet<&T> {
+        self.closure(&self.children, node)
+    }
+
+    /// Get the nodes without any parent or equivalent node.
+    pub fn roots(&self) -> BTreeSet<&T> {
+        self.parents
+            .iter()
+            .filter(|(node, parents)| parents.is_empty() && !self.equivalents.contains_key(node))
+            .map(|(node, _)| node)
+            .collect()
+    }
+
+    /// Get the nodes without any child or equivalent node.
+    pub fn leaves(&self) -> BTreeSet<&T> {
+        self.children
+            .iter()
+            .filter(|(node, children)| children.is_empty() && !self.equivalents.contains_key(node))
+            .map(|(node, _)| node)
+            .collect()
+    }
+
+    /// Get the cycles of the hierarchy.
+    ///
+    /// Every cycle is reported as the set of nodes of a strongly connected
+    /// component of the graph, so that overlapping cycles are reported
+    /// together. Equivalent nodes are collapsed into a single node before
+    /// looking for cycles, so that an equivalence is not a cycle by itself,
+    /// but is reported along with the subsumptions forming a cycle with it.
+    pub fn cycles(&self) -> Vec<BTreeSet<&T>> {
+        // collapse every set of equivalent nodes into its smallest node
+        let mut representatives = BTreeMap::<&T, &T>::new();
+        for node in self.parents.keys() {
+            if representatives.contains_key(node) {
+                continue;
+            }
+            let mut stack = vec![node];
+            while let Some(next) = stack.pop() {
+                if representatives.insert(next, node).is_none() {
+                    stack.extend(self.equivalents.get(next).into_iter().flatten());
+                }
+            }
+        }
+
+        // build the graph of the subsumptions between collapsed nodes
+        let mut graph = BTreeMap::<&T, BTreeSet<&T>>::new();
+        for (child, parents) in self.parents.iter() {
+            let source = representatives[child];
+            let targets = graph.entry(source).or_default();
+            for parent in parents.iter() {
+                let target = representatives[parent];
+                if source != target || child == parent {
+                    targets.insert(target);
+                }
+            }
+        }
+
+        let mut tarjan = Tarjan {
+            graph: &graph,
+            index: BTreeMap::new(),
+            lowlink: BTreeMap::new(),
+            stack: Vec::new(),
+            on_stack: BTreeSet::new(),
+            components: Vec::new(),
+        };
+        for node in graph.keys() {
+            if !tarjan.index.contains_key(node) {
+                tarjan.connect(node);
+            }
+        }
+        let cycles: Vec<BTreeSet<&T>> = tarjan
+            .components
+            .into_iter()
+            .filter(|component| {
+                component.len() > 1 || component.iter().all(|node| graph[node].contains(node))
+            })
+            .collect();
+
+        // expand the collapsed nodes of every cycle
+        cycles
+            .into_iter()
+            .map(|cycle| {
+                representatives
+                    .iter()
+                    .filter(|(_, representative)| cycle.contains(*representative))
+                    .map(|(&node, _)| node)
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Check whether the hierarchy is free of cycles.
+    pub fn is_acyclic(&self) -> bool {
+        self.cycles().is_empty()
+    }
+
+    /// Get the nodes reachable from a node, following equivalences as well.
+    fn closure<'h>(&'h self, graph: &'h BTreeMap<T, BTreeSet<T>>, node: &T) -> BTreeSet<&'h T> {
+        let neighbours = |node: &T| {
+            graph
+                .get(node)
+                .into_iter()
+                .chain(self.equivalents.get(node))
+                .flatten()
+        };
+        let mut seen = BTreeSet::new();
+        let mut stack = neighbours(node).collect::<Vec<_>>();
+        while let Some(next) = stack.pop() {
+            if seen.insert(next) {
+                stack.extend(neighbours(next));
+            }
+        }
+        seen
+    }
+}
+
+/// The state of Tarjan's strongly connected components algorithm.
+///
+/// Every node of the graph is expected to be a key of the graph.
+struct Tarjan<'g, 'h, T: Ord> {
+    graph: &'g BTreeMap<&'h T, BTreeSet<&'h T>>,
+    index: BTreeMap<&'h T, usize>,
+    lowlink: BTreeMap<&'h T, usize>,
+    stack: Vec<&'h T>,
+    on_stack: BTreeSet<&'h T>,
+    components: Vec<BTreeSet<&'h T>>,
+}
+
+impl<'g, 'h, T: Ord> Tarjan<'g, 'h, T> {
+    /// Assign an index to a node and push it on the stack.
+    fn discover(&mut self, node: &'h T) {
+        let index = self.index.len();
+        self.index.insert(node, index);
+        self.lowlink.insert(node, index);
+        self.stack.push(node);
+        self.on_stack.insert(node);
+    }
+
+    /// Find the components reachable from a node.
+    ///
+    /// The depth-first search is driven by an explicit stack of successor
+    /// iterators, so that deep hierarchies cannot overflow the call stack.
+    fn connect(&mut self, root: &'h T) {
+        let graph = self.graph;
+        self.discover(root);
+        let mut work = vec![(root, graph[root].iter())];
+        while let Some((node, successors)) = work.last_mut() {
+            let node = *node;
+            if let Some(&next) = successors.next() {
+                if !self.index.contains_key(next) {
+                    self.discover(next);
+                    work.push((next, graph[next].iter()));
+                } else if self.on_stack.contains(next) {
+                    let lowlink = min(self.lowlink[node], self.index[next]);
+                    self.lowlink.insert(node, lowlink);
+                }
+                continue;
+            }
+
+            work.pop();
+            if let Some((parent, _)) = work.last() {
+                let lowlink = min(self.lowlink[parent], self.lowlink[node]);
+                self.lowlink.insert(parent, lowlink);
+            }
+            if self.lowlink[node] == self.index[node] {
+                let mut component = BTreeSet::new();
+                while let Some(member) = self.stack.pop() {
+                    self.on_stack.remove(member);
+                    component.insert(member);
+                    if member == node {
+                        break;
+                    }
+                }
+                self.components.push(component);
+            }
+        }
+    }
+}
+
+/// The told hierarchies of the named entities of an ontology.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Hierarchies {
+    pub classes: Hierarchy<Class>,
+    pub object_properties: Hierarchy<ObjectProperty>,
+    pub data_properties: Hierarchy<DataProperty>,
+    pub annotation_properties: Hierarchy<AnnotationProperty>,
+}
+
+/// A visitor building the told hierarchies of the visited axioms.
+///
+/// Every named entity encountered is added to its hierarchy, and edges are
+/// added for the following axioms:
+///
+/// - `SubClassOf(A, B)`, where `A` is a named class and `B` is either a
+///   named class or an intersection, in which case an edge is added to
+///   every named operand of the intersection.
+/// - `EquivalentClasses(A, B, ...)`, where an equivalence is added between
+///   every pair of named classes, and an edge from every named class to the
+///   named operands of every intersection.
+/// - `SubObjectPropertyOf(p, q)`, `SubDataPropertyOf(p, q)` and
+///   `SubAnnotationPropertyOf(p, q)` between named properties.
+#[derive(Clone, Debug, Default)]
+pub struct HierarchyBuilder {
+    hierarchies: Hierarchies,
+}
+
+impl HierarchyBuilder {
+    /// Create a new builder with empty hierarchies.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the hierarchies built so far.
+    pub fn hierarchies(&self) -> &Hierarchies {
+        &self.hierarchies
+    }
+
+    /// Consume the builder and return the built hierarchies.
+    pub fn into_hierarchies(self) -> Hierarchies {
+        self.hierarchies
+    }
+
+    /// Add edges from a named class to the parents found in a superclass.
+    fn add_class_edges(&mut self, sub: &Class, sup: &ClassExpression) {
+        match sup {
+            ClassExpression::Class(parent) => {
+                self.hierarchies
+                    .classes
+                    .add_edge(sub.clone(), parent.clone());
+            }
+            ClassExpression::ObjectIntersectionOf(operands) => {
+                for operand in operands.iter() {
+                    if let ClassExpression::Class(parent) = operand {
+                        self.hierarchies
+                            .classes
+                            .add_edge(sub.clone(), parent.clone());
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for HierarchyBuilder {
+    fn visit_axiom(&mut self, axiom: &'ast Axiom) {
+        match axiom {
+            Axiom::SubClassOf(SubClassOf {
+                sub: ClassExpression::Class(sub),
+                sup,
+            }) => {
+                self.add_class_edges(sub, sup);
+            }
+            Axiom::EquivalentClasses(EquivalentClasses(operands)) => {
+                for operand in operands.iter() {
+                    if let ClassExpression::Class(sub) = operand {
+                        for other in operands.iter().filter(|&other| other != operand) {
+                            match other {
+                                ClassExpression::Class(class) => self
+                                    .hierarchies
+                                    .classes
+                                    .add_equivalence(sub.clone(), class.clone()),
+                                _ => self.add_class_edges(sub, other),
+                            }
+                        }
+                    }
+                }
+            }
+            Axiom::SubObjectPropertyOf(SubObjectPropertyOf {
+                sub: SubObjectPropertyExpression::ObjectPropertyExpression(sub),
+                sup,
+            }) => {
+                if let (Some(sub), Some(sup)) = (sub.as_property(), sup.as_property()) {
+                    self.hierarchies
+                        .object_properties
+                        .add_edge(sub.clone(), sup.clone());
+                }
+            }
+            Axiom::SubDataPropertyOf(SubDataPropertyOf { sub, sup }) => {
+                self.hierarchies
+                    .data_properties
+                    .add_edge(sub.clone(), sup.clone());
+            }
+            Axiom::SubAnnotationPropertyOf(SubAnnotationPropertyOf { sub, sup }) => {
+                self.hierarchies
+                    .annotation_properties
+                    .add_edge(sub.clone(), sup.clone());
+            }
+            _ => (),
+        }
+        visit::visit_axiom(self, axiom);
+    }
+
+    fn visit_annotation_property(&mut self, annotation_property: &'ast AnnotationProperty) {
+        self.hierarchies
+            .annotation_properties
+            .add_node(annotation_property.clone());
+    }
+
+    fn visit_class(&mut self, class: &'ast Class) {
+        self.hierarchies.classes.add_node(class.clone());
+    }
+
+    fn visit_data_property(&mut self, data_property: &'ast DataProperty) {
+        self.hierarchies
+            .data_properties
+            .add_node(data_property.clone());
+    }
+
+    fn visit_object_property(&mut self, object_property: &'ast ObjectProperty) {
+        self.hierarchies
+            .object_properties
+            .add_node(object_property.clone());
+    }
+}
+
+/// Build the told hierarchies of an ontology.
+///
+/// Annotations of axioms are ignored, so annotation properties only used
+/// in axiom annotations are not part of the annotation property hierarchy.
+pub fn hierarchies<'a, I>(axioms: I) -> Hierarchies
+where
+    I: IntoIterator<Item = &'a AnnotatedAxiom>,
+{
+    let mut builder = HierarchyBuilder::new();
+    for aa in axioms.into_iter() {
+        builder.visit_axiom(&aa.axiom);
+    }
+    builder.into_hierarchies()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::*;
+
+    fn equivalent_classes(names: &[&str]) -> Axiom {
+        EquivalentClasses(names.iter().map(|name| class(name)).collect()).into()
+    }
+
+    fn class_hierarchy(axioms: Vec<Axiom>) -> Hierarchy<Class> {
+        hierarchies(&ontology(axioms)).classes
+    }
+
+    #[test]
+    fn equivalence_is_not_a_cycle() {
+        let hierarchy = class_hierarchy(vec![equivalent_classes(&["A", "B"])]);
+        assert!(hierarchy.is_acyclic());
+        assert!(hierarchy
+            .ancestors(&named_class("A"))
+            .contains(&named_class("B")));
+        assert!(hierarchy
+            .ancestors(&named_class("B"))
+            .contains(&named_class("A")));
+    }
+
+    #[test]
+    fn mutual_subsumption_is_a_cycle() {
+        let hierarchy = class_hierarchy(vec![
+            sub_class_of(class("A"), class("B")),
+            sub_class_of(class("B"), class("A")),
+        ]);
+        let cycles = hierarchy.cycles();
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(
+            cycles[0],
+            vec![&named_class("A"), &named_class("B")]
+                .into_iter()
+                .collect()
+        );
+    }
+
+    #[test]
+    fn cycle_through_equivalence() {
+        let hierarchy = class_hierarchy(vec![
+            equivalent_classes(&["A", "B"]),
+            sub_class_of(class("B"), class("C")),
+            sub_class_of(class("C"), class("A")),
+            sub_class_of(class("C"), class("D")),
+        ]);
+        let cycles = hierarchy.cycles();
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(
+            cycles[0],
+            vec![&named_class("A"), &named_class("B"), &named_class("C")]
+                .into_iter()
+                .collect()
+        );
+    }
+
+    #[test]
+    fn subsumption_between_equivalents_is_not_a_cycle() {
+        let hierarchy = class_hierarchy(vec![
+            equivalent_classes(&["A", "B"]),
+            sub_class_of(class("A"), class("B")),
+        ]);
+        assert!(hierarchy.is_acyclic());
+    }
+
+    #[test]
+    fn self_subsumption_is_a_cycle() {
+        let hierarchy = class_hierarchy(vec![sub_class_of(class("A"), class("A"))]);
+        assert_eq!(hierarchy.cycles().len(), 1);
+    }
+
+    #[test]
+    fn deep_hierarchy() {
+        let mut hierarchy = Hierarchy::new();
+        for i in 0..50_000u32 {
+            hierarchy.add_edge(i, i + 1);
+        }
+        assert!(hierarchy.is_acyclic());
+        hierarchy.add_edge(50_000, 0);
+        assert_eq!(hierarchy.cycles()[0].len(), 50_001);
+    }
+}
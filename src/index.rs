@@ -0,0 +1,318 @@
+//! Indices of the axioms referencing or defining each entity of an ontology.
+//!
+//! The [`EntityIndex`] visitor maps every named entity to the annotated
+//! axioms in which it occurs, in the same fashion as the
+//! `getReferencingAxioms` method of the OWL API. Entities occurring in the
+//! annotations of an axiom are considered to reference it, while the IRI
+//! subject of an annotation assertion is not an entity and is therefore
+//! not indexed.
+//!
+//! The [`DefiningIndex`] maps every entity to the annotated axioms it is
+//! the [subject](crate::subject) of, such as the `SubClassOf` axioms of a
+//! class or the annotation assertions on its IRI.
+//!
+//! # Example
+//!
+//! ```rust
+//! # extern crate horned_owl;
+//! # extern crate horned_visit;
+//! use horned_owl::model::*;
+//! use horned_owl::ontology::set::SetOntology;
+//! use horned_visit::signature::Entity;
+//!
+//! let b = Build::new();
+//! let a = b.class("http://example.com/A");
+//! let c = b.class("http://example.com/B");
+//!
+//! let mut ontology = SetOntology::new();
+//! ontology.declare(a.clone());
+//! ontology.insert(SubClassOf {
+//!     sub: a.clone().into(),
+//!     sup: c.clone().into(),
+//! });
+//!
+//! let index = horned_visit::index::index(&ontology);
+//! assert_eq!(index.referencing_axioms(&Entity::from(&a)).count(), 2);
+//! assert_eq!(index.referencing_axioms(&Entity::from(&c)).count(), 1);
+//!
+//! let index = horned_visit::index::defining_index(&ontology);
+//! assert_eq!(index.defining_axioms(&Entity::from(&a)).count(), 1);
+//! assert_eq!(index.defining_axioms(&Entity::from(&c)).count(), 0);
+//! ```
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use horned_owl::model::*;
+
+use super::signature::Entity;
+use super::subject::subjects;
+use super::subject::Subject;
+use super::visit;
+use super::Visit;
+
+/// A visitor indexing the annotated axioms referencing each entity.
+#[derive(Clone, Debug, Default)]
+pub struct EntityIndex<'a> {
+    axiom: Option<&'a AnnotatedAxiom>,
+    index: BTreeMap<Entity, BTreeSet<&'a AnnotatedAxiom>>,
+}
+
+impl<'a> EntityIndex<'a> {
+    /// Create a new empty index.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an annotated axiom to the index.
+    pub fn insert(&mut self, annotated_axiom: &'a AnnotatedAxiom) {
+        self.visit_annotated_axiom(annotated_axiom);
+    }
+
+    /// Iterate over the indexed entities.
+    pub fn entities(&self) -> impl Iterator<Item = &Entity> {
+        self.index.keys()
+    }
+
+    /// Check whether an entity is referenced by any indexed axiom.
+    pub fn contains(&self, entity: &Entity) -> bool {
+        self.index.contains_key(entity)
+    }
+
+    /// Iterate over the indexed axioms referencing an entity.
+    pub fn referencing_axioms(
+        &self,
+        entity: &Entity,
+    ) -> impl Iterator<Item = &'a AnnotatedAxiom> + '_ {
+        self.index.get(entity).into_iter().flatten().cloned()
+    }
+}
+
+impl<'a> Visit<'a> for EntityIndex<'a> {
+    fn visit_annotated_axiom(&mut self, annotated_axiom: &'a AnnotatedAxiom) {
+        self.axiom = Some(annotated_axiom);
+        visit::visit_annotated_axiom(self, annotated_axiom);
+        self.axiom = None;
+    }
+
+    fn visit_entity(&mut self, kind: NamedEntityKind, iri: &'a IRI) {
+        if let Some(axiom) = self.axiom {
+            self.index
+                .entry(Entity::new(kind, iri.clone()))
+                .or_default()
+                .insert(axiom);
+        }
+    }
+}
+
+/// Index the axioms of an ontology by the entities they reference.
+pub fn index<'a, I>(axioms: I) -> EntityIndex<'a>
+where
+    I: IntoIterator<Item = &'a AnnotatedAxiom>,
+{
+    let mut index = EntityIndex::new();
+    for aa in axioms.into_iter() {
+        index.insert(aa);
+    }
+    index
+}
+
+/// An index of the annotated axioms defining each entity.
+#[derive(Clone, Debug, Default)]
+pub struct DefiningIndex<'a> {
+    entities: BTreeMap<Entity, BTreeSet<&'a AnnotatedAxiom>>,
+    iris: BTreeMap<IRI, BTreeSet<&'a AnnotatedAxiom>>,
+}
+
+impl<'a> DefiningIndex<'a> {
+    /// Create a new empty index.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an annotated axiom to the index.
+    pub fn insert(&mut self, annotated_axiom: &'a AnnotatedAxiom) {
+        for subject in subjects(&annotated_axiom.axiom) {
+            let axioms = match subject {
+                Subject::Entity(entity) => self.entities.entry(entity).or_default(),
+                Subject::IRI(iri) => self.iris.entry(iri).or_default(),
+            };
+            axioms.insert(annotated_axiom);
+        }
+    }
+
+    /// Iterate over the indexed axioms defining an entity.
+    pub fn defining_axioms(
+        &self,
+        entity: &Entity,
+    ) -> impl Iterator<Item = &'a AnnotatedAxiom> + '_ {
+        let entities = self.entities.get(entity).into_iter().flatten();
+        let iris = self.iris.get(&entity.iri).into_iter().flatten();
+        entities.chain(iris).cloned()
+    }
+}
+
+/// Index the axioms of an ontology by the entities they define.
+pub fn defining_index<'a, I>(axioms: I) -> DefiningIndex<'a>
+where
+    I: IntoIterator<Item = &'a AnnotatedAxiom>,
+{
+    let mut index = DefiningIndex::new();
+    for aa in axioms.into_iter() {
+        index.insert(aa);
+    }
+    index
+}
+
+#[cfg(test)]
+mod tests {
+    use horned_owl::ontology::set::SetOntology;
+
+    use super::*;
+    use crate::fixtures::*;
+
+    fn annotated<A: Into<Axiom>>(axiom: A, ap: &str, av: &str) -> AnnotatedAxiom {
+        let annotation = Annotation {
+            ap: AnnotationProperty(iri(ap)),
+            av: AnnotationValue::IRI(iri(av)),
+        };
+        AnnotatedAxiom::new(axiom, vec![annotation].into_iter().collect())
+    }
+
+    /// An ontology with an annotated subsumption and an annotation assertion.
+    fn ontology() -> SetOntology {
+        let mut ontology = SetOntology::new();
+        ontology.insert(annotated(
+            SubClassOf {
+                sub: named_class("A").into(),
+                sup: ClassExpression::ObjectSomeValuesFrom {
+                    ope: ObjectPropertyExpression::InverseObjectProperty(ObjectProperty(iri("r"))),
+                    bce: Box::new(named_class("B").into()),
+                },
+            },
+            "p",
+            "X",
+        ));
+        ontology.insert(AnnotationAssertion {
+            subject: AnnotationSubject::IRI(iri("C")),
+            ann: Annotation {
+                ap: AnnotationProperty(iri("q")),
+                av: AnnotationValue::IRI(iri("D")),
+            },
+        });
+        ontology
+    }
+
+    #[test]
+    fn referencing_axioms() {
+        let ontology = ontology();
+        let index = index(&ontology);
+        let entities: BTreeSet<&Entity> = index.entities().collect();
+        let expected = [
+            Entity::from(named_class("A")),
+            Entity::from(named_class("B")),
+            Entity::from(ObjectProperty(iri("r"))),
+            Entity::from(AnnotationProperty(iri("p"))),
+            Entity::from(AnnotationProperty(iri("q"))),
+        ];
+        assert_eq!(entities, expected.iter().collect());
+        for entity in expected.iter() {
+            assert_eq!(index.referencing_axioms(entity).count(), 1);
+        }
+    }
+
+    #[test]
+    fn annotation_entities() {
+        let ontology = ontology();
+        let index = index(&ontology);
+        let p = Entity::from(AnnotationProperty(iri("p")));
+        let axiom = index.referencing_axioms(&p).next().unwrap();
+        assert!(matches!(axiom.axiom, Axiom::SubClassOf(_)));
+        assert!(!index.contains(&Entity::from(named_class("X"))));
+    }
+
+    #[test]
+    fn annotation_assertion_subject() {
+        let ontology = ontology();
+        let index = index(&ontology);
+        for kind in [
+            NamedEntityKind::Class,
+            NamedEntityKind::ObjectProperty,
+            NamedEntityKind::NamedIndividual,
+            NamedEntityKind::AnnotationProperty,
+        ] {
+            assert!(!index.contains(&Entity::new(kind, iri("C"))));
+        }
+        let q = Entity::from(AnnotationProperty(iri("q")));
+        assert!(index.contains(&q));
+    }
+
+    #[test]
+    fn shared_entities() {
+        let mut ontology = ontology();
+        ontology.declare(named_class("A"));
+        ontology.insert(annotated(DeclareClass(named_class("B")), "p", "Y"));
+        let index = index(&ontology);
+        assert_eq!(
+            index
+                .referencing_axioms(&Entity::from(named_class("A")))
+                .count(),
+            2
+        );
+        assert_eq!(
+            index
+                .referencing_axioms(&Entity::from(AnnotationProperty(iri("p"))))
+                .count(),
+            2
+        );
+        assert_eq!(
+            index
+                .referencing_axioms(&Entity::from(named_class("C")))
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn defining_axioms() {
+        let mut ontology = ontology();
+        ontology.declare(named_class("A"));
+        ontology.insert(SubClassOf {
+            sub: named_class("B").into(),
+            sup: named_class("A").into(),
+        });
+        let index = defining_index(&ontology);
+
+        let a = Entity::from(named_class("A"));
+        let defining: Vec<&AnnotatedAxiom> = index.defining_axioms(&a).collect();
+        assert_eq!(defining.len(), 1);
+        assert!(matches!(defining[0].axiom, Axiom::SubClassOf(_)));
+        assert_eq!(
+            index
+                .defining_axioms(&Entity::from(ObjectProperty(iri("r"))))
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn defining_annotation_assertions() {
+        let ontology = ontology();
+        let index = defining_index(&ontology);
+        for entity in [
+            Entity::from(named_class("C")),
+            Entity::from(ObjectProperty(iri("C"))),
+            Entity::from(NamedIndividual(iri("C"))),
+        ] {
+            let defining: Vec<&AnnotatedAxiom> = index.defining_axioms(&entity).collect();
+            assert_eq!(defining.len(), 1);
+            assert!(matches!(defining[0].axiom, Axiom::AnnotationAssertion(_)));
+        }
+        assert_eq!(
+            index
+                .defining_axioms(&Entity::from(named_class("D")))
+                .count(),
+            0
+        );
+    }
+}
@@ -6,10 +6,36 @@ extern crate paste;
 #[macro_use]
 extern crate blanket;
 extern crate horned_owl;
+extern crate sha2;
 
 use horned_owl::model::*;
+use horned_owl::vocab::is_nothing;
+use horned_owl::vocab::is_thing;
 use std::collections::BTreeSet;
 
+pub mod check;
+pub mod compat;
+pub mod diff;
+pub mod display;
+#[cfg(test)]
+mod fixtures;
+pub mod hash;
+pub mod hierarchy;
+pub mod index;
+pub mod locality;
+pub mod mireot;
+pub mod patch;
+pub mod polarity;
+pub mod reasoner;
+pub mod relation_graph;
+pub mod signature;
+pub mod sink;
+pub mod subject;
+pub mod transform;
+
+/// The IRI of `owl:topObjectProperty`, missing from `horned_owl::vocab`.
+const TOP_OBJECT_PROPERTY: &str = "http://www.w3.org/2002/07/owl#topObjectProperty";
+
 macro_rules! impl_traits {
     ($visit:ident, $($name:ident($type:ty),)*) => {
         paste! {
@@ -19,6 +45,28 @@ macro_rules! impl_traits {
             pub trait $visit<'ast> {
                 /// Visit the annotations of an [`AnnotatedAxiom`].
                 fn visit_annotations(&mut self, annotations: &'ast BTreeSet<Annotation>);
+                /// Visit the IRI of a named entity of the given kind.
+                fn visit_entity(&mut self, kind: NamedEntityKind, iri: &'ast IRI);
+                /// Visit a declaration axiom.
+                fn visit_declaration(&mut self, declaration: &'ast Axiom);
+                /// Visit a logical axiom.
+                fn visit_logical_axiom(&mut self, logical_axiom: &'ast Axiom);
+                /// Visit a class axiom.
+                fn visit_class_axiom(&mut self, class_axiom: &'ast Axiom);
+                /// Visit an object property axiom.
+                fn visit_object_property_axiom(&mut self, object_property_axiom: &'ast Axiom);
+                /// Visit a data property axiom.
+                fn visit_data_property_axiom(&mut self, data_property_axiom: &'ast Axiom);
+                /// Visit an assertion axiom.
+                fn visit_assertion(&mut self, assertion: &'ast Axiom);
+                /// Visit an annotation axiom.
+                fn visit_annotation_axiom(&mut self, annotation_axiom: &'ast Axiom);
+                /// Visit the `owl:Thing` [`Class`].
+                fn visit_thing(&mut self, thing: &'ast Class);
+                /// Visit the `owl:Nothing` [`Class`].
+                fn visit_nothing(&mut self, nothing: &'ast Class);
+                /// Visit the `owl:topObjectProperty` [`ObjectProperty`].
+                fn visit_top_object_property(&mut self, top_object_property: &'ast ObjectProperty);
                 $(
                     #[doc = "Visit a [`" $type "`]."]
                     fn [<visit_ $name>] (&mut self, $name: &'ast $type);
@@ -31,6 +79,28 @@ macro_rules! impl_traits {
             pub trait [<$visit Mut>] {
                 /// Visit the annotations of an [`AnnotatedAxiom`].
                 fn visit_annotations(&mut self, annotations: &mut BTreeSet<Annotation>);
+                /// Visit the IRI of a named entity of the given kind.
+                fn visit_entity(&mut self, kind: NamedEntityKind, iri: &mut IRI);
+                /// Visit a declaration axiom.
+                fn visit_declaration(&mut self, declaration: &mut Axiom);
+                /// Visit a logical axiom.
+                fn visit_logical_axiom(&mut self, logical_axiom: &mut Axiom);
+                /// Visit a class axiom.
+                fn visit_class_axiom(&mut self, class_axiom: &mut Axiom);
+                /// Visit an object property axiom.
+                fn visit_object_property_axiom(&mut self, object_property_axiom: &mut Axiom);
+                /// Visit a data property axiom.
+                fn visit_data_property_axiom(&mut self, data_property_axiom: &mut Axiom);
+                /// Visit an assertion axiom.
+                fn visit_assertion(&mut self, assertion: &mut Axiom);
+                /// Visit an annotation axiom.
+                fn visit_annotation_axiom(&mut self, annotation_axiom: &mut Axiom);
+                /// Visit the `owl:Thing` [`Class`].
+                fn visit_thing(&mut self, thing: &mut Class);
+                /// Visit the `owl:Nothing` [`Class`].
+                fn visit_nothing(&mut self, nothing: &mut Class);
+                /// Visit the `owl:topObjectProperty` [`ObjectProperty`].
+                fn visit_top_object_property(&mut self, top_object_property: &mut ObjectProperty);
                 $(
                     #[doc = "Visit a [`" $type "`]."]
                     fn [<visit_ $name>] (&mut self, $name: &mut $type);
@@ -124,6 +194,12 @@ macro_rules! impl_default {
                     annotations.iter().for_each(|a| visitor.visit_annotation(a));
                 }
 
+                #[allow(unused_variables)]
+                /// Default implementation of the [`Visit::visit_entity`] method
+                pub fn visit_entity<'ast, V: Visit<'ast> + ?Sized>(visitor: &mut V, kind: NamedEntityKind, iri: &'ast IRI) {
+                    visitor.visit_iri(iri);
+                }
+
                 $(#[allow(unused_variables)]
                 #[doc = "Default implementation of the [`Visit::visit_" $name "`] method"]
                 pub fn [<visit_ $name>] <'ast, V: Visit<'ast> + ?Sized>($visitor: &mut V, $name: &'ast $type) {
@@ -143,6 +219,12 @@ macro_rules! impl_default {
                     // cannot mutably visit the elements of a BTreeSet
                 }
 
+                #[allow(unused_variables)]
+                /// Default implementation of the [`VisitMut::visit_entity`] method
+                pub fn visit_entity<V: VisitMut + ?Sized>($visitor: &mut V, kind: NamedEntityKind, iri: &mut IRI) {
+                    $visitor.visit_iri(iri);
+                }
+
                 $(#[allow(unused_variables)]
                 #[doc = "Default implementation of the [`VisitMut::visit_" $name "`] method"]
                 pub fn [<visit_ $name>] <V: VisitMut + ?Sized>($visitor: &mut V, $name: &mut $type) {
@@ -171,7 +253,7 @@ impl_default! { visitor,
     },
 
     annotation_property(AnnotationProperty) => {
-        visitor.visit_iri(r!(annotation_property.0));
+        visitor.visit_entity(NamedEntityKind::AnnotationProperty, r!(annotation_property.0));
     },
 
     annotation_property_domain(AnnotationPropertyDomain) => {
@@ -205,42 +287,20 @@ impl_default! { visitor,
         visitor.visit_object_property_expression(r!(asymmetric_object_property.0));
     },
 
-    axiom(Axiom) => {
+    annotation_axiom(Axiom) => {
         use self::Axiom::*;
-        match axiom {
-            OntologyAnnotation(oa) => visitor.visit_ontology_annotation(r!(*oa)),
-            Import(import) => visitor.visit_import(r!(*import)),
-            DeclareClass(dc) => visitor.visit_declare_class(r!(*dc)),
-            DeclareObjectProperty(dop) => visitor.visit_declare_object_property(r!(*dop)),
-            DeclareAnnotationProperty(dap) => visitor.visit_declare_annotation_property(r!(*dap)),
-            DeclareDataProperty(ddp) => visitor.visit_declare_data_property(r!(*ddp)),
-            DeclareNamedIndividual(dni) => visitor.visit_declare_named_individual(r!(*dni)),
-            DeclareDatatype(ddt) => visitor.visit_declare_datatype(r!(*ddt)),
-            SubClassOf(sco) => visitor.visit_sub_class_of(r!(*sco)),
-            EquivalentClasses(ec) => visitor.visit_equivalent_classes(r!(*ec)),
-            DisjointClasses(dc) => visitor.visit_disjoint_classes(r!(*dc)),
-            DisjointUnion(du) => visitor.visit_disjoint_union(r!(*du)),
-            SubObjectPropertyOf(sopo) => visitor.visit_sub_object_property_of(r!(*sopo)),
-            EquivalentObjectProperties(eop) => visitor.visit_equivalent_object_properties(r!(*eop)),
-            DisjointObjectProperties(dop) => visitor.visit_disjoint_object_properties(r!(*dop)),
-            InverseObjectProperties(iop) => visitor.visit_inverse_object_properties(r!(*iop)),
-            ObjectPropertyDomain(opd) => visitor.visit_object_property_domain(r!(*opd)),
-            ObjectPropertyRange(opr) => visitor.visit_object_property_range(r!(*opr)),
-            FunctionalObjectProperty(fop) => visitor.visit_functional_object_property(r!(*fop)),
-            InverseFunctionalObjectProperty(ifop) => visitor.visit_inverse_functional_object_property(r!(*ifop)),
-            ReflexiveObjectProperty(rop) => visitor.visit_reflexive_object_property(r!(*rop)),
-            IrreflexiveObjectProperty(iop) => visitor.visit_irreflexive_object_property(r!(*iop)),
-            SymmetricObjectProperty(sop) => visitor.visit_symmetric_object_property(r!(*sop)),
-            AsymmetricObjectProperty(aop) => visitor.visit_asymmetric_object_property(r!(*aop)),
-            TransitiveObjectProperty(top) => visitor.visit_transitive_object_property(r!(*top)),
-            SubDataPropertyOf(sdpo) => visitor.visit_sub_data_property_of(r!(*sdpo)),
-            EquivalentDataProperties(edp) => visitor.visit_equivalent_data_properties(r!(*edp)),
-            DisjointDataProperties(ddp) => visitor.visit_disjoint_data_properties(r!(*ddp)),
-            DataPropertyDomain(dpd) => visitor.visit_data_property_domain(r!(*dpd)),
-            DataPropertyRange(dpr) => visitor.visit_data_property_range(r!(*dpr)),
-            FunctionalDataProperty(fdp) => visitor.visit_functional_data_property(r!(*fdp)),
-            DatatypeDefinition(dd) => visitor.visit_datatype_definition(r!(*dd)),
-            HasKey(hk) => visitor.visit_has_key(r!(*hk)),
+        match annotation_axiom {
+            AnnotationAssertion(aa) => visitor.visit_annotation_assertion(r!(*aa)),
+            SubAnnotationPropertyOf(sapo) => visitor.visit_sub_annotation_property_of(r!(*sapo)),
+            AnnotationPropertyDomain(apd) => visitor.visit_annotation_property_domain(r!(*apd)),
+            AnnotationPropertyRange(apr) => visitor.visit_annotation_property_range(r!(*apr)),
+            _ => (),
+        }
+    },
+
+    assertion(Axiom) => {
+        use self::Axiom::*;
+        match assertion {
             SameIndividual(si) => visitor.visit_same_individual(r!(*si)),
             DifferentIndividuals(di) => visitor.visit_different_individuals(r!(*di)),
             ClassAssertion(ca) => visitor.visit_class_assertion(r!(*ca)),
@@ -248,15 +308,37 @@ impl_default! { visitor,
             NegativeObjectPropertyAssertion(nopa) => visitor.visit_negative_object_property_assertion(r!(*nopa)),
             DataPropertyAssertion(dpa) => visitor.visit_data_property_assertion(r!(*dpa)),
             NegativeDataPropertyAssertion(ndpa) => visitor.visit_negative_data_property_assertion(r!(*ndpa)),
-            AnnotationAssertion(aa) => visitor.visit_annotation_assertion(r!(*aa)),
-            SubAnnotationPropertyOf(sapo) => visitor.visit_sub_annotation_property_of(r!(*sapo)),
-            AnnotationPropertyDomain(apd) => visitor.visit_annotation_property_domain(r!(*apd)),
-            AnnotationPropertyRange(apr) => visitor.visit_annotation_property_range(r!(*apr)),
+            _ => (),
+        }
+    },
+
+    axiom(Axiom) => {
+        use self::Axiom::*;
+        match axiom {
+            OntologyAnnotation(oa) => visitor.visit_ontology_annotation(r!(*oa)),
+            Import(import) => visitor.visit_import(r!(*import)),
+            DeclareClass(_) |
+            DeclareObjectProperty(_) |
+            DeclareAnnotationProperty(_) |
+            DeclareDataProperty(_) |
+            DeclareNamedIndividual(_) |
+            DeclareDatatype(_) => visitor.visit_declaration(axiom),
+            AnnotationAssertion(_) |
+            SubAnnotationPropertyOf(_) |
+            AnnotationPropertyDomain(_) |
+            AnnotationPropertyRange(_) => visitor.visit_annotation_axiom(axiom),
+            _ => visitor.visit_logical_axiom(axiom),
         }
     },
 
     class(Class) => {
-        visitor.visit_iri(r!(class.0));
+        if is_thing(&class.0) {
+            visitor.visit_thing(class);
+        } else if is_nothing(&class.0) {
+            visitor.visit_nothing(class);
+        } else {
+            visitor.visit_entity(NamedEntityKind::Class, r!(class.0));
+        }
     },
 
     class_assertion(ClassAssertion) => {
@@ -264,19 +346,30 @@ impl_default! { visitor,
         visitor.visit_individual(r!(class_assertion.i));
     },
 
+    class_axiom(Axiom) => {
+        use self::Axiom::*;
+        match class_axiom {
+            SubClassOf(sco) => visitor.visit_sub_class_of(r!(*sco)),
+            EquivalentClasses(ec) => visitor.visit_equivalent_classes(r!(*ec)),
+            DisjointClasses(dc) => visitor.visit_disjoint_classes(r!(*dc)),
+            DisjointUnion(du) => visitor.visit_disjoint_union(r!(*du)),
+            _ => (),
+        }
+    },
+
     class_expression(ClassExpression) => {
         use self::ClassExpression::*;
         match class_expression {
             Class(c) => visitor.visit_class(r!(*c)),
             ObjectIntersectionOf(ces) |
             ObjectUnionOf(ces) => {
-                for ce in r!(*ces).into_iter() {
+                for ce in r!(*ces) {
                     visitor.visit_class_expression(ce);
                 }
             }
             ObjectComplementOf(ce) => visitor.visit_class_expression(r!(*ce)),
             ObjectOneOf(is) => {
-                for i in r!(*is).into_iter() {
+                for i in r!(*is) {
                     visitor.visit_individual(i);
                 }
             }
@@ -309,7 +402,20 @@ impl_default! { visitor,
     },
 
     data_property(DataProperty) => {
-        visitor.visit_iri(r!(data_property.0));
+        visitor.visit_entity(NamedEntityKind::DataProperty, r!(data_property.0));
+    },
+
+    data_property_axiom(Axiom) => {
+        use self::Axiom::*;
+        match data_property_axiom {
+            SubDataPropertyOf(sdpo) => visitor.visit_sub_data_property_of(r!(*sdpo)),
+            EquivalentDataProperties(edp) => visitor.visit_equivalent_data_properties(r!(*edp)),
+            DisjointDataProperties(ddp) => visitor.visit_disjoint_data_properties(r!(*ddp)),
+            DataPropertyDomain(dpd) => visitor.visit_data_property_domain(r!(*dpd)),
+            DataPropertyRange(dpr) => visitor.visit_data_property_range(r!(*dpr)),
+            FunctionalDataProperty(fdp) => visitor.visit_functional_data_property(r!(*fdp)),
+            _ => (),
+        }
     },
 
     data_property_assertion(DataPropertyAssertion) => {
@@ -334,23 +440,23 @@ impl_default! { visitor,
             Datatype(dt) => visitor.visit_datatype(r!(*dt)),
             DataComplementOf(dr) => visitor.visit_data_range(r!(*dr)),
             DataIntersectionOf(drs) => {
-                for dr in r!(*drs).into_iter() {
+                for dr in r!(*drs) {
                     visitor.visit_data_range(dr);
                 }
             }
             DataUnionOf(drs) => {
-                for dr in r!(*drs).into_iter() {
+                for dr in r!(*drs) {
                     visitor.visit_data_range(dr);
                 }
             }
             DataOneOf(lits) => {
-                for lit in r!(*lits).into_iter() {
+                for lit in r!(*lits) {
                     visitor.visit_literal(lit);
                 }
             }
             DatatypeRestriction(dt, frs) => {
                 visitor.visit_datatype(dt);
-                for fr in r!(*frs).into_iter() {
+                for fr in r!(*frs) {
                     visitor.visit_facet_restriction(fr);
                 }
             }
@@ -358,7 +464,7 @@ impl_default! { visitor,
     },
 
     datatype(Datatype) => {
-        visitor.visit_iri(r!(datatype.0));
+        visitor.visit_entity(NamedEntityKind::Datatype, r!(datatype.0));
     },
 
     datatype_definition(DatatypeDefinition) => {
@@ -366,6 +472,19 @@ impl_default! { visitor,
         visitor.visit_data_range(r!(datatype_definition.range));
     },
 
+    declaration(Axiom) => {
+        use self::Axiom::*;
+        match declaration {
+            DeclareClass(dc) => visitor.visit_declare_class(r!(*dc)),
+            DeclareObjectProperty(dop) => visitor.visit_declare_object_property(r!(*dop)),
+            DeclareAnnotationProperty(dap) => visitor.visit_declare_annotation_property(r!(*dap)),
+            DeclareDataProperty(ddp) => visitor.visit_declare_data_property(r!(*ddp)),
+            DeclareNamedIndividual(dni) => visitor.visit_declare_named_individual(r!(*dni)),
+            DeclareDatatype(ddt) => visitor.visit_declare_datatype(r!(*ddt)),
+            _ => (),
+        }
+    },
+
     declare_annotation_property(DeclareAnnotationProperty) => {
         visitor.visit_annotation_property(r!(declare_annotation_property.0));
     },
@@ -391,50 +510,50 @@ impl_default! { visitor,
     },
 
     different_individuals(DifferentIndividuals) => {
-        for i in r!(different_individuals.0).into_iter() {
+        for i in r!(different_individuals.0) {
             visitor.visit_individual(i);
         }
     },
 
     disjoint_classes(DisjointClasses) => {
-        for ce in r!(disjoint_classes.0).into_iter() {
+        for ce in r!(disjoint_classes.0) {
             visitor.visit_class_expression(ce);
         }
     },
 
     disjoint_data_properties(DisjointDataProperties) => {
-        for dp in r!(disjoint_data_properties.0).into_iter() {
+        for dp in r!(disjoint_data_properties.0) {
             visitor.visit_data_property(dp);
         }
     },
 
     disjoint_object_properties(DisjointObjectProperties) => {
-        for ope in r!(disjoint_object_properties.0).into_iter() {
+        for ope in r!(disjoint_object_properties.0) {
             visitor.visit_object_property_expression(ope);
         }
     },
 
     disjoint_union(DisjointUnion) => {
         visitor.visit_class(r!(disjoint_union.0));
-        for ce in r!(disjoint_union.1).into_iter() {
+        for ce in r!(disjoint_union.1) {
             visitor.visit_class_expression(ce);
         }
     },
 
     equivalent_classes(EquivalentClasses) => {
-        for ce in r!(equivalent_classes.0).into_iter() {
+        for ce in r!(equivalent_classes.0) {
             visitor.visit_class_expression(ce);
         }
     },
 
     equivalent_data_properties(EquivalentDataProperties) => {
-        for dp in r!(equivalent_data_properties.0).into_iter() {
+        for dp in r!(equivalent_data_properties.0) {
             visitor.visit_data_property(dp);
         }
     },
 
     equivalent_object_properties(EquivalentObjectProperties) => {
-        for ope in r!(equivalent_object_properties.0).into_iter() {
+        for ope in r!(equivalent_object_properties.0) {
             visitor.visit_object_property_expression(ope);
         }
     },
@@ -456,7 +575,7 @@ impl_default! { visitor,
 
     has_key(HasKey) => {
         visitor.visit_class_expression(r!(has_key.ce));
-        for pe in r!(has_key.vpe).into_iter() {
+        for pe in r!(has_key.vpe) {
             visitor.visit_property_expression(pe);
         }
     },
@@ -490,8 +609,47 @@ impl_default! { visitor,
 
     literal(Literal) => {},
 
+    logical_axiom(Axiom) => {
+        use self::Axiom::*;
+        match logical_axiom {
+            SubClassOf(_) |
+            EquivalentClasses(_) |
+            DisjointClasses(_) |
+            DisjointUnion(_) => visitor.visit_class_axiom(logical_axiom),
+            SubObjectPropertyOf(_) |
+            EquivalentObjectProperties(_) |
+            DisjointObjectProperties(_) |
+            InverseObjectProperties(_) |
+            ObjectPropertyDomain(_) |
+            ObjectPropertyRange(_) |
+            FunctionalObjectProperty(_) |
+            InverseFunctionalObjectProperty(_) |
+            ReflexiveObjectProperty(_) |
+            IrreflexiveObjectProperty(_) |
+            SymmetricObjectProperty(_) |
+            AsymmetricObjectProperty(_) |
+            TransitiveObjectProperty(_) => visitor.visit_object_property_axiom(logical_axiom),
+            SubDataPropertyOf(_) |
+            EquivalentDataProperties(_) |
+            DisjointDataProperties(_) |
+            DataPropertyDomain(_) |
+            DataPropertyRange(_) |
+            FunctionalDataProperty(_) => visitor.visit_data_property_axiom(logical_axiom),
+            SameIndividual(_) |
+            DifferentIndividuals(_) |
+            ClassAssertion(_) |
+            ObjectPropertyAssertion(_) |
+            NegativeObjectPropertyAssertion(_) |
+            DataPropertyAssertion(_) |
+            NegativeDataPropertyAssertion(_) => visitor.visit_assertion(logical_axiom),
+            DatatypeDefinition(dd) => visitor.visit_datatype_definition(r!(*dd)),
+            HasKey(hk) => visitor.visit_has_key(r!(*hk)),
+            _ => (),
+        }
+    },
+
     named_individual(NamedIndividual) => {
-        visitor.visit_iri(r!(named_individual.0));
+        visitor.visit_entity(NamedEntityKind::NamedIndividual, r!(named_individual.0));
     },
 
     negative_data_property_assertion(NegativeDataPropertyAssertion) => {
@@ -506,8 +664,36 @@ impl_default! { visitor,
         visitor.visit_individual(r!(negative_object_property_assertion.to));
     },
 
+    nothing(Class) => {
+        visitor.visit_entity(NamedEntityKind::Class, r!(nothing.0));
+    },
+
     object_property(ObjectProperty) => {
-        visitor.visit_iri(r!(object_property.0));
+        if object_property.0.as_ref() == TOP_OBJECT_PROPERTY {
+            visitor.visit_top_object_property(object_property);
+        } else {
+            visitor.visit_entity(NamedEntityKind::ObjectProperty, r!(object_property.0));
+        }
+    },
+
+    object_property_axiom(Axiom) => {
+        use self::Axiom::*;
+        match object_property_axiom {
+            SubObjectPropertyOf(sopo) => visitor.visit_sub_object_property_of(r!(*sopo)),
+            EquivalentObjectProperties(eop) => visitor.visit_equivalent_object_properties(r!(*eop)),
+            DisjointObjectProperties(dop) => visitor.visit_disjoint_object_properties(r!(*dop)),
+            InverseObjectProperties(iop) => visitor.visit_inverse_object_properties(r!(*iop)),
+            ObjectPropertyDomain(opd) => visitor.visit_object_property_domain(r!(*opd)),
+            ObjectPropertyRange(opr) => visitor.visit_object_property_range(r!(*opr)),
+            FunctionalObjectProperty(fop) => visitor.visit_functional_object_property(r!(*fop)),
+            InverseFunctionalObjectProperty(ifop) => visitor.visit_inverse_functional_object_property(r!(*ifop)),
+            ReflexiveObjectProperty(rop) => visitor.visit_reflexive_object_property(r!(*rop)),
+            IrreflexiveObjectProperty(iop) => visitor.visit_irreflexive_object_property(r!(*iop)),
+            SymmetricObjectProperty(sop) => visitor.visit_symmetric_object_property(r!(*sop)),
+            AsymmetricObjectProperty(aop) => visitor.visit_asymmetric_object_property(r!(*aop)),
+            TransitiveObjectProperty(top) => visitor.visit_transitive_object_property(r!(*top)),
+            _ => (),
+        }
     },
 
     object_property_assertion(ObjectPropertyAssertion) => {
@@ -552,7 +738,7 @@ impl_default! { visitor,
     },
 
     same_individual(SameIndividual) => {
-        for i in r!(same_individual.0).into_iter() {
+        for i in r!(same_individual.0) {
             visitor.visit_individual(i);
         }
     },
@@ -577,7 +763,7 @@ impl_default! { visitor,
         match sub_object_property_expression {
             ObjectPropertyExpression(ope) => visitor.visit_object_property_expression(r!(*ope)),
             ObjectPropertyChain(opes) => {
-                for ope in r!(*opes).into_iter() {
+                for ope in r!(*opes) {
                     visitor.visit_object_property_expression(ope);
                 }
             }
@@ -593,6 +779,14 @@ impl_default! { visitor,
         visitor.visit_object_property_expression(r!(symmetric_object_property.0));
     },
 
+    thing(Class) => {
+        visitor.visit_entity(NamedEntityKind::Class, r!(thing.0));
+    },
+
+    top_object_property(ObjectProperty) => {
+        visitor.visit_entity(NamedEntityKind::ObjectProperty, r!(top_object_property.0));
+    },
+
     transitive_object_property(TransitiveObjectProperty) => {
         visitor.visit_object_property_expression(r!(transitive_object_property.0));
     },
@@ -0,0 +1,627 @@
+//! Extraction of syntactic locality-based modules.
+//!
+//! An axiom is *⊥-local* with respect to a signature when it becomes a
+//! tautology once every entity outside of the signature is interpreted as
+//! the empty set, and *⊤-local* when it becomes a tautology once every
+//! entity outside of the signature is interpreted as the whole domain.
+//! The [`LocalityChecker`] implements the syntactic approximation of these
+//! notions described in [Cuenca Grau *et al.*](https://doi.org/10.1613/jair.2375),
+//! by visiting each axiom and classifying its class expressions.
+//!
+//! The checker does not use the [`Polarized`](crate::polarity::Polarized)
+//! traversal: whether a class expression is equivalent to `owl:Nothing` or
+//! to `owl:Thing` depends on its operands, so the classification is
+//! computed bottom-up by two mutually recursive functions, which swap
+//! where [`polarity`](crate::polarity) would flip, e.g. under
+//! `ObjectComplementOf` or in the filler of `ObjectMaxCardinality`. A
+//! top-down traversal reporting occurrences one at a time cannot combine
+//! the results of the operands of intersections and unions.
+//!
+//! The [`ModuleExtractor`] computes the module of an ontology for a seed
+//! signature by collecting the axioms which are not local with respect to
+//! the seed, extended with the signature of the axioms collected so far.
+//! The resulting module preserves all the entailments of the ontology over
+//! the seed signature. The ⊥⊤\* modules, obtained by alternating the
+//! extraction of ⊥- and ⊤-modules until a fixpoint is reached, are usually
+//! the smallest.
+//!
+//! Non-logical axioms are always local: the declarations of the entities
+//! in the signature of the module are added to it, as well as their
+//! annotation assertions unless disabled with
+//! [`ModuleExtractor::annotations`].
+//!
+//! # Example
+//!
+//! ```rust
+//! # extern crate horned_owl;
+//! # extern crate horned_visit;
+//! # use std::fs::File;
+//! # use std::io::BufReader;
+//! use horned_owl::model::*;
+//! use horned_owl::ontology::set::SetOntology;
+//! use horned_visit::locality::ModuleType;
+//! use horned_visit::signature::Entity;
+//!
+//! let mut f = File::open("tests/data/bfo.owl").map(BufReader::new).unwrap();
+//! let ontology: SetOntology = horned_owl::io::rdf::reader::read(&mut f).unwrap().0.into();
+//!
+//! let b = Build::new();
+//! let material_entity = b.class("http://purl.obolibrary.org/obo/BFO_0000040");
+//! let independent_continuant = b.class("http://purl.obolibrary.org/obo/BFO_0000004");
+//! let process = b.class("http://purl.obolibrary.org/obo/BFO_0000015");
+//!
+//! let seed = std::iter::once(Entity::from(&material_entity)).collect();
+//! let module = horned_visit::locality::extract(&ontology, &seed, ModuleType::Bottom);
+//!
+//! let axiom = AnnotatedAxiom::from(SubClassOf {
+//!     sub: material_entity.into(),
+//!     sup: independent_continuant.into(),
+//! });
+//! assert!(module.iter().any(|aa| aa.axiom == axiom.axiom));
+//! assert!(!module.iter().any(|aa| aa.axiom == DeclareClass(process.clone()).into()));
+//! ```
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use horned_owl::model::*;
+use horned_owl::ontology::set::SetOntology;
+use horned_owl::vocab::is_nothing;
+use horned_owl::vocab::is_thing;
+use horned_owl::vocab::OWL2Datatype;
+use horned_owl::vocab::WithIRI;
+use horned_owl::vocab::OWL;
+
+use super::signature::signature;
+use super::signature::Entity;
+use super::visit;
+use super::Visit;
+use super::TOP_OBJECT_PROPERTY;
+
+/// The IRI of `owl:bottomObjectProperty`, missing from `horned_owl::vocab`.
+const BOTTOM_OBJECT_PROPERTY: &str = "http://www.w3.org/2002/07/owl#bottomObjectProperty";
+
+/// The IRI of `owl:bottomDataProperty`, missing from `horned_owl::vocab`.
+const BOTTOM_DATA_PROPERTY: &str = "http://www.w3.org/2002/07/owl#bottomDataProperty";
+
+/// The interpretation of the entities outside of the signature.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Locality {
+    /// Entities outside of the signature are interpreted as the empty set.
+    Bottom,
+    /// Entities outside of the signature are interpreted as the domain.
+    Top,
+}
+
+/// The kind of locality-based module to extract.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ModuleType {
+    /// A ⊥-module, which contains all the superclasses of the seed.
+    Bottom,
+    /// A ⊤-module, which contains all the subclasses of the seed.
+    Top,
+    /// A ⊥⊤\*-module, obtained by nesting ⊥- and ⊤-modules.
+    Star,
+}
+
+/// A visitor checking the syntactic locality of axioms.
+#[derive(Clone, Debug)]
+pub struct LocalityChecker<'s> {
+    locality: Locality,
+    signature: &'s BTreeSet<Entity>,
+    local: bool,
+}
+
+impl<'s> LocalityChecker<'s> {
+    /// Create a new checker for the given locality and signature.
+    pub fn new(locality: Locality, signature: &'s BTreeSet<Entity>) -> Self {
+        Self {
+            locality,
+            signature,
+            local: true,
+        }
+    }
+
+    /// Check whether an axiom is local with respect to the signature.
+    pub fn is_local(&mut self, axiom: &Axiom) -> bool {
+        self.visit_axiom(axiom);
+        self.local
+    }
+
+    /// Check whether an entity outside of the signature has the given interpretation.
+    fn is_outside(&self, kind: NamedEntityKind, iri: &IRI, locality: Locality) -> bool {
+        self.locality == locality && !self.signature.contains(&Entity::new(kind, iri.clone()))
+    }
+
+    /// Check whether a class expression is interpreted as the empty set.
+    fn is_bottom(&self, class_expression: &ClassExpression) -> bool {
+        use self::ClassExpression::*;
+        match class_expression {
+            Class(c) if is_nothing(&c.0) => true,
+            Class(c) if is_thing(&c.0) => false,
+            Class(c) => self.is_outside(NamedEntityKind::Class, &c.0, Locality::Bottom),
+            ObjectIntersectionOf(ces) => ces.iter().any(|ce| self.is_bottom(ce)),
+            ObjectUnionOf(ces) => ces.iter().all(|ce| self.is_bottom(ce)),
+            ObjectComplementOf(ce) => self.is_top(ce),
+            ObjectOneOf(individuals) => individuals.is_empty(),
+            ObjectSomeValuesFrom { ope, bce } => self.is_empty(ope) || self.is_bottom(bce),
+            ObjectAllValuesFrom { ope, bce } => self.is_universal(ope) && self.is_bottom(bce),
+            ObjectHasValue { ope, .. } => self.is_empty(ope),
+            ObjectHasSelf(ope) => self.is_empty(ope),
+            ObjectMinCardinality { n, ope, bce } | ObjectExactCardinality { n, ope, bce } => {
+                *n > 0 && (self.is_empty(ope) || self.is_bottom(bce))
+            }
+            ObjectMaxCardinality { .. } => false,
+            DataSomeValuesFrom { dp, .. } => self.is_empty_data(dp),
+            DataHasValue { dp, .. } => self.is_empty_data(dp),
+            DataMinCardinality { n, dp, .. } | DataExactCardinality { n, dp, .. } => {
+                *n > 0 && self.is_empty_data(dp)
+            }
+            DataAllValuesFrom { .. } | DataMaxCardinality { .. } => false,
+        }
+    }
+
+    /// Check whether a class expression is interpreted as the domain.
+    fn is_top(&self, class_expression: &ClassExpression) -> bool {
+        use self::ClassExpression::*;
+        match class_expression {
+            Class(c) if is_thing(&c.0) => true,
+            Class(c) if is_nothing(&c.0) => false,
+            Class(c) => self.is_outside(NamedEntityKind::Class, &c.0, Locality::Top),
+            ObjectIntersectionOf(ces) => ces.iter().all(|ce| self.is_top(ce)),
+            ObjectUnionOf(ces) => ces.iter().any(|ce| self.is_top(ce)),
+            ObjectComplementOf(ce) => self.is_bottom(ce),
+            ObjectOneOf(_) => false,
+            ObjectSomeValuesFrom { ope, bce } => self.is_universal(ope) && self.is_top(bce),
+            ObjectAllValuesFrom { ope, bce } => self.is_empty(ope) || self.is_top(bce),
+            ObjectHasValue { ope, .. } => self.is_universal(ope),
+            ObjectHasSelf(_) => false,
+            ObjectMinCardinality { n, ope, bce } => {
+                *n == 0 || (*n == 1 && self.is_universal(ope) && self.is_top(bce))
+            }
+            ObjectMaxCardinality { ope, bce, .. } => self.is_empty(ope) || self.is_bottom(bce),
+            ObjectExactCardinality { n, ope, bce } => {
+                *n == 0 && (self.is_empty(ope) || self.is_bottom(bce))
+            }
+            DataSomeValuesFrom { dp, dr } => self.is_universal_data(dp) && is_top_data_range(dr),
+            DataAllValuesFrom { dp, dr } => self.is_empty_data(dp) || is_top_data_range(dr),
+            DataHasValue { dp, .. } => self.is_universal_data(dp),
+            DataMinCardinality { n, dp, dr } => {
+                *n == 0 || (*n == 1 && self.is_universal_data(dp) && is_top_data_range(dr))
+            }
+            DataMaxCardinality { dp, .. } => self.is_empty_data(dp),
+            DataExactCardinality { n, dp, .. } => *n == 0 && self.is_empty_data(dp),
+        }
+    }
+
+    /// Check whether an object property expression is interpreted as the empty relation.
+    fn is_empty(&self, ope: &ObjectPropertyExpression) -> bool {
+        let op = match ope {
+            ObjectPropertyExpression::ObjectProperty(op) => op,
+            ObjectPropertyExpression::InverseObjectProperty(op) => op,
+        };
+        match op.0.as_ref() {
+            BOTTOM_OBJECT_PROPERTY => true,
+            TOP_OBJECT_PROPERTY => false,
+            _ => self.is_outside(NamedEntityKind::ObjectProperty, &op.0, Locality::Bottom),
+        }
+    }
+
+    /// Check whether an object property expression is interpreted as the universal relation.
+    fn is_universal(&self, ope: &ObjectPropertyExpression) -> bool {
+        let op = match ope {
+            ObjectPropertyExpression::ObjectProperty(op) => op,
+            ObjectPropertyExpression::InverseObjectProperty(op) => op,
+        };
+        match op.0.as_ref() {
+            TOP_OBJECT_PROPERTY => true,
+            BOTTOM_OBJECT_PROPERTY => false,
+            _ => self.is_outside(NamedEntityKind::ObjectProperty, &op.0, Locality::Top),
+        }
+    }
+
+    /// Check whether a data property is interpreted as the empty relation.
+    fn is_empty_data(&self, dp: &DataProperty) -> bool {
+        if dp.0.as_ref() == BOTTOM_DATA_PROPERTY {
+            true
+        } else if dp.0.as_ref() == OWL::TopDataProperty.iri_str() {
+            false
+        } else {
+            self.is_outside(NamedEntityKind::DataProperty, &dp.0, Locality::Bottom)
+        }
+    }
+
+    /// Check whether a data property is interpreted as the universal relation.
+    fn is_universal_data(&self, dp: &DataProperty) -> bool {
+        if dp.0.as_ref() == OWL::TopDataProperty.iri_str() {
+            true
+        } else if dp.0.as_ref() == BOTTOM_DATA_PROPERTY {
+            false
+        } else {
+            self.is_outside(NamedEntityKind::DataProperty, &dp.0, Locality::Top)
+        }
+    }
+}
+
+/// Check whether a data range is `rdfs:Literal`.
+fn is_top_data_range(data_range: &DataRange) -> bool {
+    match data_range {
+        DataRange::Datatype(dt) => dt.0.as_ref() == OWL2Datatype::RDFSLiteral.iri_str(),
+        _ => false,
+    }
+}
+
+impl<'ast> Visit<'ast> for LocalityChecker<'_> {
+    fn visit_axiom(&mut self, axiom: &'ast Axiom) {
+        self.local = true;
+        visit::visit_axiom(self, axiom);
+    }
+
+    fn visit_asymmetric_object_property(&mut self, axiom: &'ast AsymmetricObjectProperty) {
+        self.local = self.is_empty(&axiom.0);
+    }
+
+    fn visit_class_assertion(&mut self, axiom: &'ast ClassAssertion) {
+        self.local = self.is_top(&axiom.ce);
+    }
+
+    fn visit_data_property_assertion(&mut self, axiom: &'ast DataPropertyAssertion) {
+        self.local = self.is_universal_data(&axiom.dp);
+    }
+
+    fn visit_data_property_domain(&mut self, axiom: &'ast DataPropertyDomain) {
+        self.local = self.is_empty_data(&axiom.dp) || self.is_top(&axiom.ce);
+    }
+
+    fn visit_data_property_range(&mut self, axiom: &'ast DataPropertyRange) {
+        self.local = self.is_empty_data(&axiom.dp) || is_top_data_range(&axiom.dr);
+    }
+
+    fn visit_datatype_definition(&mut self, _axiom: &'ast DatatypeDefinition) {
+        self.local = false;
+    }
+
+    fn visit_different_individuals(&mut self, _axiom: &'ast DifferentIndividuals) {
+        self.local = false;
+    }
+
+    fn visit_disjoint_classes(&mut self, axiom: &'ast DisjointClasses) {
+        self.local = axiom.0.iter().filter(|ce| !self.is_bottom(ce)).count() <= 1;
+    }
+
+    fn visit_disjoint_data_properties(&mut self, axiom: &'ast DisjointDataProperties) {
+        self.local = axiom.0.iter().filter(|dp| !self.is_empty_data(dp)).count() <= 1;
+    }
+
+    fn visit_disjoint_object_properties(&mut self, axiom: &'ast DisjointObjectProperties) {
+        self.local = axiom.0.iter().filter(|ope| !self.is_empty(ope)).count() <= 1;
+    }
+
+    fn visit_disjoint_union(&mut self, axiom: &'ast DisjointUnion) {
+        let class = ClassExpression::Class(axiom.0.clone());
+        let disjoint = axiom.1.iter().filter(|ce| !self.is_bottom(ce)).count() <= 1;
+        let equivalent = (self.is_bottom(&class) && axiom.1.iter().all(|ce| self.is_bottom(ce)))
+            || (self.is_top(&class) && axiom.1.iter().any(|ce| self.is_top(ce)));
+        self.local = disjoint && equivalent;
+    }
+
+    fn visit_equivalent_classes(&mut self, axiom: &'ast EquivalentClasses) {
+        self.local =
+            axiom.0.iter().all(|ce| self.is_bottom(ce)) || axiom.0.iter().all(|ce| self.is_top(ce));
+    }
+
+    fn visit_equivalent_data_properties(&mut self, axiom: &'ast EquivalentDataProperties) {
+        self.local = axiom.0.iter().all(|dp| self.is_empty_data(dp))
+            || axiom.0.iter().all(|dp| self.is_universal_data(dp));
+    }
+
+    fn visit_equivalent_object_properties(&mut self, axiom: &'ast EquivalentObjectProperties) {
+        self.local = axiom.0.iter().all(|ope| self.is_empty(ope))
+            || axiom.0.iter().all(|ope| self.is_universal(ope));
+    }
+
+    fn visit_functional_data_property(&mut self, axiom: &'ast FunctionalDataProperty) {
+        self.local = self.is_empty_data(&axiom.0);
+    }
+
+    fn visit_functional_object_property(&mut self, axiom: &'ast FunctionalObjectProperty) {
+        self.local = self.is_empty(&axiom.0);
+    }
+
+    fn visit_has_key(&mut self, axiom: &'ast HasKey) {
+        self.local = self.is_bottom(&axiom.ce);
+    }
+
+    fn visit_inverse_functional_object_property(
+        &mut self,
+        axiom: &'ast InverseFunctionalObjectProperty,
+    ) {
+        self.local = self.is_empty(&axiom.0);
+    }
+
+    fn visit_inverse_object_properties(&mut self, axiom: &'ast InverseObjectProperties) {
+        let first = ObjectPropertyExpression::ObjectProperty(axiom.0.clone());
+        let second = ObjectPropertyExpression::ObjectProperty(axiom.1.clone());
+        self.local = (self.is_empty(&first) && self.is_empty(&second))
+            || (self.is_universal(&first) && self.is_universal(&second));
+    }
+
+    fn visit_irreflexive_object_property(&mut self, axiom: &'ast IrreflexiveObjectProperty) {
+        self.local = self.is_empty(&axiom.0);
+    }
+
+    fn visit_negative_data_property_assertion(
+        &mut self,
+        axiom: &'ast NegativeDataPropertyAssertion,
+    ) {
+        self.local = self.is_empty_data(&axiom.dp);
+    }
+
+    fn visit_negative_object_property_assertion(
+        &mut self,
+        axiom: &'ast NegativeObjectPropertyAssertion,
+    ) {
+        self.local = self.is_empty(&axiom.ope);
+    }
+
+    fn visit_object_property_assertion(&mut self, axiom: &'ast ObjectPropertyAssertion) {
+        self.local = self.is_universal(&axiom.ope);
+    }
+
+    fn visit_object_property_domain(&mut self, axiom: &'ast ObjectPropertyDomain) {
+        self.local = self.is_empty(&axiom.ope) || self.is_top(&axiom.ce);
+    }
+
+    fn visit_object_property_range(&mut self, axiom: &'ast ObjectPropertyRange) {
+        self.local = self.is_empty(&axiom.ope) || self.is_top(&axiom.ce);
+    }
+
+    fn visit_reflexive_object_property(&mut self, axiom: &'ast ReflexiveObjectProperty) {
+        self.local = self.is_universal(&axiom.0);
+    }
+
+    fn visit_same_individual(&mut self, _axiom: &'ast SameIndividual) {
+        self.local = false;
+    }
+
+    fn visit_sub_class_of(&mut self, axiom: &'ast SubClassOf) {
+        self.local = self.is_bottom(&axiom.sub) || self.is_top(&axiom.sup);
+    }
+
+    fn visit_sub_data_property_of(&mut self, axiom: &'ast SubDataPropertyOf) {
+        self.local = self.is_empty_data(&axiom.sub) || self.is_universal_data(&axiom.sup);
+    }
+
+    fn visit_sub_object_property_of(&mut self, axiom: &'ast SubObjectPropertyOf) {
+        let empty = match &axiom.sub {
+            SubObjectPropertyExpression::ObjectPropertyChain(chain) => {
+                chain.iter().any(|ope| self.is_empty(ope))
+            }
+            SubObjectPropertyExpression::ObjectPropertyExpression(ope) => self.is_empty(ope),
+        };
+        self.local = empty || self.is_universal(&axiom.sup);
+    }
+
+    fn visit_symmetric_object_property(&mut self, axiom: &'ast SymmetricObjectProperty) {
+        self.local = self.is_empty(&axiom.0) || self.is_universal(&axiom.0);
+    }
+
+    fn visit_transitive_object_property(&mut self, axiom: &'ast TransitiveObjectProperty) {
+        self.local = self.is_empty(&axiom.0) || self.is_universal(&axiom.0);
+    }
+}
+
+/// An extractor of syntactic locality-based modules.
+#[derive(Clone, Debug)]
+pub struct ModuleExtractor {
+    module_type: ModuleType,
+    annotations: bool,
+}
+
+impl ModuleExtractor {
+    /// Create a new extractor for the given module type.
+    pub fn new(module_type: ModuleType) -> Self {
+        Self {
+            module_type,
+            annotations: true,
+        }
+    }
+
+    /// Set whether to add the annotation assertions of the module entities.
+    pub fn annotations(mut self, annotations: bool) -> Self {
+        self.annotations = annotations;
+        self
+    }
+
+    /// Extract the module of an ontology for the given seed signature.
+    pub fn extract(&self, ontology: &SetOntology, seed: &BTreeSet<Entity>) -> SetOntology {
+        let mut axioms: Vec<&AnnotatedAxiom> = ontology.iter().collect();
+        match self.module_type {
+            ModuleType::Bottom => axioms = module(axioms, Locality::Bottom, seed),
+            ModuleType::Top => axioms = module(axioms, Locality::Top, seed),
+            ModuleType::Star => loop {
+                let size = axioms.len();
+                axioms = module(axioms, Locality::Bottom, seed);
+                axioms = module(axioms, Locality::Top, seed);
+                if axioms.len() == size {
+                    break;
+                }
+            },
+        }
+
+        // collect the signature of the module
+        let mut entities = seed.clone();
+        for aa in axioms.iter() {
+            entities.extend(signature(&aa.axiom));
+        }
+
+        // add the annotation assertions of the module entities
+        if self.annotations {
+            let iris: BTreeSet<IRI> = entities.iter().map(|e| e.iri.clone()).collect();
+            for aa in ontology.iter() {
+                if let Axiom::AnnotationAssertion(AnnotationAssertion {
+                    subject: AnnotationSubject::IRI(iri),
+                    ..
+                }) = &aa.axiom
+                {
+                    if iris.contains(iri) {
+                        entities.extend(signature(&aa.axiom));
+                        axioms.push(aa);
+                    }
+                }
+            }
+        }
+
+        // add the declarations of the module entities
+        for aa in ontology.iter() {
+            let declaration = matches!(
+                aa.axiom,
+                Axiom::DeclareClass(_)
+                    | Axiom::DeclareObjectProperty(_)
+                    | Axiom::DeclareAnnotationProperty(_)
+                    | Axiom::DeclareDataProperty(_)
+                    | Axiom::DeclareNamedIndividual(_)
+                    | Axiom::DeclareDatatype(_)
+            );
+            if declaration && signature(&aa.axiom).is_subset(&entities) {
+                axioms.push(aa);
+            }
+        }
+
+        axioms.into_iter().cloned().collect()
+    }
+}
+
+/// Collect the axioms which are not local with respect to the module signature.
+///
+/// The locality of an axiom can only change when one of the entities of its
+/// signature is added to the module signature, so every axiom is checked
+/// once against the seed, and then only rechecked when an axiom using one
+/// of its entities is added to the module.
+fn module<'a>(
+    axioms: Vec<&'a AnnotatedAxiom>,
+    locality: Locality,
+    seed: &BTreeSet<Entity>,
+) -> Vec<&'a AnnotatedAxiom> {
+    // index the axioms by the entities of their signature
+    let signatures: Vec<BTreeSet<Entity>> = axioms.iter().map(|aa| signature(&aa.axiom)).collect();
+    let mut index = BTreeMap::<&Entity, Vec<usize>>::new();
+    for (i, entities) in signatures.iter().enumerate() {
+        for entity in entities.iter().filter(|entity| !seed.contains(entity)) {
+            index.entry(entity).or_default().push(i);
+        }
+    }
+
+    let mut entities = seed.clone();
+    let mut included = vec![false; axioms.len()];
+    let mut module = Vec::new();
+    let mut pending: Vec<usize> = (0..axioms.len()).rev().collect();
+    while let Some(i) = pending.pop() {
+        if included[i] || LocalityChecker::new(locality, &entities).is_local(&axioms[i].axiom) {
+            continue;
+        }
+        included[i] = true;
+        module.push(axioms[i]);
+        for entity in signatures[i].iter() {
+            if entities.insert(entity.clone()) {
+                let users = index.remove(entity).into_iter().flatten();
+                pending.extend(users.filter(|&j| !included[j]));
+            }
+        }
+    }
+    module
+}
+
+/// Extract the module of an ontology for the given seed signature.
+pub fn extract(
+    ontology: &SetOntology,
+    seed: &BTreeSet<Entity>,
+    module_type: ModuleType,
+) -> SetOntology {
+    ModuleExtractor::new(module_type).extract(ontology, seed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::*;
+
+    fn seed(names: &[&str]) -> BTreeSet<Entity> {
+        names
+            .iter()
+            .map(|name| Entity::from(named_class(name)))
+            .collect()
+    }
+
+    fn exists() -> ClassExpression {
+        some("r", class("G"))
+    }
+
+    /// An ontology with `D ⊑ A ⊑ B ⊑ C`, `A ⊑ ∃r.G` and `E ⊑ F`.
+    fn example_ontology() -> SetOntology {
+        ontology(vec![
+            sub_class_of(class("D"), class("A")),
+            sub_class_of(class("A"), class("B")),
+            sub_class_of(class("B"), class("C")),
+            sub_class_of(class("A"), exists()),
+            sub_class_of(class("E"), class("F")),
+        ])
+    }
+
+    fn module(seed: &BTreeSet<Entity>, module_type: ModuleType) -> BTreeSet<Axiom> {
+        extract(&example_ontology(), seed, module_type)
+            .iter()
+            .map(|aa| aa.axiom.clone())
+            .collect()
+    }
+
+    #[test]
+    fn bottom_module() {
+        let module = module(&seed(&["A"]), ModuleType::Bottom);
+        let expected = vec![
+            sub_class_of(class("A"), class("B")),
+            sub_class_of(class("B"), class("C")),
+            sub_class_of(class("A"), exists()),
+        ];
+        assert_eq!(module, expected.into_iter().collect());
+    }
+
+    #[test]
+    fn top_module() {
+        let module = module(&seed(&["A"]), ModuleType::Top);
+        let expected = vec![sub_class_of(class("D"), class("A"))];
+        assert_eq!(module, expected.into_iter().collect());
+    }
+
+    #[test]
+    fn star_module() {
+        assert!(module(&seed(&["A"]), ModuleType::Star).is_empty());
+
+        let module = module(&seed(&["A", "B"]), ModuleType::Star);
+        let expected = vec![sub_class_of(class("A"), class("B"))];
+        assert_eq!(module, expected.into_iter().collect());
+    }
+
+    #[test]
+    fn star_module_is_smallest() {
+        let seed = seed(&["A", "B"]);
+        let star = module(&seed, ModuleType::Star);
+        assert!(star.is_subset(&module(&seed, ModuleType::Bottom)));
+        assert!(star.is_subset(&module(&seed, ModuleType::Top)));
+    }
+
+    #[test]
+    fn module_declarations() {
+        let mut ontology = example_ontology();
+        for name in ["A", "B", "E"] {
+            ontology.declare(named_class(name));
+        }
+        let module = extract(&ontology, &seed(&["A", "B"]), ModuleType::Star);
+        let declared: BTreeSet<&Axiom> = module
+            .iter()
+            .map(|aa| &aa.axiom)
+            .filter(|axiom| matches!(axiom, Axiom::DeclareClass(_)))
+            .collect();
+        assert_eq!(declared.len(), 2);
+    }
+}
@@ -0,0 +1,363 @@
+//! Extraction of terms following the MIREOT guidelines.
+//!
+//! The [Minimum Information to Reference an External Ontology Term](https://doi.org/10.3233/AO-2011-0087)
+//! guidelines describe a lightweight alternative to importing a complete
+//! ontology: only the referenced terms are copied, along with their
+//! declarations, some of their annotations, and their told named
+//! superclasses up to an upper boundary.
+//!
+//! The [`MireotExtractor`] walks the told hierarchies built by the
+//! [`hierarchy`](crate::hierarchy) module from every requested term,
+//! stopping at the upper boundary terms, or at the roots of the hierarchy
+//! if no boundary is given. Each traversed edge is copied as a plain
+//! subsumption axiom, and the declarations and annotation assertions of all
+//! the traversed entities are copied from the source ontology.
+//!
+//! # Example
+//!
+//! ```rust
+//! # extern crate horned_owl;
+//! # extern crate horned_visit;
+//! # use std::fs::File;
+//! # use std::io::BufReader;
+//! use horned_owl::model::*;
+//! use horned_owl::ontology::set::SetOntology;
+//! use horned_visit::mireot::MireotExtractor;
+//!
+//! let mut f = File::open("tests/data/bfo.owl").map(BufReader::new).unwrap();
+//! let ontology: SetOntology = horned_owl::io::rdf::reader::read(&mut f).unwrap().0.into();
+//!
+//! let b = Build::new();
+//! let entity = b.class("http://purl.obolibrary.org/obo/BFO_0000001");
+//! let continuant = b.class("http://purl.obolibrary.org/obo/BFO_0000002");
+//! let material_entity = b.class("http://purl.obolibrary.org/obo/BFO_0000040");
+//! let label = b.annotation_property("http://www.w3.org/2000/01/rdf-schema#label");
+//!
+//! let extract = MireotExtractor::new()
+//!     .upper(vec![continuant.0.clone()])
+//!     .annotation_properties(vec![label.clone()])
+//!     .extract(&ontology, &[material_entity.0.clone()]);
+//!
+//! let axioms: Vec<Axiom> = extract.iter().map(|aa| aa.axiom.clone()).collect();
+//! assert!(axioms.contains(&DeclareClass(continuant.clone()).into()));
+//! assert!(!axioms.contains(&DeclareClass(entity.clone()).into()));
+//! assert!(!axioms.contains(
+//!     &SubClassOf {
+//!         sub: continuant.into(),
+//!         sup: entity.into()
+//!     }
+//!     .into()
+//! ));
+//! ```
+
+use std::collections::BTreeSet;
+
+use horned_owl::model::*;
+use horned_owl::ontology::set::SetOntology;
+
+use super::hierarchy::hierarchies;
+use super::hierarchy::Hierarchy;
+
+/// An extractor of terms with their told ancestors and annotations.
+#[derive(Clone, Debug, Default)]
+pub struct MireotExtractor {
+    upper: BTreeSet<IRI>,
+    properties: Option<BTreeSet<AnnotationProperty>>,
+}
+
+impl MireotExtractor {
+    /// Create a new extractor copying all the ancestors and annotations.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the upper boundary terms, above which ancestors are not copied.
+    pub fn upper<I>(mut self, upper: I) -> Self
+    where
+        I: IntoIterator<Item = IRI>,
+    {
+        self.upper = upper.into_iter().collect();
+        self
+    }
+
+    /// Restrict the copied annotation assertions to the given properties.
+    pub fn annotation_properties<I>(mut self, properties: I) -> Self
+    where
+        I: IntoIterator<Item = AnnotationProperty>,
+    {
+        self.properties = Some(properties.into_iter().collect());
+        self
+    }
+
+    /// Extract the given terms from an ontology into a new ontology.
+    pub fn extract(&self, ontology: &SetOntology, terms: &[IRI]) -> SetOntology {
+        let hierarchies = hierarchies(ontology);
+        let mut iris: BTreeSet<IRI> = terms.iter().cloned().collect();
+        let mut axioms: Vec<AnnotatedAxiom> = Vec::new();
+
+        // copy the told ancestors of the terms
+        for (sub, sup) in self.ancestry(&hierarchies.classes, terms) {
+            iris.insert(sub.0.clone());
+            iris.insert(sup.0.clone());
+            axioms.push(
+                SubClassOf {
+                    sub: sub.into(),
+                    sup: sup.into(),
+                }
+                .into(),
+            );
+        }
+        for (sub, sup) in self.ancestry(&hierarchies.object_properties, terms) {
+            iris.insert(sub.0.clone());
+            iris.insert(sup.0.clone());
+            axioms.push(
+                SubObjectPropertyOf {
+                    sub: SubObjectPropertyExpression::ObjectPropertyExpression(sub.into()),
+                    sup: sup.into(),
+                }
+                .into(),
+            );
+        }
+        for (sub, sup) in self.ancestry(&hierarchies.data_properties, terms) {
+            iris.insert(sub.0.clone());
+            iris.insert(sup.0.clone());
+            axioms.push(SubDataPropertyOf { sub, sup }.into());
+        }
+        for (sub, sup) in self.ancestry(&hierarchies.annotation_properties, terms) {
+            iris.insert(sub.0.clone());
+            iris.insert(sup.0.clone());
+            axioms.push(SubAnnotationPropertyOf { sub, sup }.into());
+        }
+
+        // copy the selected annotation assertions of the extracted entities
+        let mut used = BTreeSet::new();
+        for aa in ontology.iter() {
+            if let Axiom::AnnotationAssertion(AnnotationAssertion {
+                subject: AnnotationSubject::IRI(iri),
+                ann,
+            }) = &aa.axiom
+            {
+                let selected = match &self.properties {
+                    Some(properties) => properties.contains(&ann.ap),
+                    None => true,
+                };
+                if selected && iris.contains(iri) {
+                    used.insert(ann.ap.0.clone());
+                    axioms.push(aa.clone());
+                }
+            }
+        }
+
+        // copy the declarations of the extracted entities
+        for aa in ontology.iter() {
+            if let Some(iri) = declared(&aa.axiom) {
+                let property = matches!(aa.axiom, Axiom::DeclareAnnotationProperty(_));
+                if iris.contains(iri) || (property && used.contains(iri)) {
+                    axioms.push(aa.clone());
+                }
+            }
+        }
+
+        axioms.into_iter().collect()
+    }
+
+    /// Collect the edges from the given terms to their ancestors.
+    fn ancestry<T>(&self, hierarchy: &Hierarchy<T>, terms: &[IRI]) -> BTreeSet<(T, T)>
+    where
+        T: Ord + Clone + From<IRI>,
+    {
+        let upper: BTreeSet<T> = self.upper.iter().cloned().map(T::from).collect();
+        let mut queue: Vec<T> = terms
+            .iter()
+            .cloned()
+            .map(T::from)
+            .filter(|node| hierarchy.contains(node))
+            .collect();
+        let mut seen: BTreeSet<T> = queue.iter().cloned().collect();
+        let mut edges = BTreeSet::new();
+        while let Some(node) = queue.pop() {
+            if upper.contains(&node) {
+                continue;
+            }
+            for parent in hierarchy.parents(&node) {
+                edges.insert((node.clone(), parent.clone()));
+                if seen.insert(parent.clone()) {
+                    queue.push(parent.clone());
+                }
+            }
+        }
+        edges
+    }
+}
+
+/// Get the IRI of the entity declared by a declaration axiom.
+fn declared(axiom: &Axiom) -> Option<&IRI> {
+    match axiom {
+        Axiom::DeclareClass(DeclareClass(c)) => Some(&c.0),
+        Axiom::DeclareObjectProperty(DeclareObjectProperty(op)) => Some(&op.0),
+        Axiom::DeclareAnnotationProperty(DeclareAnnotationProperty(ap)) => Some(&ap.0),
+        Axiom::DeclareDataProperty(DeclareDataProperty(dp)) => Some(&dp.0),
+        Axiom::DeclareNamedIndividual(DeclareNamedIndividual(ni)) => Some(&ni.0),
+        Axiom::DeclareDatatype(DeclareDatatype(dt)) => Some(&dt.0),
+        _ => None,
+    }
+}
+
+/// Extract the given terms from an ontology, up to the given upper terms.
+pub fn extract(ontology: &SetOntology, terms: &[IRI], upper: &[IRI]) -> SetOntology {
+    MireotExtractor::new()
+        .upper(upper.iter().cloned())
+        .extract(ontology, terms)
+}
+
+#[cfg(test)]
+mod tests {
+    use horned_owl::vocab::AnnotationBuiltIn;
+    use horned_owl::vocab::WithIRI;
+
+    use super::*;
+    use crate::fixtures::*;
+
+    fn annotation(subject: &str, ap: &AnnotationProperty, text: &str) -> AnnotatedAxiom {
+        AnnotationAssertion {
+            subject: AnnotationSubject::IRI(iri(subject)),
+            ann: Annotation {
+                ap: ap.clone(),
+                av: AnnotationValue::Literal(Literal::Simple {
+                    literal: text.into(),
+                }),
+            },
+        }
+        .into()
+    }
+
+    fn label() -> AnnotationProperty {
+        Build::new().annotation_property(AnnotationBuiltIn::LABEL.iri_str())
+    }
+
+    fn comment() -> AnnotationProperty {
+        Build::new().annotation_property(AnnotationBuiltIn::COMMENT.iri_str())
+    }
+
+    /// The ontology `D ⊑ C ⊑ B ⊑ A` with an unrelated class `E`.
+    fn example_ontology() -> SetOntology {
+        let mut ontology = SetOntology::new();
+        for name in ["A", "B", "C", "D", "E"] {
+            ontology.declare(named_class(name));
+            ontology.insert(annotation(name, &label(), name));
+        }
+        ontology.insert(sub_class_of(class("D"), class("C")));
+        ontology.insert(sub_class_of(class("C"), class("B")));
+        ontology.insert(sub_class_of(class("B"), class("A")));
+        ontology.insert(annotation("D", &comment(), "a D"));
+        ontology
+    }
+
+    fn axioms(ontology: &SetOntology) -> BTreeSet<Axiom> {
+        ontology.iter().map(|aa| aa.axiom.clone()).collect()
+    }
+
+    #[test]
+    fn stop_at_upper_boundary() {
+        let extract = extract(&example_ontology(), &[iri("D")], &[iri("B")]);
+        let axioms = axioms(&extract);
+        assert!(axioms.contains(&sub_class_of(class("D"), class("C"))));
+        assert!(axioms.contains(&sub_class_of(class("C"), class("B"))));
+        assert!(!axioms.contains(&sub_class_of(class("B"), class("A"))));
+        assert!(axioms.contains(&DeclareClass(named_class("B")).into()));
+        assert!(!axioms.contains(&DeclareClass(named_class("A")).into()));
+        assert!(!axioms.contains(&annotation("A", &label(), "A").axiom));
+    }
+
+    #[test]
+    fn walk_to_the_roots() {
+        let extract = extract(&example_ontology(), &[iri("C")], &[]);
+        let axioms = axioms(&extract);
+        assert!(axioms.contains(&sub_class_of(class("C"), class("B"))));
+        assert!(axioms.contains(&sub_class_of(class("B"), class("A"))));
+        assert!(!axioms.contains(&sub_class_of(class("D"), class("C"))));
+        assert!(!axioms.contains(&DeclareClass(named_class("D")).into()));
+        assert!(!axioms.contains(&DeclareClass(named_class("E")).into()));
+    }
+
+    #[test]
+    fn filter_annotations() {
+        let all = MireotExtractor::new().extract(&example_ontology(), &[iri("D")]);
+        assert!(axioms(&all).contains(&annotation("D", &comment(), "a D").axiom));
+
+        let labels = MireotExtractor::new()
+            .annotation_properties(vec![label()])
+            .extract(&example_ontology(), &[iri("D")]);
+        let axioms = axioms(&labels);
+        assert!(axioms.contains(&annotation("D", &label(), "D").axiom));
+        assert!(axioms.contains(&annotation("A", &label(), "A").axiom));
+        assert!(!axioms.contains(&annotation("D", &comment(), "a D").axiom));
+    }
+
+    #[test]
+    fn declare_used_annotation_properties() {
+        let definition = Build::new().annotation_property("http://example.com/definition");
+        let unused = Build::new().annotation_property("http://example.com/unused");
+        let mut ontology = example_ontology();
+        ontology.declare(definition.clone());
+        ontology.declare(unused.clone());
+        ontology.insert(annotation("C", &definition, "a C"));
+
+        let axioms = axioms(&extract(&ontology, &[iri("C")], &[]));
+        assert!(axioms.contains(&annotation("C", &definition, "a C").axiom));
+        assert!(axioms.contains(&DeclareAnnotationProperty(definition).into()));
+        assert!(!axioms.contains(&DeclareAnnotationProperty(unused).into()));
+    }
+
+    #[test]
+    fn property_hierarchies() {
+        let b = Build::new();
+        let mut ontology = SetOntology::new();
+        ontology.insert(SubObjectPropertyOf {
+            sub: SubObjectPropertyExpression::ObjectPropertyExpression(
+                ObjectProperty(iri("r")).into(),
+            ),
+            sup: ObjectProperty(iri("s")).into(),
+        });
+        ontology.insert(SubObjectPropertyOf {
+            sub: SubObjectPropertyExpression::ObjectPropertyExpression(
+                ObjectProperty(iri("s")).into(),
+            ),
+            sup: ObjectProperty(iri("t")).into(),
+        });
+        ontology.insert(SubDataPropertyOf {
+            sub: DataProperty(iri("d")),
+            sup: DataProperty(iri("e")),
+        });
+        ontology.insert(SubAnnotationPropertyOf {
+            sub: AnnotationProperty(iri("p")),
+            sup: AnnotationProperty(iri("q")),
+        });
+        ontology.declare(b.object_property("http://example.com/s"));
+
+        let extract = extract(&ontology, &[iri("r"), iri("d"), iri("p")], &[iri("s")]);
+        let expected: BTreeSet<Axiom> = vec![
+            SubObjectPropertyOf {
+                sub: SubObjectPropertyExpression::ObjectPropertyExpression(
+                    ObjectProperty(iri("r")).into(),
+                ),
+                sup: ObjectProperty(iri("s")).into(),
+            }
+            .into(),
+            SubDataPropertyOf {
+                sub: DataProperty(iri("d")),
+                sup: DataProperty(iri("e")),
+            }
+            .into(),
+            SubAnnotationPropertyOf {
+                sub: AnnotationProperty(iri("p")),
+                sup: AnnotationProperty(iri("q")),
+            }
+            .into(),
+            DeclareObjectProperty(b.object_property("http://example.com/s")).into(),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(axioms(&extract), expected);
+    }
+}
@@ -0,0 +1,830 @@
+//! Serialisable patches turning an ontology into another one.
+//!
+//! A [`Patch`] is a set of annotated axioms to remove from an ontology and
+//! a set of annotated axioms to insert into it, usually obtained from a
+//! [`Diff`](crate::diff::Diff). It is written as one axiom per line in the
+//! OWL2 functional-style syntax rendered by the [`display`](crate::display)
+//! module, prefixed with `-` for removed axioms and `+` for added axioms:
+//!
+//! ```text
+//! - SubClassOf(<http://example.com/A> <http://example.com/B>)
+//! + SubClassOf(<http://example.com/A> <http://example.com/C>)
+//! ```
+//!
+//! Patches can be read back with [`str::parse`], ignoring empty lines and
+//! lines starting with `#`. An axiom spans several lines when one of its
+//! literals contains line breaks. IRIs must be written in full, since
+//! prefixes are not supported.
+//!
+//! # Example
+//!
+//! ```rust
+//! # extern crate horned_owl;
+//! # extern crate horned_visit;
+//! use horned_owl::model::*;
+//! use horned_owl::ontology::set::SetOntology;
+//! use horned_visit::patch::Patch;
+//!
+//! let b = Build::new();
+//! let mut old = SetOntology::new();
+//! old.insert(SubClassOf {
+//!     sub: b.class("http://example.com/A").into(),
+//!     sup: b.class("http://example.com/B").into(),
+//! });
+//!
+//! let mut new = SetOntology::new();
+//! new.insert(SubClassOf {
+//!     sub: b.class("http://example.com/A").into(),
+//!     sup: b.class("http://example.com/C").into(),
+//! });
+//!
+//! let text = horned_visit::diff::diff(&old, &new).patch().to_string();
+//! let patch: Patch = text.parse().unwrap();
+//! patch.apply(&mut old);
+//! assert_eq!(old, new);
+//! ```
+
+use std::collections::BTreeSet;
+use std::error::Error;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+use std::str::FromStr;
+
+use horned_owl::model::*;
+use horned_owl::vocab::WithIRI;
+
+use super::display::Functional;
+use super::sink::AxiomSink;
+
+/// A set of axioms to remove from an ontology and to insert into it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Patch {
+    removed: BTreeSet<AnnotatedAxiom>,
+    added: BTreeSet<AnnotatedAxiom>,
+}
+
+impl Patch {
+    /// Create a new empty patch.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the axioms removed by the patch.
+    pub fn removed(&self) -> &BTreeSet<AnnotatedAxiom> {
+        &self.removed
+    }
+
+    /// Get the axioms added by the patch.
+    pub fn added(&self) -> &BTreeSet<AnnotatedAxiom> {
+        &self.added
+    }
+
+    /// Record the removal of an axiom.
+    pub fn remove<A: Into<AnnotatedAxiom>>(&mut self, axiom: A) {
+        self.removed.insert(axiom.into());
+    }
+
+    /// Record the insertion of an axiom.
+    pub fn insert<A: Into<AnnotatedAxiom>>(&mut self, axiom: A) {
+        self.added.insert(axiom.into());
+    }
+
+    /// Check whether the patch leaves ontologies unchanged.
+    pub fn is_empty(&self) -> bool {
+        self.removed.is_empty() && self.added.is_empty()
+    }
+
+    /// Apply the patch to an ontology.
+    ///
+    /// Removals are applied before insertions.
+    pub fn apply<O: MutableOntology + ?Sized>(&self, ontology: &mut O) {
+        let mut sink = AxiomSink::new();
+        self.removed.iter().for_each(|aa| sink.remove(aa.clone()));
+        self.added.iter().for_each(|aa| sink.insert(aa.clone()));
+        sink.apply(ontology)
+    }
+}
+
+impl Display for Patch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        for aa in self.removed.iter() {
+            writeln!(f, "- {}", Functional(aa))?;
+        }
+        for aa in self.added.iter() {
+            writeln!(f, "+ {}", Functional(aa))?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Patch {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut patch = Patch::new();
+        let mut parser = Parser::new(s);
+        while let Some(added) = parser.change().map_err(|e| parser.error(e))? {
+            let aa = parser
+                .annotated_axiom()
+                .and_then(|aa| parser.end_of_line().map(|_| aa))
+                .map_err(|e| parser.error(e))?;
+            if added {
+                patch.added.insert(aa);
+            } else {
+                patch.removed.insert(aa);
+            }
+        }
+        Ok(patch)
+    }
+}
+
+/// An error encountered while reading a [`Patch`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    line: usize,
+    message: String,
+}
+
+impl ParseError {
+    /// Get the line of the error, starting at 1.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Get a description of the error.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl Error for ParseError {}
+
+/// A parser for axioms in the functional-style syntax.
+///
+/// Axioms are not required to fit on a single line, since literals may
+/// contain line breaks.
+struct Parser<'s> {
+    source: &'s str,
+    input: &'s str,
+    build: Build,
+}
+
+type ParseResult<T> = Result<T, String>;
+
+impl<'s> Parser<'s> {
+    fn new(source: &'s str) -> Self {
+        Self {
+            source,
+            input: source,
+            build: Build::new(),
+        }
+    }
+
+    /// Locate an error message at the current line.
+    fn error(&self, message: String) -> ParseError {
+        let offset = self.source.len() - self.input.len();
+        ParseError {
+            line: self.source[..offset].matches('\n').count() + 1,
+            message,
+        }
+    }
+
+    /// Parse the marker of the next change, skipping empty and comment lines.
+    ///
+    /// Returns `None` at the end of the input, or whether the change is
+    /// an insertion.
+    fn change(&mut self) -> ParseResult<Option<bool>> {
+        loop {
+            self.skip_whitespace();
+            if self.input.starts_with('#') {
+                let end = self.input.find('\n').unwrap_or(self.input.len());
+                self.input = &self.input[end..];
+            } else if let Some(rest) = self.input.strip_prefix('-') {
+                self.input = rest;
+                return Ok(Some(false));
+            } else if let Some(rest) = self.input.strip_prefix('+') {
+                self.input = rest;
+                return Ok(Some(true));
+            } else if self.input.is_empty() {
+                return Ok(None);
+            } else {
+                return Err(self.unexpected("`-` or `+`"));
+            }
+        }
+    }
+
+    /// Consume the end of the current line.
+    fn end_of_line(&mut self) -> ParseResult<()> {
+        self.input = self.input.trim_start_matches([' ', '\t', '\r']);
+        if self.input.is_empty() || self.input.starts_with('\n') {
+            Ok(())
+        } else {
+            Err(self.unexpected("end of line"))
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.input = self.input.trim_start();
+    }
+
+    /// Check whether the remaining input starts with a token.
+    fn at(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        self.input.starts_with(token)
+    }
+
+    /// Consume a token, failing if the remaining input does not start with it.
+    fn expect(&mut self, token: &str) -> ParseResult<()> {
+        if self.at(token) {
+            self.input = &self.input[token.len()..];
+            Ok(())
+        } else {
+            Err(self.unexpected(&format!("`{}`", token)))
+        }
+    }
+
+    fn unexpected(&self, expected: &str) -> String {
+        match self.input.chars().next() {
+            Some(c) => format!("expected {}, found `{}`", expected, c),
+            None => format!("expected {}, found end of line", expected),
+        }
+    }
+
+    /// Consume the characters matching a predicate.
+    fn take_while<F: Fn(char) -> bool>(&mut self, predicate: F) -> &'s str {
+        let end = self
+            .input
+            .find(|c| !predicate(c))
+            .unwrap_or(self.input.len());
+        let (token, rest) = self.input.split_at(end);
+        self.input = rest;
+        token
+    }
+
+    /// Consume a constructor name and its opening parenthesis.
+    fn open(&mut self) -> ParseResult<&'s str> {
+        self.skip_whitespace();
+        let name = self.take_while(|c| c.is_ascii_alphabetic());
+        if name.is_empty() {
+            return Err(self.unexpected("a constructor"));
+        }
+        self.expect("(")?;
+        Ok(name)
+    }
+
+    fn close(&mut self) -> ParseResult<()> {
+        self.expect(")")
+    }
+
+    /// Parse elements up to the closing parenthesis of the constructor.
+    fn many<T, F>(&mut self, element: F) -> ParseResult<Vec<T>>
+    where
+        F: Fn(&mut Self) -> ParseResult<T>,
+    {
+        let mut elements = Vec::new();
+        while !self.at(")") {
+            elements.push(element(self)?);
+        }
+        Ok(elements)
+    }
+
+    fn iri(&mut self) -> ParseResult<IRI> {
+        self.expect("<")?;
+        let iri = self.take_while(|c| c != '>');
+        self.expect(">")?;
+        Ok(self.build.iri(iri))
+    }
+
+    fn integer(&mut self) -> ParseResult<u32> {
+        self.skip_whitespace();
+        let digits = self.take_while(|c| c.is_ascii_digit());
+        digits
+            .parse()
+            .map_err(|_| self.unexpected("a non-negative integer"))
+    }
+
+    fn literal(&mut self) -> ParseResult<Literal> {
+        self.expect("\"")?;
+        let mut literal = String::new();
+        let mut chars = self.input.char_indices();
+        let end = loop {
+            match chars.next() {
+                Some((i, '"')) => break i,
+                Some((_, '\\')) => match chars.next() {
+                    Some((_, c)) => literal.push(c),
+                    None => return Err("unterminated literal".into()),
+                },
+                Some((_, c)) => literal.push(c),
+                None => return Err("unterminated literal".into()),
+            }
+        };
+        self.input = &self.input[end + 1..];
+        if self.input.starts_with('@') {
+            self.input = &self.input[1..];
+            let lang = self.take_while(|c| c.is_ascii_alphanumeric() || c == '-');
+            Ok(Literal::Language {
+                literal,
+                lang: lang.into(),
+            })
+        } else if self.input.starts_with("^^") {
+            self.input = &self.input[2..];
+            let datatype_iri = self.iri()?;
+            Ok(Literal::Datatype {
+                literal,
+                datatype_iri,
+            })
+        } else {
+            Ok(Literal::Simple { literal })
+        }
+    }
+
+    fn anonymous_individual(&mut self) -> ParseResult<AnonymousIndividual> {
+        self.expect("_:")?;
+        let id = self.take_while(|c| !c.is_whitespace() && c != '(' && c != ')');
+        Ok(AnonymousIndividual::from(id.to_string()))
+    }
+
+    fn class(&mut self) -> ParseResult<Class> {
+        self.iri().map(Class::from)
+    }
+
+    fn object_property(&mut self) -> ParseResult<ObjectProperty> {
+        self.iri().map(ObjectProperty::from)
+    }
+
+    fn data_property(&mut self) -> ParseResult<DataProperty> {
+        self.iri().map(DataProperty::from)
+    }
+
+    fn annotation_property(&mut self) -> ParseResult<AnnotationProperty> {
+        self.iri().map(AnnotationProperty::from)
+    }
+
+    fn datatype(&mut self) -> ParseResult<Datatype> {
+        self.iri().map(Datatype::from)
+    }
+
+    fn individual(&mut self) -> ParseResult<Individual> {
+        if self.at("_:") {
+            self.anonymous_individual().map(Individual::from)
+        } else {
+            self.iri().map(|iri| Individual::from(&iri))
+        }
+    }
+
+    fn annotation_subject(&mut self) -> ParseResult<AnnotationSubject> {
+        if self.at("_:") {
+            self.anonymous_individual().map(AnnotationSubject::from)
+        } else {
+            self.iri().map(AnnotationSubject::IRI)
+        }
+    }
+
+    fn annotation_value(&mut self) -> ParseResult<AnnotationValue> {
+        if self.at("\"") {
+            self.literal().map(AnnotationValue::Literal)
+        } else {
+            self.iri().map(AnnotationValue::IRI)
+        }
+    }
+
+    /// Parse the contents of an `Annotation` constructor.
+    fn annotation_body(&mut self) -> ParseResult<Annotation> {
+        let ap = self.annotation_property()?;
+        let av = self.annotation_value()?;
+        self.close()?;
+        Ok(Annotation { ap, av })
+    }
+
+    fn object_property_expression(&mut self) -> ParseResult<ObjectPropertyExpression> {
+        if self.at("<") {
+            return self.object_property().map(From::from);
+        }
+        match self.open()? {
+            "ObjectInverseOf" => {
+                let op = self.object_property()?;
+                self.close()?;
+                Ok(ObjectPropertyExpression::InverseObjectProperty(op))
+            }
+            name => Err(format!("unknown object property expression `{}`", name)),
+        }
+    }
+
+    fn class_expression(&mut self) -> ParseResult<ClassExpression> {
+        use self::ClassExpression::*;
+        if self.at("<") {
+            return self.class().map(From::from);
+        }
+        let ce = match self.open()? {
+            "ObjectIntersectionOf" => ObjectIntersectionOf(self.many(Self::class_expression)?),
+            "ObjectUnionOf" => ObjectUnionOf(self.many(Self::class_expression)?),
+            "ObjectOneOf" => ObjectOneOf(self.many(Self::individual)?),
+            "ObjectComplementOf" => ObjectComplementOf(Box::new(self.class_expression()?)),
+            "ObjectSomeValuesFrom" => ObjectSomeValuesFrom {
+                ope: self.object_property_expression()?,
+                bce: Box::new(self.class_expression()?),
+            },
+            "ObjectAllValuesFrom" => ObjectAllValuesFrom {
+                ope: self.object_property_expression()?,
+                bce: Box::new(self.class_expression()?),
+            },
+            "ObjectHasValue" => ObjectHasValue {
+                ope: self.object_property_expression()?,
+                i: self.individual()?,
+            },
+            "ObjectHasSelf" => ObjectHasSelf(self.object_property_expression()?),
+            "ObjectMinCardinality" => ObjectMinCardinality {
+                n: self.integer()?,
+                ope: self.object_property_expression()?,
+                bce: Box::new(self.class_expression()?),
+            },
+            "ObjectMaxCardinality" => ObjectMaxCardinality {
+                n: self.integer()?,
+                ope: self.object_property_expression()?,
+                bce: Box::new(self.class_expression()?),
+            },
+            "ObjectExactCardinality" => ObjectExactCardinality {
+                n: self.integer()?,
+                ope: self.object_property_expression()?,
+                bce: Box::new(self.class_expression()?),
+            },
+            "DataSomeValuesFrom" => DataSomeValuesFrom {
+                dp: self.data_property()?,
+                dr: self.data_range()?,
+            },
+            "DataAllValuesFrom" => DataAllValuesFrom {
+                dp: self.data_property()?,
+                dr: self.data_range()?,
+            },
+            "DataHasValue" => DataHasValue {
+                dp: self.data_property()?,
+                l: self.literal()?,
+            },
+            "DataMinCardinality" => DataMinCardinality {
+                n: self.integer()?,
+                dp: self.data_property()?,
+                dr: self.data_range()?,
+            },
+            "DataMaxCardinality" => DataMaxCardinality {
+                n: self.integer()?,
+                dp: self.data_property()?,
+                dr: self.data_range()?,
+            },
+            "DataExactCardinality" => DataExactCardinality {
+                n: self.integer()?,
+                dp: self.data_property()?,
+                dr: self.data_range()?,
+            },
+            name => return Err(format!("unknown class expression `{}`", name)),
+        };
+        self.close()?;
+        Ok(ce)
+    }
+
+    fn data_range(&mut self) -> ParseResult<DataRange> {
+        use self::DataRange::*;
+        if self.at("<") {
+            return self.datatype().map(From::from);
+        }
+        let dr = match self.open()? {
+            "DataIntersectionOf" => DataIntersectionOf(self.many(Self::data_range)?),
+            "DataUnionOf" => DataUnionOf(self.many(Self::data_range)?),
+            "DataOneOf" => DataOneOf(self.many(Self::literal)?),
+            "DataComplementOf" => DataComplementOf(Box::new(self.data_range()?)),
+            "DatatypeRestriction" => {
+                DatatypeRestriction(self.datatype()?, self.many(Self::facet_restriction)?)
+            }
+            name => return Err(format!("unknown data range `{}`", name)),
+        };
+        self.close()?;
+        Ok(dr)
+    }
+
+    fn facet_restriction(&mut self) -> ParseResult<FacetRestriction> {
+        let iri = self.iri()?;
+        let f = Facet::var_s(&iri).ok_or_else(|| format!("unknown facet `{}`", iri))?;
+        let l = self.literal()?;
+        Ok(FacetRestriction { f, l })
+    }
+
+    /// Parse the contents of a `HasKey` axiom.
+    fn has_key_body(&mut self) -> ParseResult<HasKey> {
+        let ce = self.class_expression()?;
+        self.expect("(")?;
+        let opes = self.many(Self::object_property_expression)?;
+        self.close()?;
+        self.expect("(")?;
+        let dps = self.many(Self::data_property)?;
+        self.close()?;
+        let vpe = opes
+            .into_iter()
+            .map(PropertyExpression::ObjectPropertyExpression)
+            .chain(dps.into_iter().map(PropertyExpression::DataProperty))
+            .collect();
+        Ok(HasKey { ce, vpe })
+    }
+
+    fn sub_object_property_expression(&mut self) -> ParseResult<SubObjectPropertyExpression> {
+        if self.at("ObjectPropertyChain") {
+            self.open()?;
+            let opes = self.many(Self::object_property_expression)?;
+            self.close()?;
+            Ok(SubObjectPropertyExpression::ObjectPropertyChain(opes))
+        } else {
+            self.object_property_expression()
+                .map(SubObjectPropertyExpression::ObjectPropertyExpression)
+        }
+    }
+
+    /// Parse the entity of a `Declaration` axiom.
+    fn declaration_body(&mut self) -> ParseResult<Axiom> {
+        let axiom = match self.open()? {
+            "Class" => DeclareClass(self.class()?).into(),
+            "ObjectProperty" => DeclareObjectProperty(self.object_property()?).into(),
+            "DataProperty" => DeclareDataProperty(self.data_property()?).into(),
+            "AnnotationProperty" => DeclareAnnotationProperty(self.annotation_property()?).into(),
+            "NamedIndividual" => DeclareNamedIndividual(self.iri()?.into()).into(),
+            "Datatype" => DeclareDatatype(self.datatype()?).into(),
+            name => return Err(format!("unknown entity type `{}`", name)),
+        };
+        self.close()?;
+        Ok(axiom)
+    }
+
+    fn annotated_axiom(&mut self) -> ParseResult<AnnotatedAxiom> {
+        let name = self.open()?;
+        match name {
+            "Import" => {
+                let iri = self.iri()?;
+                self.close()?;
+                return Ok(Import(iri).into());
+            }
+            "Annotation" => {
+                return self.annotation_body().map(|a| OntologyAnnotation(a).into());
+            }
+            _ => (),
+        }
+
+        let mut ann = BTreeSet::new();
+        while self.at("Annotation(") {
+            self.open()?;
+            ann.insert(self.annotation_body()?);
+        }
+
+        let axiom: Axiom = match name {
+            "Declaration" => self.declaration_body()?,
+            "SubClassOf" => SubClassOf {
+                sub: self.class_expression()?,
+                sup: self.class_expression()?,
+            }
+            .into(),
+            "EquivalentClasses" => EquivalentClasses(self.many(Self::class_expression)?).into(),
+            "DisjointClasses" => DisjointClasses(self.many(Self::class_expression)?).into(),
+            "DisjointUnion" => {
+                DisjointUnion(self.class()?, self.many(Self::class_expression)?).into()
+            }
+            "SubObjectPropertyOf" => SubObjectPropertyOf {
+                sub: self.sub_object_property_expression()?,
+                sup: self.object_property_expression()?,
+            }
+            .into(),
+            "EquivalentObjectProperties" => {
+                EquivalentObjectProperties(self.many(Self::object_property_expression)?).into()
+            }
+            "DisjointObjectProperties" => {
+                DisjointObjectProperties(self.many(Self::object_property_expression)?).into()
+            }
+            "InverseObjectProperties" => {
+                InverseObjectProperties(self.object_property()?, self.object_property()?).into()
+            }
+            "ObjectPropertyDomain" => ObjectPropertyDomain {
+                ope: self.object_property_expression()?,
+                ce: self.class_expression()?,
+            }
+            .into(),
+            "ObjectPropertyRange" => ObjectPropertyRange {
+                ope: self.object_property_expression()?,
+                ce: self.class_expression()?,
+            }
+            .into(),
+            "FunctionalObjectProperty" => {
+                FunctionalObjectProperty(self.object_property_expression()?).into()
+            }
+            "InverseFunctionalObjectProperty" => {
+                InverseFunctionalObjectProperty(self.object_property_expression()?).into()
+            }
+            "ReflexiveObjectProperty" => {
+                ReflexiveObjectProperty(self.object_property_expression()?).into()
+            }
+            "IrreflexiveObjectProperty" => {
+                IrreflexiveObjectProperty(self.object_property_expression()?).into()
+            }
+            "SymmetricObjectProperty" => {
+                SymmetricObjectProperty(self.object_property_expression()?).into()
+            }
+            "AsymmetricObjectProperty" => {
+                AsymmetricObjectProperty(self.object_property_expression()?).into()
+            }
+            "TransitiveObjectProperty" => {
+                TransitiveObjectProperty(self.object_property_expression()?).into()
+            }
+            "SubDataPropertyOf" => SubDataPropertyOf {
+                sub: self.data_property()?,
+                sup: self.data_property()?,
+            }
+            .into(),
+            "EquivalentDataProperties" => {
+                EquivalentDataProperties(self.many(Self::data_property)?).into()
+            }
+            "DisjointDataProperties" => {
+                DisjointDataProperties(self.many(Self::data_property)?).into()
+            }
+            "DataPropertyDomain" => DataPropertyDomain {
+                dp: self.data_property()?,
+                ce: self.class_expression()?,
+            }
+            .into(),
+            "DataPropertyRange" => DataPropertyRange {
+                dp: self.data_property()?,
+                dr: self.data_range()?,
+            }
+            .into(),
+            "FunctionalDataProperty" => FunctionalDataProperty(self.data_property()?).into(),
+            "DatatypeDefinition" => DatatypeDefinition {
+                kind: self.datatype()?,
+                range: self.data_range()?,
+            }
+            .into(),
+            "HasKey" => self.has_key_body()?.into(),
+            "SameIndividual" => SameIndividual(self.many(Self::individual)?).into(),
+            "DifferentIndividuals" => DifferentIndividuals(self.many(Self::individual)?).into(),
+            "ClassAssertion" => ClassAssertion {
+                ce: self.class_expression()?,
+                i: self.individual()?,
+            }
+            .into(),
+            "ObjectPropertyAssertion" => ObjectPropertyAssertion {
+                ope: self.object_property_expression()?,
+                from: self.individual()?,
+                to: self.individual()?,
+            }
+            .into(),
+            "NegativeObjectPropertyAssertion" => NegativeObjectPropertyAssertion {
+                ope: self.object_property_expression()?,
+                from: self.individual()?,
+                to: self.individual()?,
+            }
+            .into(),
+            "DataPropertyAssertion" => DataPropertyAssertion {
+                dp: self.data_property()?,
+                from: self.individual()?,
+                to: self.literal()?,
+            }
+            .into(),
+            "NegativeDataPropertyAssertion" => NegativeDataPropertyAssertion {
+                dp: self.data_property()?,
+                from: self.individual()?,
+                to: self.literal()?,
+            }
+            .into(),
+            "AnnotationAssertion" => {
+                let ap = self.annotation_property()?;
+                let subject = self.annotation_subject()?;
+                let av = self.annotation_value()?;
+                AnnotationAssertion {
+                    subject,
+                    ann: Annotation { ap, av },
+                }
+                .into()
+            }
+            "SubAnnotationPropertyOf" => SubAnnotationPropertyOf {
+                sub: self.annotation_property()?,
+                sup: self.annotation_property()?,
+            }
+            .into(),
+            "AnnotationPropertyDomain" => AnnotationPropertyDomain {
+                ap: self.annotation_property()?,
+                iri: self.iri()?,
+            }
+            .into(),
+            "AnnotationPropertyRange" => AnnotationPropertyRange {
+                ap: self.annotation_property()?,
+                iri: self.iri()?,
+            }
+            .into(),
+            name => return Err(format!("unknown axiom `{}`", name)),
+        };
+        self.close()?;
+        Ok(AnnotatedAxiom::new(axiom, ann))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::BufReader;
+
+    use horned_owl::ontology::set::SetOntology;
+
+    use super::*;
+
+    #[test]
+    fn roundtrip_bfo() {
+        let mut f = File::open("tests/data/bfo.owl")
+            .map(BufReader::new)
+            .unwrap();
+        let ontology: SetOntology = horned_owl::io::rdf::reader::read(&mut f).unwrap().0.into();
+
+        let mut patch = Patch::new();
+        ontology.iter().for_each(|aa| patch.insert(aa.clone()));
+        let parsed: Patch = patch.to_string().parse().unwrap();
+        assert_eq!(parsed, patch);
+
+        let mut copy = SetOntology::new();
+        parsed.apply(&mut copy);
+        let copied: BTreeSet<&AnnotatedAxiom> = copy.iter().collect();
+        let original: BTreeSet<&AnnotatedAxiom> = ontology.iter().collect();
+        assert_eq!(copied, original);
+    }
+
+    #[test]
+    fn roundtrip_constructs() {
+        let lines = [
+            "+ SubClassOf(Annotation(<http://example.com/p> \"a \\\"quoted\\\" \\\\ text\"@en) <http://example.com/A> ObjectIntersectionOf(<http://example.com/B> ObjectComplementOf(ObjectSomeValuesFrom(ObjectInverseOf(<http://example.com/r>) <http://example.com/C>))))",
+            "+ EquivalentClasses(<http://example.com/A> ObjectMinCardinality(2 <http://example.com/r> <http://example.com/B>) DataExactCardinality(1 <http://example.com/d> DatatypeRestriction(<http://www.w3.org/2001/XMLSchema#integer> <http://www.w3.org/2001/XMLSchema#minInclusive> \"0\"^^<http://www.w3.org/2001/XMLSchema#integer>)))",
+            "+ SubObjectPropertyOf(ObjectPropertyChain(<http://example.com/r> <http://example.com/s>) <http://example.com/t>)",
+            "+ HasKey(<http://example.com/A> (<http://example.com/r>) (<http://example.com/d>))",
+            "+ ClassAssertion(ObjectOneOf(<http://example.com/i> _:x) _:y)",
+            "+ AnnotationAssertion(<http://example.com/p> <http://example.com/A> <http://example.com/B>)",
+            "- Declaration(NamedIndividual(<http://example.com/i>))",
+            "- DataPropertyRange(<http://example.com/d> DataUnionOf(DataOneOf(\"a\" \"b\") DataComplementOf(<http://example.com/T>)))",
+            "- Import(<http://example.com/ontology>)",
+            "- Annotation(<http://example.com/p> \"ontology\")",
+        ];
+        let patch: Patch = lines.join("\n").parse().unwrap();
+        assert_eq!(patch.added().len(), 6);
+        assert_eq!(patch.removed().len(), 4);
+        assert_eq!(patch.to_string().parse::<Patch>().unwrap(), patch);
+    }
+
+    #[test]
+    fn roundtrip_anonymous_individuals() {
+        let mut patch = Patch::new();
+        for id in ["x", "_:y", "_:_:z"] {
+            patch.insert(ClassAssertion {
+                ce: Build::new().class("http://example.com/A").into(),
+                i: AnonymousIndividual::from(id.to_string()).into(),
+            });
+        }
+        assert_eq!(patch.to_string().parse::<Patch>().unwrap(), patch);
+    }
+
+    #[test]
+    fn multiline_literal() {
+        let mut patch = Patch::new();
+        patch.insert(AnnotationAssertion {
+            subject: AnnotationSubject::IRI(Build::new().iri("http://example.com/A")),
+            ann: Annotation {
+                ap: Build::new().annotation_property("http://example.com/p"),
+                av: AnnotationValue::Literal(Literal::Simple {
+                    literal: "first line\n+ second line".into(),
+                }),
+            },
+        });
+        assert_eq!(patch.to_string().parse::<Patch>().unwrap(), patch);
+    }
+
+    #[test]
+    fn skip_comments_and_empty_lines() {
+        let text = "# a comment\n\n+ Declaration(Class(<http://example.com/A>))\n";
+        let patch: Patch = text.parse().unwrap();
+        assert_eq!(patch.added().len(), 1);
+        assert!(patch.removed().is_empty());
+    }
+
+    #[test]
+    fn parse_errors() {
+        let text = "+ Declaration(Class(<http://example.com/A>))\n* SubClassOf()";
+        let err = text.parse::<Patch>().unwrap_err();
+        assert_eq!(err.line(), 2);
+
+        let text = "+ SubClassOf(<http://example.com/A>)";
+        let err = text.parse::<Patch>().unwrap_err();
+        assert_eq!(err.line(), 1);
+
+        let text = "+ Declaration(Class(<http://example.com/A>)) trailing";
+        let err = text.parse::<Patch>().unwrap_err();
+        assert_eq!(err.message(), "expected end of line, found `t`");
+
+        let text = "+ AnnotationAssertion(<http://example.com/p> <http://example.com/A> \"a";
+        let err = text.parse::<Patch>().unwrap_err();
+        assert_eq!(err.message(), "unterminated literal");
+    }
+}
@@ -0,0 +1,363 @@
+//! Polarity of the class expressions occurring in axioms.
+//!
+//! An occurrence of a class expression is *positive* when replacing it by
+//! a more general expression makes the axiom weaker, and *negative* when
+//! replacing it by a more specific expression makes the axiom weaker. For
+//! instance, in `SubClassOf(A B)`, `A` occurs negatively and `B` occurs
+//! positively, while the operands of `EquivalentClasses` occur with both
+//! polarities.
+//!
+//! The [`Polarized`] adapter visits the class expressions of an axiom and
+//! reports each occurrence, along with its polarity, to a [`PolarVisit`]
+//! implementor. The polarity is flipped by `ObjectComplementOf` and by the
+//! filler of `ObjectMaxCardinality`, and becomes [`Polarity::Both`] under
+//! `ObjectExactCardinality`.
+//!
+//! # Example
+//!
+//! ```rust
+//! # extern crate horned_owl;
+//! # extern crate horned_visit;
+//! use horned_owl::model::*;
+//! use horned_visit::polarity::Polarity;
+//!
+//! let b = Build::new();
+//! let a: ClassExpression = b.class("http://example.com/A").into();
+//! let c: ClassExpression = b.class("http://example.com/B").into();
+//! let axiom = Axiom::from(SubClassOf {
+//!     sub: a.clone(),
+//!     sup: ClassExpression::ObjectComplementOf(Box::new(c.clone())),
+//! });
+//!
+//! let occurrences = horned_visit::polarity::occurrences(&axiom);
+//! assert!(occurrences.contains(&(a, Polarity::Negative)));
+//! assert!(occurrences.contains(&(c, Polarity::Negative)));
+//! ```
+
+use std::mem::replace;
+use std::ops::Not;
+
+use horned_owl::model::*;
+
+use super::visit;
+use super::Visit;
+
+/// The polarity of an occurrence of a class expression.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Polarity {
+    /// The occurrence is positive.
+    #[default]
+    Positive,
+    /// The occurrence is negative.
+    Negative,
+    /// The occurrence is both positive and negative.
+    Both,
+}
+
+impl Polarity {
+    /// Check whether the occurrence is positive, possibly also negative.
+    pub fn is_positive(self) -> bool {
+        self != Polarity::Negative
+    }
+
+    /// Check whether the occurrence is negative, possibly also positive.
+    pub fn is_negative(self) -> bool {
+        self != Polarity::Positive
+    }
+}
+
+impl Not for Polarity {
+    type Output = Self;
+    fn not(self) -> Self {
+        match self {
+            Polarity::Positive => Polarity::Negative,
+            Polarity::Negative => Polarity::Positive,
+            Polarity::Both => Polarity::Both,
+        }
+    }
+}
+
+/// A trait for visiting class expressions along with their polarity.
+///
+/// The visited class expressions are not borrowed for the lifetime of the
+/// traversal, since some occurrences, such as the class defined by a
+/// `DisjointUnion`, are not class expressions in the visited axiom.
+#[blanket(derive(Mut, Box))]
+pub trait PolarVisit {
+    /// Visit an occurrence of a [`ClassExpression`] with the given polarity.
+    fn visit_polar_class_expression(
+        &mut self,
+        class_expression: &ClassExpression,
+        polarity: Polarity,
+    );
+}
+
+/// A visitor tracking the polarity of the visited class expressions.
+///
+/// Every class expression occurrence, including nested ones, is reported
+/// to the wrapped [`PolarVisit`] implementor. Class expressions visited
+/// outside of an axiom are considered positive.
+#[derive(Clone, Debug, Default)]
+pub struct Polarized<V> {
+    inner: V,
+    polarity: Polarity,
+}
+
+impl<V> Polarized<V> {
+    /// Wrap a visitor to report the polarity of class expressions to it.
+    pub fn new(inner: V) -> Self {
+        Self {
+            inner,
+            polarity: Polarity::default(),
+        }
+    }
+
+    /// Get a reference to the wrapped visitor.
+    pub fn inner(&self) -> &V {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the wrapped visitor.
+    pub fn inner_mut(&mut self) -> &mut V {
+        &mut self.inner
+    }
+
+    /// Consume the adapter and return the wrapped visitor.
+    pub fn into_inner(self) -> V {
+        self.inner
+    }
+}
+
+impl<'ast, V: PolarVisit> Polarized<V> {
+    /// Visit a class expression with the given polarity.
+    fn visit_with(&mut self, class_expression: &'ast ClassExpression, polarity: Polarity) {
+        let previous = replace(&mut self.polarity, polarity);
+        self.visit_class_expression(class_expression);
+        self.polarity = previous;
+    }
+}
+
+impl<'ast, V: PolarVisit> Visit<'ast> for Polarized<V> {
+    fn visit_class_assertion(&mut self, class_assertion: &'ast ClassAssertion) {
+        self.visit_with(&class_assertion.ce, Polarity::Positive);
+        self.visit_individual(&class_assertion.i);
+    }
+
+    fn visit_class_expression(&mut self, class_expression: &'ast ClassExpression) {
+        use self::ClassExpression::*;
+        let polarity = self.polarity;
+        self.inner
+            .visit_polar_class_expression(class_expression, polarity);
+        match class_expression {
+            ObjectComplementOf(ce) => {
+                self.visit_with(ce, !polarity);
+            }
+            ObjectMaxCardinality { ope, bce, .. } => {
+                self.visit_object_property_expression(ope);
+                self.visit_with(bce, !polarity);
+            }
+            ObjectExactCardinality { ope, bce, .. } => {
+                self.visit_object_property_expression(ope);
+                self.visit_with(bce, Polarity::Both);
+            }
+            _ => visit::visit_class_expression(self, class_expression),
+        }
+    }
+
+    fn visit_data_property_domain(&mut self, data_property_domain: &'ast DataPropertyDomain) {
+        self.visit_data_property(&data_property_domain.dp);
+        self.visit_with(&data_property_domain.ce, Polarity::Positive);
+    }
+
+    fn visit_disjoint_classes(&mut self, disjoint_classes: &'ast DisjointClasses) {
+        for ce in disjoint_classes.0.iter() {
+            self.visit_with(ce, Polarity::Negative);
+        }
+    }
+
+    fn visit_disjoint_union(&mut self, disjoint_union: &'ast DisjointUnion) {
+        let defined = ClassExpression::Class(disjoint_union.0.clone());
+        self.inner
+            .visit_polar_class_expression(&defined, Polarity::Both);
+        self.visit_class(&disjoint_union.0);
+        for ce in disjoint_union.1.iter() {
+            self.visit_with(ce, Polarity::Both);
+        }
+    }
+
+    fn visit_equivalent_classes(&mut self, equivalent_classes: &'ast EquivalentClasses) {
+        for ce in equivalent_classes.0.iter() {
+            self.visit_with(ce, Polarity::Both);
+        }
+    }
+
+    fn visit_has_key(&mut self, has_key: &'ast HasKey) {
+        self.visit_with(&has_key.ce, Polarity::Negative);
+        for pe in has_key.vpe.iter() {
+            self.visit_property_expression(pe);
+        }
+    }
+
+    fn visit_object_property_domain(&mut self, object_property_domain: &'ast ObjectPropertyDomain) {
+        self.visit_object_property_expression(&object_property_domain.ope);
+        self.visit_with(&object_property_domain.ce, Polarity::Positive);
+    }
+
+    fn visit_object_property_range(&mut self, object_property_range: &'ast ObjectPropertyRange) {
+        self.visit_object_property_expression(&object_property_range.ope);
+        self.visit_with(&object_property_range.ce, Polarity::Positive);
+    }
+
+    fn visit_sub_class_of(&mut self, sub_class_of: &'ast SubClassOf) {
+        self.visit_with(&sub_class_of.sub, Polarity::Negative);
+        self.visit_with(&sub_class_of.sup, Polarity::Positive);
+    }
+}
+
+/// A polar visitor collecting every class expression occurrence.
+struct Occurrences(Vec<(ClassExpression, Polarity)>);
+
+impl PolarVisit for Occurrences {
+    fn visit_polar_class_expression(
+        &mut self,
+        class_expression: &ClassExpression,
+        polarity: Polarity,
+    ) {
+        self.0.push((class_expression.clone(), polarity));
+    }
+}
+
+/// Get all the class expressions occurring in an axiom with their polarity.
+pub fn occurrences(axiom: &Axiom) -> Vec<(ClassExpression, Polarity)> {
+    let mut polarized = Polarized::new(Occurrences(Vec::new()));
+    polarized.visit_axiom(axiom);
+    polarized.into_inner().0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::*;
+
+    fn polarity(axiom: impl Into<Axiom>, ce: &ClassExpression) -> Vec<Polarity> {
+        occurrences(&axiom.into())
+            .into_iter()
+            .filter(|(occurrence, _)| occurrence == ce)
+            .map(|(_, polarity)| polarity)
+            .collect()
+    }
+
+    #[test]
+    fn sub_class_of() {
+        let axiom = SubClassOf {
+            sub: class("A"),
+            sup: class("B"),
+        };
+        assert_eq!(
+            polarity(axiom.clone(), &class("A")),
+            vec![Polarity::Negative]
+        );
+        assert_eq!(polarity(axiom, &class("B")), vec![Polarity::Positive]);
+    }
+
+    #[test]
+    fn complement() {
+        let axiom = SubClassOf {
+            sub: ClassExpression::ObjectComplementOf(Box::new(class("A"))),
+            sup: ClassExpression::ObjectComplementOf(Box::new(
+                ClassExpression::ObjectComplementOf(Box::new(class("B"))),
+            )),
+        };
+        assert_eq!(
+            polarity(axiom.clone(), &class("A")),
+            vec![Polarity::Positive]
+        );
+        assert_eq!(polarity(axiom, &class("B")), vec![Polarity::Positive]);
+    }
+
+    #[test]
+    fn cardinalities() {
+        let axiom = SubClassOf {
+            sub: class("A"),
+            sup: ClassExpression::ObjectIntersectionOf(vec![
+                ClassExpression::ObjectMinCardinality {
+                    n: 1,
+                    ope: op("r"),
+                    bce: Box::new(class("B")),
+                },
+                ClassExpression::ObjectMaxCardinality {
+                    n: 1,
+                    ope: op("r"),
+                    bce: Box::new(class("C")),
+                },
+                ClassExpression::ObjectExactCardinality {
+                    n: 1,
+                    ope: op("r"),
+                    bce: Box::new(class("D")),
+                },
+                ClassExpression::ObjectAllValuesFrom {
+                    ope: op("r"),
+                    bce: Box::new(class("E")),
+                },
+            ]),
+        };
+        assert_eq!(
+            polarity(axiom.clone(), &class("B")),
+            vec![Polarity::Positive]
+        );
+        assert_eq!(
+            polarity(axiom.clone(), &class("C")),
+            vec![Polarity::Negative]
+        );
+        assert_eq!(polarity(axiom.clone(), &class("D")), vec![Polarity::Both]);
+        assert_eq!(polarity(axiom, &class("E")), vec![Polarity::Positive]);
+    }
+
+    #[test]
+    fn domain_and_range() {
+        let domain = ObjectPropertyDomain {
+            ope: op("r"),
+            ce: class("A"),
+        };
+        let range = ObjectPropertyRange {
+            ope: op("r"),
+            ce: ClassExpression::ObjectComplementOf(Box::new(class("B"))),
+        };
+        let data_domain = DataPropertyDomain {
+            dp: DataProperty(iri("d")),
+            ce: class("C"),
+        };
+        assert_eq!(polarity(domain, &class("A")), vec![Polarity::Positive]);
+        assert_eq!(polarity(range, &class("B")), vec![Polarity::Negative]);
+        assert_eq!(polarity(data_domain, &class("C")), vec![Polarity::Positive]);
+    }
+
+    #[test]
+    fn n_ary_axioms() {
+        let equivalent = EquivalentClasses(vec![class("A"), class("B")]);
+        let disjoint = DisjointClasses(vec![class("A"), class("B")]);
+        assert_eq!(polarity(equivalent, &class("A")), vec![Polarity::Both]);
+        assert_eq!(polarity(disjoint, &class("B")), vec![Polarity::Negative]);
+    }
+
+    #[test]
+    fn disjoint_union() {
+        let axiom = DisjointUnion(named_class("A"), vec![class("B"), class("C")]);
+        assert_eq!(polarity(axiom.clone(), &class("A")), vec![Polarity::Both]);
+        assert_eq!(polarity(axiom, &class("B")), vec![Polarity::Both]);
+    }
+
+    #[test]
+    fn class_assertion_and_has_key() {
+        let assertion = ClassAssertion {
+            ce: class("A"),
+            i: NamedIndividual(iri("a")).into(),
+        };
+        let has_key = HasKey {
+            ce: class("B"),
+            vpe: vec![PropertyExpression::ObjectPropertyExpression(op("r"))],
+        };
+        assert_eq!(polarity(assertion, &class("A")), vec![Polarity::Positive]);
+        assert_eq!(polarity(has_key, &class("B")), vec![Polarity::Negative]);
+    }
+}
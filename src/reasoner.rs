@@ -0,0 +1,659 @@
+//! A minimal classifier for the OWL2 EL profile.
+//!
+//! The [`ElClassifier`] collects the class and object property axioms of
+//! an ontology, normalises class axioms with the
+//! [`transform::el_normal_form`](crate::transform::el_normal_form) module,
+//! and saturates them with the completion rules of the
+//! [CEL](https://doi.org/10.1007/11814771_26) algorithm. The resulting
+//! [`Classification`] gives the inferred subsumers of every named class,
+//! from which the direct superclasses and the unsatisfiable classes are
+//! derived.
+//!
+//! Object property inclusions, property chains, equivalent and transitive
+//! object properties are taken into account. Axioms outside of the
+//! supported fragment are ignored and reported by
+//! [`Classification::unsupported`], in which case the classification may
+//! be incomplete.
+//!
+//! # Example
+//!
+//! ```rust
+//! # extern crate horned_owl;
+//! # extern crate horned_visit;
+//! # use std::fs::File;
+//! # use std::io::BufReader;
+//! use horned_owl::model::*;
+//! use horned_owl::ontology::set::SetOntology;
+//!
+//! let mut f = File::open("tests/data/bfo.owl").map(BufReader::new).unwrap();
+//! let ontology: SetOntology = horned_owl::io::rdf::reader::read(&mut f).unwrap().0.into();
+//! let classification = horned_visit::reasoner::classify(&ontology);
+//!
+//! let b = Build::new();
+//! let material_entity = b.class("http://purl.obolibrary.org/obo/BFO_0000040");
+//! let independent_continuant = b.class("http://purl.obolibrary.org/obo/BFO_0000004");
+//! let continuant = b.class("http://purl.obolibrary.org/obo/BFO_0000002");
+//!
+//! assert!(classification.unsatisfiable().is_empty());
+//! assert!(classification.is_subclass_of(&material_entity, &continuant));
+//! assert!(classification
+//!     .direct_superclasses(&material_entity)
+//!     .contains(&independent_continuant));
+//! ```
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+
+use horned_owl::model::*;
+use horned_owl::vocab::WithIRI;
+use horned_owl::vocab::OWL;
+
+use super::transform::el_normal_form::ElNormalizer;
+use super::transform::el_normal_form::NormalForm;
+use super::visit;
+use super::Visit;
+
+/// The default prefix of the IRIs of fresh object properties.
+pub const FRESH_PROPERTY_PREFIX: &str = "urn:horned-visit:fresh-property:";
+
+/// The inferred class hierarchy of an ontology.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Classification {
+    subsumers: BTreeMap<Class, BTreeSet<Class>>,
+    unsatisfiable: BTreeSet<Class>,
+    unsupported: Vec<Axiom>,
+}
+
+impl Classification {
+    /// Iterate over the named classes of the classification.
+    pub fn classes(&self) -> impl Iterator<Item = &Class> {
+        self.subsumers.keys()
+    }
+
+    /// Get all the inferred superclasses of a class, including itself.
+    pub fn superclasses(&self, class: &Class) -> Option<&BTreeSet<Class>> {
+        self.subsumers.get(class)
+    }
+
+    /// Check whether a class is inferred to be a subclass of another class.
+    pub fn is_subclass_of(&self, sub: &Class, sup: &Class) -> bool {
+        self.subsumers
+            .get(sub)
+            .map(|s| s.contains(sup))
+            .unwrap_or(sub == sup)
+    }
+
+    /// Get the classes inferred to be equivalent to a class, excluding itself.
+    pub fn equivalent_classes(&self, class: &Class) -> BTreeSet<&Class> {
+        self.subsumers
+            .get(class)
+            .into_iter()
+            .flatten()
+            .filter(|&sup| sup != class && self.is_subclass_of(sup, class))
+            .collect()
+    }
+
+    /// Get the direct superclasses of a class.
+    ///
+    /// Unsatisfiable classes are equivalent to `owl:Nothing`, and have no
+    /// direct superclass.
+    pub fn direct_superclasses(&self, class: &Class) -> BTreeSet<&Class> {
+        if self.unsatisfiable.contains(class) {
+            return BTreeSet::new();
+        }
+        let strict = self
+            .subsumers
+            .get(class)
+            .into_iter()
+            .flatten()
+            .filter(|&sup| !self.is_subclass_of(sup, class))
+            .collect::<Vec<&Class>>();
+        strict
+            .iter()
+            .filter(|&&sup| {
+                !strict.iter().any(|&other| {
+                    other != sup
+                        && self.is_subclass_of(other, sup)
+                        && !self.is_subclass_of(sup, other)
+                })
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Check whether a class is inferred to be unsatisfiable.
+    pub fn is_unsatisfiable(&self, class: &Class) -> bool {
+        self.unsatisfiable.contains(class)
+    }
+
+    /// Get the unsatisfiable named classes.
+    pub fn unsatisfiable(&self) -> &BTreeSet<Class> {
+        &self.unsatisfiable
+    }
+
+    /// Get the axioms that were ignored during classification.
+    pub fn unsupported(&self) -> &[Axiom] {
+        &self.unsupported
+    }
+}
+
+/// A visitor collecting the EL axioms of an ontology for classification.
+#[derive(Debug)]
+pub struct ElClassifier {
+    build: Build,
+    normalizer: ElNormalizer,
+    classes: BTreeSet<Class>,
+    inclusions: Vec<(ObjectProperty, ObjectProperty)>,
+    chains: Vec<(ObjectProperty, ObjectProperty, ObjectProperty)>,
+    counter: usize,
+    unsupported: Vec<Axiom>,
+}
+
+impl Default for ElClassifier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ElClassifier {
+    /// Create a new classifier.
+    pub fn new() -> Self {
+        Self {
+            build: Build::new(),
+            normalizer: ElNormalizer::new(),
+            classes: BTreeSet::new(),
+            inclusions: Vec::new(),
+            chains: Vec::new(),
+            counter: 0,
+            unsupported: Vec::new(),
+        }
+    }
+
+    /// Record the chain `properties ⊑ sup`, binarizing it if needed.
+    fn add_chain(&mut self, properties: &[ObjectProperty], sup: ObjectProperty) {
+        match properties {
+            [] => (),
+            [p] => self.inclusions.push((p.clone(), sup)),
+            [p, q] => self.chains.push((p.clone(), q.clone(), sup)),
+            [rest @ .., q] => {
+                self.counter += 1;
+                let fresh = self
+                    .build
+                    .object_property(format!("{}{}", FRESH_PROPERTY_PREFIX, self.counter));
+                self.add_chain(rest, fresh.clone());
+                self.chains.push((fresh, q.clone(), sup));
+            }
+        }
+    }
+
+    /// Saturate the collected axioms and return the classification.
+    pub fn classify(self) -> Classification {
+        let normal_form = self.normalizer.into_normal_form();
+        let mut saturation = Saturation::new(&self.build);
+
+        for class in self.classes.iter() {
+            saturation.class(class);
+        }
+        for (sub, sup) in self.inclusions.iter() {
+            let sub = saturation.role(sub);
+            let sup = saturation.role(sup);
+            saturation.inclusions.entry(sub).or_default().push(sup);
+        }
+        for (first, second, sup) in self.chains.iter() {
+            let first = saturation.role(first);
+            let second = saturation.role(second);
+            let sup = saturation.role(sup);
+            saturation
+                .chains_first
+                .entry(first)
+                .or_default()
+                .push((second, sup));
+            saturation
+                .chains_second
+                .entry(second)
+                .or_default()
+                .push((first, sup));
+        }
+        for nf in normal_form.axioms().iter() {
+            saturation.add_normal_form(nf);
+        }
+        saturation.saturate();
+
+        let bottom = saturation.bottom;
+        let mut classification = Classification {
+            unsupported: self.unsupported,
+            ..Default::default()
+        };
+        classification
+            .unsupported
+            .extend(normal_form.unsupported().iter().cloned());
+        let named = |c: &&Class| !normal_form.is_fresh(c);
+        for (i, class) in saturation.classes.iter().enumerate() {
+            if !named(&class) || i == bottom {
+                continue;
+            }
+            let subsumers = &saturation.contexts[i].subsumers;
+            let supers = if subsumers.contains(&bottom) {
+                classification.unsatisfiable.insert(class.clone());
+                saturation.classes.iter().filter(named).cloned().collect()
+            } else {
+                subsumers
+                    .iter()
+                    .map(|&j| &saturation.classes[j])
+                    .filter(named)
+                    .cloned()
+                    .collect()
+            };
+            classification.subsumers.insert(class.clone(), supers);
+        }
+        classification
+    }
+}
+
+impl<'ast> Visit<'ast> for ElClassifier {
+    fn visit_axiom(&mut self, axiom: &'ast Axiom) {
+        self.normalizer.visit_axiom(axiom);
+        match axiom {
+            Axiom::SubObjectPropertyOf(SubObjectPropertyOf { sup, sub }) => {
+                let sup = match sup.as_property() {
+                    Some(op) => op.clone(),
+                    None => return self.unsupported.push(axiom.clone()),
+                };
+                let chain = match sub {
+                    SubObjectPropertyExpression::ObjectPropertyExpression(ope) => {
+                        vec![ope.clone()]
+                    }
+                    SubObjectPropertyExpression::ObjectPropertyChain(opes) => opes.clone(),
+                };
+                let properties = chain
+                    .iter()
+                    .map(|ope| ope.as_property().cloned())
+                    .collect::<Option<Vec<ObjectProperty>>>();
+                match properties {
+                    Some(properties) => self.add_chain(&properties, sup),
+                    None => self.unsupported.push(axiom.clone()),
+                }
+            }
+            Axiom::EquivalentObjectProperties(EquivalentObjectProperties(opes)) => {
+                let properties = opes
+                    .iter()
+                    .map(|ope| ope.as_property().cloned())
+                    .collect::<Option<Vec<ObjectProperty>>>();
+                match properties {
+                    Some(properties) => {
+                        for op in properties.iter().skip(1) {
+                            self.inclusions.push((properties[0].clone(), op.clone()));
+                            self.inclusions.push((op.clone(), properties[0].clone()));
+                        }
+                    }
+                    None => self.unsupported.push(axiom.clone()),
+                }
+            }
+            Axiom::TransitiveObjectProperty(TransitiveObjectProperty(ope)) => {
+                match ope.as_property() {
+                    Some(op) => self.chains.push((op.clone(), op.clone(), op.clone())),
+                    None => self.unsupported.push(axiom.clone()),
+                }
+            }
+            Axiom::InverseObjectProperties(_)
+            | Axiom::ReflexiveObjectProperty(_)
+            | Axiom::SymmetricObjectProperty(_) => {
+                self.unsupported.push(axiom.clone());
+            }
+            _ => (),
+        }
+        visit::visit_axiom(self, axiom);
+    }
+
+    fn visit_class(&mut self, class: &'ast Class) {
+        self.classes.insert(class.clone());
+    }
+}
+
+/// An element of the saturation work queue.
+enum Item {
+    /// `class ⊑ subsumer`.
+    Subsumer(usize, usize),
+    /// `class ⊑ ∃role.filler`.
+    Link(usize, usize, usize),
+}
+
+/// The saturation state of a single class.
+#[derive(Default)]
+struct Context {
+    subsumers: HashSet<usize>,
+    predecessors: HashSet<(usize, usize)>,
+    successors: HashSet<(usize, usize)>,
+}
+
+/// The state of the saturation of a set of normalised axioms.
+struct Saturation {
+    classes: Vec<Class>,
+    class_index: HashMap<Class, usize>,
+    contexts: Vec<Context>,
+    role_index: HashMap<ObjectProperty, usize>,
+    inclusions: HashMap<usize, Vec<usize>>,
+    chains_first: HashMap<usize, Vec<(usize, usize)>>,
+    chains_second: HashMap<usize, Vec<(usize, usize)>>,
+    told: HashMap<usize, Vec<usize>>,
+    conjunctions: HashMap<usize, Vec<(usize, usize)>>,
+    existentials: HashMap<usize, Vec<(usize, usize)>>,
+    restrictions: HashMap<(usize, usize), Vec<usize>>,
+    queue: VecDeque<Item>,
+    top: usize,
+    bottom: usize,
+}
+
+impl Saturation {
+    fn new(build: &Build) -> Self {
+        let mut saturation = Saturation {
+            classes: Vec::new(),
+            class_index: HashMap::new(),
+            contexts: Vec::new(),
+            role_index: HashMap::new(),
+            inclusions: HashMap::new(),
+            chains_first: HashMap::new(),
+            chains_second: HashMap::new(),
+            told: HashMap::new(),
+            conjunctions: HashMap::new(),
+            existentials: HashMap::new(),
+            restrictions: HashMap::new(),
+            queue: VecDeque::new(),
+            top: 0,
+            bottom: 0,
+        };
+        saturation.top = saturation.class(&build.class(OWL::Thing.iri_str()));
+        saturation.bottom = saturation.class(&build.class(OWL::Nothing.iri_str()));
+        saturation
+    }
+
+    fn class(&mut self, class: &Class) -> usize {
+        if let Some(&i) = self.class_index.get(class) {
+            return i;
+        }
+        let i = self.classes.len();
+        self.classes.push(class.clone());
+        self.class_index.insert(class.clone(), i);
+        self.contexts.push(Context::default());
+        i
+    }
+
+    fn role(&mut self, role: &ObjectProperty) -> usize {
+        let n = self.role_index.len();
+        *self.role_index.entry(role.clone()).or_insert(n)
+    }
+
+    fn add_normal_form(&mut self, nf: &NormalForm) {
+        match nf {
+            NormalForm::Subsumption { sub, sup } => {
+                let (sub, sup) = (self.class(sub), self.class(sup));
+                self.told.entry(sub).or_default().push(sup);
+            }
+            NormalForm::Conjunction { left, right, sup } => {
+                let (left, right, sup) = (self.class(left), self.class(right), self.class(sup));
+                self.conjunctions
+                    .entry(left)
+                    .or_default()
+                    .push((right, sup));
+                self.conjunctions
+                    .entry(right)
+                    .or_default()
+                    .push((left, sup));
+            }
+            NormalForm::Existential {
+                sub,
+                property,
+                filler,
+            } => {
+                let (sub, filler) = (self.class(sub), self.class(filler));
+                let role = self.role(property);
+                self.existentials
+                    .entry(sub)
+                    .or_default()
+                    .push((role, filler));
+            }
+            NormalForm::Restriction {
+                property,
+                filler,
+                sup,
+            } => {
+                let (filler, sup) = (self.class(filler), self.class(sup));
+                let role = self.role(property);
+                self.restrictions
+                    .entry((role, filler))
+                    .or_default()
+                    .push(sup);
+            }
+        }
+    }
+
+    /// Get all the super-roles of a role, including itself.
+    fn super_roles(&self, role: usize) -> Vec<usize> {
+        let mut seen = vec![role];
+        let mut i = 0;
+        while i < seen.len() {
+            for &sup in self.inclusions.get(&seen[i]).into_iter().flatten() {
+                if !seen.contains(&sup) {
+                    seen.push(sup);
+                }
+            }
+            i += 1;
+        }
+        seen
+    }
+
+    fn saturate(&mut self) {
+        for i in 0..self.classes.len() {
+            self.queue.push_back(Item::Subsumer(i, i));
+            self.queue.push_back(Item::Subsumer(i, self.top));
+        }
+        let super_roles = (0..self.role_index.len())
+            .map(|r| self.super_roles(r))
+            .collect::<Vec<_>>();
+        while let Some(item) = self.queue.pop_front() {
+            match item {
+                Item::Subsumer(class, subsumer) => self.process_subsumer(class, subsumer),
+                Item::Link(class, role, filler) => {
+                    for &sup in super_roles[role].iter() {
+                        self.process_link(class, sup, filler);
+                    }
+                }
+            }
+        }
+    }
+
+    fn process_subsumer(&mut self, class: usize, subsumer: usize) {
+        if !self.contexts[class].subsumers.insert(subsumer) {
+            return;
+        }
+        for &sup in self.told.get(&subsumer).into_iter().flatten() {
+            self.queue.push_back(Item::Subsumer(class, sup));
+        }
+        for &(other, sup) in self.conjunctions.get(&subsumer).into_iter().flatten() {
+            if self.contexts[class].subsumers.contains(&other) {
+                self.queue.push_back(Item::Subsumer(class, sup));
+            }
+        }
+        for &(role, filler) in self.existentials.get(&subsumer).into_iter().flatten() {
+            self.queue.push_back(Item::Link(class, role, filler));
+        }
+        for &(role, predecessor) in self.contexts[class].predecessors.iter() {
+            if subsumer == self.bottom {
+                self.queue
+                    .push_back(Item::Subsumer(predecessor, self.bottom));
+            }
+            for &sup in self
+                .restrictions
+                .get(&(role, subsumer))
+                .into_iter()
+                .flatten()
+            {
+                self.queue.push_back(Item::Subsumer(predecessor, sup));
+            }
+        }
+    }
+
+    fn process_link(&mut self, class: usize, role: usize, filler: usize) {
+        if !self.contexts[filler].predecessors.insert((role, class)) {
+            return;
+        }
+        self.contexts[class].successors.insert((role, filler));
+        for &subsumer in self.contexts[filler].subsumers.iter() {
+            if subsumer == self.bottom {
+                self.queue.push_back(Item::Subsumer(class, self.bottom));
+            }
+            for &sup in self
+                .restrictions
+                .get(&(role, subsumer))
+                .into_iter()
+                .flatten()
+            {
+                self.queue.push_back(Item::Subsumer(class, sup));
+            }
+        }
+        for &(second, sup) in self.chains_first.get(&role).into_iter().flatten() {
+            for &(r, target) in self.contexts[filler].successors.iter() {
+                if r == second {
+                    self.queue.push_back(Item::Link(class, sup, target));
+                }
+            }
+        }
+        for &(first, sup) in self.chains_second.get(&role).into_iter().flatten() {
+            for &(r, source) in self.contexts[class].predecessors.iter() {
+                if r == first {
+                    self.queue.push_back(Item::Link(source, sup, filler));
+                }
+            }
+        }
+    }
+}
+
+/// Classify the EL axioms of an ontology.
+pub fn classify<'a, I>(axioms: I) -> Classification
+where
+    I: IntoIterator<Item = &'a AnnotatedAxiom>,
+{
+    let mut classifier = ElClassifier::new();
+    for aa in axioms.into_iter() {
+        classifier.visit_axiom(&aa.axiom);
+    }
+    classifier.classify()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::*;
+
+    #[test]
+    fn bottom_through_existentials() {
+        let classification = classify(&ontology(vec![
+            sub_class_of(class("A"), some("r", class("B"))),
+            sub_class_of(class("B"), some("s", class("C"))),
+            sub_class_of(class("C"), nothing()),
+            sub_class_of(class("D"), class("E")),
+        ]));
+        for name in ["A", "B", "C"] {
+            assert!(
+                classification.is_unsatisfiable(&named_class(name)),
+                "{}",
+                name
+            );
+            assert!(classification
+                .direct_superclasses(&named_class(name))
+                .is_empty());
+        }
+        assert!(!classification.is_unsatisfiable(&named_class("D")));
+        assert!(!classification.is_unsatisfiable(&named_class("E")));
+    }
+
+    #[test]
+    fn bottom_through_disjointness() {
+        let classification = classify(&ontology(vec![
+            DisjointClasses(vec![class("B"), class("C")]).into(),
+            sub_class_of(class("D"), class("B")),
+            sub_class_of(class("D"), class("C")),
+            sub_class_of(class("A"), some("r", class("D"))),
+        ]));
+        assert!(classification.is_unsatisfiable(&named_class("D")));
+        assert!(classification.is_unsatisfiable(&named_class("A")));
+        assert!(!classification.is_unsatisfiable(&named_class("B")));
+    }
+
+    #[test]
+    fn bottom_filler_in_subclass() {
+        // `∃r.⊥` is unsatisfiable, so `∃r.⊥ ⊑ A` has no consequence
+        let classification = classify(&ontology(vec![
+            sub_class_of(some("r", nothing()), class("A")),
+            sub_class_of(class("B"), class("C")),
+        ]));
+        assert!(classification.unsatisfiable().is_empty());
+        assert!(!classification.is_subclass_of(&named_class("B"), &named_class("A")));
+    }
+
+    #[test]
+    fn binary_role_chain() {
+        let classification = classify(&ontology(vec![
+            chain(&["r", "s"], op("t")),
+            sub_class_of(class("A"), some("r", class("B"))),
+            sub_class_of(class("B"), some("s", class("C"))),
+            sub_class_of(some("t", class("C")), class("D")),
+        ]));
+        assert!(classification.is_subclass_of(&named_class("A"), &named_class("D")));
+        assert!(!classification.is_subclass_of(&named_class("B"), &named_class("D")));
+    }
+
+    #[test]
+    fn long_role_chain() {
+        let classification = classify(&ontology(vec![
+            chain(&["r", "s", "u"], op("t")),
+            sub_class_of(class("A"), some("r", class("B"))),
+            sub_class_of(class("B"), some("s", class("C"))),
+            sub_class_of(class("C"), some("u", class("E"))),
+            sub_class_of(some("t", class("E")), class("D")),
+        ]));
+        assert!(classification.is_subclass_of(&named_class("A"), &named_class("D")));
+        assert!(!classification.is_subclass_of(&named_class("B"), &named_class("D")));
+    }
+
+    #[test]
+    fn role_chain_with_subproperties() {
+        let classification = classify(&ontology(vec![
+            chain(&["r", "s"], op("t")),
+            sub_object_property_of(op("q"), op("r")),
+            sub_object_property_of(op("t"), op("v")),
+            sub_class_of(class("A"), some("q", class("B"))),
+            sub_class_of(class("B"), some("s", class("C"))),
+            sub_class_of(some("v", class("C")), class("D")),
+        ]));
+        assert!(classification.is_subclass_of(&named_class("A"), &named_class("D")));
+    }
+
+    #[test]
+    fn transitive_role() {
+        let classification = classify(&ontology(vec![
+            TransitiveObjectProperty(op("r")).into(),
+            sub_class_of(class("A"), some("r", class("B"))),
+            sub_class_of(class("B"), some("r", class("C"))),
+            sub_class_of(class("C"), some("r", class("E"))),
+            sub_class_of(some("r", class("E")), class("D")),
+        ]));
+        assert!(classification.is_subclass_of(&named_class("A"), &named_class("D")));
+        assert!(classification.is_subclass_of(&named_class("B"), &named_class("D")));
+    }
+
+    #[test]
+    fn bottom_through_role_chain() {
+        let classification = classify(&ontology(vec![
+            chain(&["r", "s"], op("t")),
+            sub_class_of(class("A"), some("r", class("B"))),
+            sub_class_of(class("B"), some("s", class("C"))),
+            sub_class_of(some("t", class("C")), nothing()),
+        ]));
+        assert!(classification.is_unsatisfiable(&named_class("A")));
+        assert!(!classification.is_unsatisfiable(&named_class("B")));
+    }
+}
@@ -0,0 +1,378 @@
+//! Projection of the class axioms of an ontology into a labelled graph.
+//!
+//! The [`RelationGraphBuilder`] turns the existential restrictions on named
+//! classes into edges between these classes, labelled with the restricted
+//! object property, alongside `is_a` edges for the told superclasses:
+//!
+//! - `SubClassOf(A B)` gives the edge `A --is_a--> B`;
+//! - `SubClassOf(A ObjectSomeValuesFrom(p B))` gives the edge `A --p--> B`;
+//! - `SubClassOf(A ObjectIntersectionOf(...))` gives the edges of each
+//!   operand of the intersection;
+//! - `EquivalentClasses(A CE)` gives the same edges as `SubClassOf(A CE)`.
+//!
+//! where `A` and `B` are named classes and `p` is a named object property.
+//! Other class expressions are ignored. The resulting [`RelationGraph`] is
+//! displayed as a tab-separated edge list, using `rdfs:subClassOf` as the
+//! label of `is_a` edges.
+//!
+//! # Example
+//!
+//! ```rust
+//! # extern crate horned_owl;
+//! # extern crate horned_visit;
+//! use horned_owl::model::*;
+//! use horned_owl::ontology::set::SetOntology;
+//! use horned_visit::relation_graph::Predicate;
+//!
+//! let b = Build::new();
+//! let mut ontology = SetOntology::new();
+//! ontology.insert(SubClassOf {
+//!     sub: b.class("http://example.com/Hand").into(),
+//!     sup: ClassExpression::ObjectIntersectionOf(vec![
+//!         b.class("http://example.com/Limb").into(),
+//!         ClassExpression::ObjectSomeValuesFrom {
+//!             ope: b.object_property("http://example.com/part_of").into(),
+//!             bce: Box::new(b.class("http://example.com/Arm").into()),
+//!         },
+//!     ]),
+//! });
+//!
+//! let graph = horned_visit::relation_graph::relation_graph(&ontology);
+//! let hand = b.class("http://example.com/Hand");
+//! let predicates = graph
+//!     .outgoing(&hand)
+//!     .map(|edge| edge.predicate.clone())
+//!     .collect::<Vec<_>>();
+//! assert_eq!(
+//!     predicates,
+//!     vec![Predicate::IsA, Predicate::Property(b.object_property("http://example.com/part_of"))],
+//! );
+//! ```
+
+use std::collections::BTreeSet;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+
+use horned_owl::model::*;
+use horned_owl::vocab::WithIRI;
+use horned_owl::vocab::RDFS;
+
+use super::visit;
+use super::Visit;
+
+/// The label of an edge of a [`RelationGraph`].
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Predicate {
+    /// A told subsumption between two classes.
+    IsA,
+    /// An existential restriction on an object property.
+    Property(ObjectProperty),
+}
+
+impl Display for Predicate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            Predicate::IsA => f.write_str(RDFS::SubClassOf.iri_str()),
+            Predicate::Property(op) => write!(f, "{}", op.0),
+        }
+    }
+}
+
+/// A labelled edge between two named classes.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Edge {
+    pub subject: Class,
+    pub predicate: Predicate,
+    pub object: Class,
+}
+
+impl Display for Edge {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "{}\t{}\t{}",
+            self.subject.0, self.predicate, self.object.0
+        )
+    }
+}
+
+/// A graph of labelled edges between named classes.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RelationGraph {
+    edges: BTreeSet<Edge>,
+}
+
+impl RelationGraph {
+    /// Get the edges of the graph.
+    pub fn edges(&self) -> &BTreeSet<Edge> {
+        &self.edges
+    }
+
+    /// Get the number of edges in the graph.
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Check whether the graph has no edge.
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    /// Iterate over the edges starting from a class.
+    pub fn outgoing<'g>(&'g self, class: &'g Class) -> impl Iterator<Item = &'g Edge> {
+        self.edges.iter().filter(move |edge| &edge.subject == class)
+    }
+
+    /// Iterate over the edges ending in a class.
+    pub fn incoming<'g>(&'g self, class: &'g Class) -> impl Iterator<Item = &'g Edge> {
+        self.edges.iter().filter(move |edge| &edge.object == class)
+    }
+
+    /// Iterate over the edges labelled with a predicate.
+    pub fn labelled<'g>(&'g self, predicate: &'g Predicate) -> impl Iterator<Item = &'g Edge> {
+        self.edges
+            .iter()
+            .filter(move |edge| &edge.predicate == predicate)
+    }
+}
+
+impl Display for RelationGraph {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        for edge in self.edges.iter() {
+            writeln!(f, "{}", edge)?;
+        }
+        Ok(())
+    }
+}
+
+/// A visitor projecting the visited class axioms into a [`RelationGraph`].
+#[derive(Clone, Debug)]
+pub struct RelationGraphBuilder {
+    is_a: bool,
+    graph: RelationGraph,
+}
+
+impl Default for RelationGraphBuilder {
+    fn default() -> Self {
+        Self {
+            is_a: true,
+            graph: RelationGraph::default(),
+        }
+    }
+}
+
+impl RelationGraphBuilder {
+    /// Create a new builder, including `is_a` edges in the graph.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set whether `is_a` edges are included in the graph.
+    pub fn is_a(mut self, is_a: bool) -> Self {
+        self.is_a = is_a;
+        self
+    }
+
+    /// Get the graph built so far.
+    pub fn graph(&self) -> &RelationGraph {
+        &self.graph
+    }
+
+    /// Consume the builder and return the built graph.
+    pub fn into_graph(self) -> RelationGraph {
+        self.graph
+    }
+
+    /// Add the edges from a named class to the classes of a superclass.
+    fn project(&mut self, subject: &Class, sup: &ClassExpression) {
+        match sup {
+            ClassExpression::Class(object) if self.is_a => {
+                self.graph.edges.insert(Edge {
+                    subject: subject.clone(),
+                    predicate: Predicate::IsA,
+                    object: object.clone(),
+                });
+            }
+            ClassExpression::ObjectSomeValuesFrom {
+                ope: ObjectPropertyExpression::ObjectProperty(op),
+                bce,
+            } => {
+                if let ClassExpression::Class(object) = bce.as_ref() {
+                    self.graph.edges.insert(Edge {
+                        subject: subject.clone(),
+                        predicate: Predicate::Property(op.clone()),
+                        object: object.clone(),
+                    });
+                }
+            }
+            ClassExpression::ObjectIntersectionOf(operands) => {
+                for operand in operands.iter() {
+                    self.project(subject, operand);
+                }
+            }
+            _ => (),
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for RelationGraphBuilder {
+    fn visit_sub_class_of(&mut self, sub_class_of: &'ast SubClassOf) {
+        if let ClassExpression::Class(subject) = &sub_class_of.sub {
+            self.project(subject, &sub_class_of.sup);
+        }
+        visit::visit_sub_class_of(self, sub_class_of);
+    }
+
+    fn visit_equivalent_classes(&mut self, equivalent_classes: &'ast EquivalentClasses) {
+        let operands = &equivalent_classes.0;
+        for operand in operands.iter() {
+            if let ClassExpression::Class(subject) = operand {
+                for other in operands.iter().filter(|&other| other != operand) {
+                    self.project(subject, other);
+                }
+            }
+        }
+        visit::visit_equivalent_classes(self, equivalent_classes);
+    }
+}
+
+/// Project the class axioms of an ontology into a relation graph.
+pub fn relation_graph<'a, I>(axioms: I) -> RelationGraph
+where
+    I: IntoIterator<Item = &'a AnnotatedAxiom>,
+{
+    let mut builder = RelationGraphBuilder::new();
+    for aa in axioms.into_iter() {
+        builder.visit_axiom(&aa.axiom);
+    }
+    builder.into_graph()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use horned_owl::ontology::set::SetOntology;
+
+    use super::*;
+    use crate::fixtures::*;
+
+    fn edge(subject: &str, predicate: Predicate, object: &str) -> Edge {
+        Edge {
+            subject: named_class(subject),
+            predicate,
+            object: named_class(object),
+        }
+    }
+
+    fn part_of() -> Predicate {
+        Predicate::Property(ObjectProperty(iri("part_of")))
+    }
+
+    fn example_ontology() -> SetOntology {
+        ontology(vec![
+            sub_class_of(
+                class("Hand"),
+                ClassExpression::ObjectIntersectionOf(vec![
+                    class("Limb"),
+                    some("part_of", class("Arm")),
+                ]),
+            ),
+            sub_class_of(class("Finger"), some("part_of", class("Hand"))),
+            sub_class_of(class("Arm"), class("Limb")),
+        ])
+    }
+
+    #[test]
+    fn incoming() {
+        let graph = relation_graph(&example_ontology());
+        let edges = graph
+            .incoming(&named_class("Limb"))
+            .cloned()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            edges,
+            vec![
+                edge("Arm", Predicate::IsA, "Limb"),
+                edge("Hand", Predicate::IsA, "Limb"),
+            ]
+        );
+        assert_eq!(graph.incoming(&named_class("Finger")).count(), 0);
+    }
+
+    #[test]
+    fn labelled() {
+        let graph = relation_graph(&example_ontology());
+        let edges = graph.labelled(&part_of()).cloned().collect::<Vec<_>>();
+        assert_eq!(
+            edges,
+            vec![
+                edge("Finger", part_of(), "Hand"),
+                edge("Hand", part_of(), "Arm"),
+            ]
+        );
+        assert_eq!(graph.labelled(&Predicate::IsA).count(), 2);
+        assert_eq!(graph.len(), 4);
+    }
+
+    #[test]
+    fn without_is_a() {
+        let mut builder = RelationGraphBuilder::new().is_a(false);
+        example_ontology()
+            .iter()
+            .for_each(|aa| builder.visit_annotated_axiom(aa));
+        let graph = builder.into_graph();
+        assert_eq!(graph.len(), 2);
+        assert_eq!(graph.labelled(&Predicate::IsA).count(), 0);
+    }
+
+    #[test]
+    fn equivalent_classes() {
+        let axiom = AnnotatedAxiom::from(EquivalentClasses(vec![
+            class("A"),
+            class("B"),
+            some("part_of", class("C")),
+        ]));
+        let graph = relation_graph(vec![&axiom]);
+        assert_eq!(
+            graph.edges(),
+            &vec![
+                edge("A", Predicate::IsA, "B"),
+                edge("A", part_of(), "C"),
+                edge("B", Predicate::IsA, "A"),
+                edge("B", part_of(), "C"),
+            ]
+            .into_iter()
+            .collect()
+        );
+    }
+
+    #[test]
+    fn ignored_expressions() {
+        let axioms = ontology(vec![
+            sub_class_of(some("part_of", class("A")), class("B")),
+            sub_class_of(class("A"), some("part_of", some("part_of", class("B")))),
+            sub_class_of(
+                class("A"),
+                ClassExpression::ObjectSomeValuesFrom {
+                    ope: inverse_of("part_of"),
+                    bce: Box::new(class("B")),
+                },
+            ),
+            sub_class_of(
+                class("A"),
+                ClassExpression::ObjectUnionOf(vec![class("B"), class("C")]),
+            ),
+        ]);
+        assert!(relation_graph(&axioms).is_empty());
+    }
+
+    #[test]
+    fn display() {
+        let axiom = AnnotatedAxiom::from(sub_class_of(class("A"), class("B")));
+        assert_eq!(
+            relation_graph(vec![&axiom]).to_string(),
+            "http://example.com/A\thttp://www.w3.org/2000/01/rdf-schema#subClassOf\thttp://example.com/B\n"
+        );
+    }
+}
@@ -0,0 +1,106 @@
+//! Collect the named entities referenced by ontology elements.
+
+use std::collections::BTreeSet;
+
+use horned_owl::model::*;
+
+use super::Visit;
+
+/// A named entity, identified by its kind and its IRI.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Entity {
+    pub kind: NamedEntityKind,
+    pub iri: IRI,
+}
+
+impl Entity {
+    /// Create a new entity with the given kind and IRI.
+    pub fn new(kind: NamedEntityKind, iri: IRI) -> Self {
+        Self { kind, iri }
+    }
+}
+
+macro_rules! impl_from_entity {
+    ($($name:ident,)*) => {
+        $(
+            impl From<&$name> for Entity {
+                fn from(e: &$name) -> Self {
+                    Self::new(NamedEntityKind::$name, e.0.clone())
+                }
+            }
+
+            impl From<$name> for Entity {
+                fn from(e: $name) -> Self {
+                    Self::new(NamedEntityKind::$name, e.0)
+                }
+            }
+        )*
+
+        impl From<NamedEntity> for Entity {
+            fn from(e: NamedEntity) -> Self {
+                match e {
+                    $(NamedEntity::$name(x) => Self::from(x),)*
+                }
+            }
+        }
+
+        impl From<&Entity> for NamedEntity {
+            fn from(e: &Entity) -> Self {
+                match e.kind {
+                    $(NamedEntityKind::$name => NamedEntity::$name($name(e.iri.clone())),)*
+                }
+            }
+        }
+
+        impl From<Entity> for NamedEntity {
+            fn from(e: Entity) -> Self {
+                NamedEntity::from(&e)
+            }
+        }
+    }
+}
+
+impl_from_entity! {
+    AnnotationProperty,
+    Class,
+    DataProperty,
+    Datatype,
+    NamedIndividual,
+    ObjectProperty,
+}
+
+/// A visitor collecting the named entities of the visited elements.
+#[derive(Clone, Debug, Default)]
+pub struct SignatureCollector {
+    entities: BTreeSet<Entity>,
+}
+
+impl SignatureCollector {
+    /// Create a new collector with an empty signature.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the entities collected so far.
+    pub fn entities(&self) -> &BTreeSet<Entity> {
+        &self.entities
+    }
+
+    /// Consume the collector and return the collected entities.
+    pub fn into_entities(self) -> BTreeSet<Entity> {
+        self.entities
+    }
+}
+
+impl<'ast> Visit<'ast> for SignatureCollector {
+    fn visit_entity(&mut self, kind: NamedEntityKind, iri: &'ast IRI) {
+        self.entities.insert(Entity::new(kind, iri.clone()));
+    }
+}
+
+/// Get the signature of an axiom, ignoring its annotations.
+pub fn signature(axiom: &Axiom) -> BTreeSet<Entity> {
+    let mut collector = SignatureCollector::new();
+    collector.visit_axiom(axiom);
+    collector.into_entities()
+}
@@ -0,0 +1,157 @@
+//! Queue axiom edits while visiting an ontology.
+//!
+//! Visitors only get shared references to the ontology they traverse, so
+//! they cannot add or remove axioms on the fly. Instead, a visitor can
+//! implement the [`Emit`] trait to expose an [`AxiomSink`], in which it
+//! queues the axioms to insert or remove. The queued edits are applied to
+//! the ontology once the traversal is over.
+//!
+//! # Example
+//!
+//! Declare every class used in an ontology:
+//!
+//! ```rust
+//! # extern crate horned_owl;
+//! # extern crate horned_visit;
+//! use horned_owl::model::*;
+//! use horned_owl::ontology::set::SetOntology;
+//! use horned_visit::Visit;
+//! use horned_visit::sink::{AxiomSink, Emit};
+//!
+//! #[derive(Default)]
+//! struct ClassDeclarer {
+//!     sink: AxiomSink,
+//! }
+//!
+//! impl Emit for ClassDeclarer {
+//!     fn sink(&mut self) -> &mut AxiomSink {
+//!         &mut self.sink
+//!     }
+//! }
+//!
+//! impl<'ast> Visit<'ast> for ClassDeclarer {
+//!     fn visit_class(&mut self, class: &'ast Class) {
+//!         self.sink.insert(DeclareClass(class.clone()));
+//!     }
+//! }
+//!
+//! let b = Build::new();
+//! let mut ontology = SetOntology::new();
+//! ontology.insert(SubClassOf {
+//!     sub: b.class("http://example.com/A").into(),
+//!     sup: b.class("http://example.com/B").into(),
+//! });
+//!
+//! horned_visit::sink::edit(&mut ontology, &mut ClassDeclarer::default());
+//! assert_eq!(ontology.iter().count(), 3);
+//! ```
+
+use std::mem;
+
+use horned_owl::model::*;
+
+use super::Visit;
+
+/// A queue of axiom insertions and removals to apply to an ontology.
+///
+/// Removals are always applied before insertions, so that an axiom can be
+/// replaced by an edited copy of itself.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AxiomSink {
+    insertions: Vec<AnnotatedAxiom>,
+    removals: Vec<AnnotatedAxiom>,
+}
+
+impl AxiomSink {
+    /// Create a new empty sink.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queue the insertion of an axiom.
+    pub fn insert<A: Into<AnnotatedAxiom>>(&mut self, axiom: A) {
+        self.insertions.push(axiom.into());
+    }
+
+    /// Queue the removal of an axiom.
+    pub fn remove<A: Into<AnnotatedAxiom>>(&mut self, axiom: A) {
+        self.removals.push(axiom.into());
+    }
+
+    /// Queue the replacement of an axiom by another one.
+    pub fn replace<A, B>(&mut self, old: A, new: B)
+    where
+        A: Into<AnnotatedAxiom>,
+        B: Into<AnnotatedAxiom>,
+    {
+        self.remove(old);
+        self.insert(new);
+    }
+
+    /// Get the axioms queued for insertion.
+    pub fn insertions(&self) -> &[AnnotatedAxiom] {
+        &self.insertions
+    }
+
+    /// Get the axioms queued for removal.
+    pub fn removals(&self) -> &[AnnotatedAxiom] {
+        &self.removals
+    }
+
+    /// Check whether no edit has been queued in the sink.
+    pub fn is_empty(&self) -> bool {
+        self.insertions.is_empty() && self.removals.is_empty()
+    }
+
+    /// Queue all the edits of another sink into this one.
+    pub fn append(&mut self, other: &mut AxiomSink) {
+        self.insertions.append(&mut other.insertions);
+        self.removals.append(&mut other.removals);
+    }
+
+    /// Apply the queued edits to an ontology.
+    pub fn apply<O: MutableOntology + ?Sized>(self, ontology: &mut O) {
+        for axiom in self.removals.iter() {
+            ontology.remove(axiom);
+        }
+        for axiom in self.insertions.into_iter() {
+            ontology.insert(axiom);
+        }
+    }
+}
+
+/// A trait for visitors that can emit axioms during a traversal.
+#[blanket(derive(Mut, Box))]
+pub trait Emit {
+    /// Get a mutable reference to the sink of the visitor.
+    fn sink(&mut self) -> &mut AxiomSink;
+}
+
+/// Visit every axiom of an ontology and return the edits queued by the visitor.
+///
+/// Because the visitor may hold references to the visited axioms, the
+/// edits cannot be applied while it is alive: use [`AxiomSink::apply`]
+/// once the visitor has been dropped.
+pub fn emit<'ast, V, I>(axioms: I, visitor: &mut V) -> AxiomSink
+where
+    V: Visit<'ast> + Emit + ?Sized,
+    I: IntoIterator<Item = &'ast AnnotatedAxiom>,
+{
+    for axiom in axioms.into_iter() {
+        visitor.visit_annotated_axiom(axiom);
+    }
+    mem::take(visitor.sink())
+}
+
+/// Visit every axiom of an ontology and apply the edits queued by the visitor.
+///
+/// This is only possible for visitors that do not retain references to
+/// the visited axioms; otherwise use [`emit`] and [`AxiomSink::apply`].
+pub fn edit<O, V>(ontology: &mut O, visitor: &mut V)
+where
+    O: MutableOntology,
+    for<'ast> &'ast O: IntoIterator<Item = &'ast AnnotatedAxiom>,
+    V: for<'ast> Visit<'ast> + Emit + ?Sized,
+{
+    emit(&*ontology, visitor).apply(ontology);
+}
@@ -0,0 +1,586 @@
+//! Collect the subjects of the axioms of an ontology.
+//!
+//! The subject of an axiom is the entity it is primarily about, in the
+//! sense that the axiom contributes to its definition. Following the
+//! `getAxioms` methods of the OWL API, the subjects of each kind of axiom
+//! are the following:
+//!
+//! - the named subclass of a `SubClassOf` axiom, and the class of a
+//!   `DisjointUnion` or `HasKey` axiom;
+//! - every named class of an `EquivalentClasses` or `DisjointClasses`
+//!   axiom;
+//! - the named subproperty of a sub-property axiom, and every named
+//!   property of an equivalent, disjoint or inverse properties axiom;
+//! - the named property of a domain, range or property characteristic
+//!   axiom;
+//! - the datatype of a `DatatypeDefinition` axiom;
+//! - the named individuals of an assertion, or the source individual of a
+//!   property assertion;
+//! - the IRI of an `AnnotationAssertion` axiom, which can be an entity of
+//!   any kind.
+//!
+//! Declarations, ontology annotations and imports have no subject.
+//!
+//! # Example
+//!
+//! ```rust
+//! # extern crate horned_owl;
+//! # extern crate horned_visit;
+//! use horned_owl::model::*;
+//! use horned_visit::signature::Entity;
+//! use horned_visit::subject::Subject;
+//!
+//! let b = Build::new();
+//! let a = b.class("http://example.com/A");
+//! let c = b.class("http://example.com/B");
+//! let axiom = Axiom::from(SubClassOf {
+//!     sub: a.clone().into(),
+//!     sup: c.clone().into(),
+//! });
+//!
+//! let subjects = horned_visit::subject::subjects(&axiom);
+//! assert!(subjects.contains(&Subject::Entity(Entity::from(&a))));
+//! assert!(!subjects.contains(&Subject::Entity(Entity::from(&c))));
+//! ```
+
+use std::collections::BTreeSet;
+
+use horned_owl::model::*;
+
+use super::signature::Entity;
+use super::Visit;
+
+/// The subject of an axiom.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Subject {
+    /// A named entity.
+    Entity(Entity),
+    /// The IRI of an annotation assertion, which can be an entity of any kind.
+    IRI(IRI),
+}
+
+impl Subject {
+    /// Check whether the subject is the given entity.
+    pub fn is_entity(&self, entity: &Entity) -> bool {
+        match self {
+            Subject::Entity(e) => e == entity,
+            Subject::IRI(iri) => &entity.iri == iri,
+        }
+    }
+}
+
+/// A visitor collecting the subjects of the visited axioms.
+#[derive(Clone, Debug, Default)]
+pub struct SubjectCollector {
+    subjects: BTreeSet<Subject>,
+}
+
+impl SubjectCollector {
+    /// Create a new collector.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the subjects collected so far.
+    pub fn subjects(&self) -> &BTreeSet<Subject> {
+        &self.subjects
+    }
+
+    /// Consume the collector and return the collected subjects.
+    pub fn into_subjects(self) -> BTreeSet<Subject> {
+        self.subjects
+    }
+
+    /// Add an entity to the subjects.
+    fn add_entity<E: Into<Entity>>(&mut self, entity: E) {
+        self.subjects.insert(Subject::Entity(entity.into()));
+    }
+
+    /// Add a class expression to the subjects if it is a named class.
+    fn add_class_expression(&mut self, class_expression: &ClassExpression) {
+        if let ClassExpression::Class(class) = class_expression {
+            self.add_entity(class);
+        }
+    }
+
+    /// Add an individual to the subjects if it is a named individual.
+    fn add_individual(&mut self, individual: &Individual) {
+        if let Individual::Named(named_individual) = individual {
+            self.add_entity(named_individual);
+        }
+    }
+
+    /// Add an object property expression to the subjects if it is a named property.
+    fn add_object_property_expression(&mut self, ope: &ObjectPropertyExpression) {
+        if let Some(op) = ope.as_property() {
+            self.add_entity(op);
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for SubjectCollector {
+    fn visit_annotation_assertion(&mut self, annotation_assertion: &'ast AnnotationAssertion) {
+        if let AnnotationSubject::IRI(iri) = &annotation_assertion.subject {
+            self.subjects.insert(Subject::IRI(iri.clone()));
+        }
+    }
+
+    fn visit_annotation_property_domain(&mut self, axiom: &'ast AnnotationPropertyDomain) {
+        self.add_entity(&axiom.ap);
+    }
+
+    fn visit_annotation_property_range(&mut self, axiom: &'ast AnnotationPropertyRange) {
+        self.add_entity(&axiom.ap);
+    }
+
+    fn visit_asymmetric_object_property(&mut self, axiom: &'ast AsymmetricObjectProperty) {
+        self.add_object_property_expression(&axiom.0);
+    }
+
+    fn visit_class_assertion(&mut self, axiom: &'ast ClassAssertion) {
+        self.add_individual(&axiom.i);
+    }
+
+    fn visit_data_property_assertion(&mut self, axiom: &'ast DataPropertyAssertion) {
+        self.add_individual(&axiom.from);
+    }
+
+    fn visit_data_property_domain(&mut self, axiom: &'ast DataPropertyDomain) {
+        self.add_entity(&axiom.dp);
+    }
+
+    fn visit_data_property_range(&mut self, axiom: &'ast DataPropertyRange) {
+        self.add_entity(&axiom.dp);
+    }
+
+    fn visit_datatype_definition(&mut self, axiom: &'ast DatatypeDefinition) {
+        self.add_entity(&axiom.kind);
+    }
+
+    fn visit_different_individuals(&mut self, axiom: &'ast DifferentIndividuals) {
+        for individual in axiom.0.iter() {
+            self.add_individual(individual);
+        }
+    }
+
+    fn visit_disjoint_classes(&mut self, axiom: &'ast DisjointClasses) {
+        for ce in axiom.0.iter() {
+            self.add_class_expression(ce);
+        }
+    }
+
+    fn visit_disjoint_data_properties(&mut self, axiom: &'ast DisjointDataProperties) {
+        for dp in axiom.0.iter() {
+            self.add_entity(dp);
+        }
+    }
+
+    fn visit_disjoint_object_properties(&mut self, axiom: &'ast DisjointObjectProperties) {
+        for ope in axiom.0.iter() {
+            self.add_object_property_expression(ope);
+        }
+    }
+
+    fn visit_disjoint_union(&mut self, axiom: &'ast DisjointUnion) {
+        self.add_entity(&axiom.0);
+    }
+
+    fn visit_equivalent_classes(&mut self, axiom: &'ast EquivalentClasses) {
+        for ce in axiom.0.iter() {
+            self.add_class_expression(ce);
+        }
+    }
+
+    fn visit_equivalent_data_properties(&mut self, axiom: &'ast EquivalentDataProperties) {
+        for dp in axiom.0.iter() {
+            self.add_entity(dp);
+        }
+    }
+
+    fn visit_equivalent_object_properties(&mut self, axiom: &'ast EquivalentObjectProperties) {
+        for ope in axiom.0.iter() {
+            self.add_object_property_expression(ope);
+        }
+    }
+
+    fn visit_functional_data_property(&mut self, axiom: &'ast FunctionalDataProperty) {
+        self.add_entity(&axiom.0);
+    }
+
+    fn visit_functional_object_property(&mut self, axiom: &'ast FunctionalObjectProperty) {
+        self.add_object_property_expression(&axiom.0);
+    }
+
+    fn visit_has_key(&mut self, axiom: &'ast HasKey) {
+        self.add_class_expression(&axiom.ce);
+    }
+
+    fn visit_inverse_functional_object_property(
+        &mut self,
+        axiom: &'ast InverseFunctionalObjectProperty,
+    ) {
+        self.add_object_property_expression(&axiom.0);
+    }
+
+    fn visit_inverse_object_properties(&mut self, axiom: &'ast InverseObjectProperties) {
+        self.add_entity(&axiom.0);
+        self.add_entity(&axiom.1);
+    }
+
+    fn visit_irreflexive_object_property(&mut self, axiom: &'ast IrreflexiveObjectProperty) {
+        self.add_object_property_expression(&axiom.0);
+    }
+
+    fn visit_negative_data_property_assertion(
+        &mut self,
+        axiom: &'ast NegativeDataPropertyAssertion,
+    ) {
+        self.add_individual(&axiom.from);
+    }
+
+    fn visit_negative_object_property_assertion(
+        &mut self,
+        axiom: &'ast NegativeObjectPropertyAssertion,
+    ) {
+        self.add_individual(&axiom.from);
+    }
+
+    fn visit_object_property_assertion(&mut self, axiom: &'ast ObjectPropertyAssertion) {
+        self.add_individual(&axiom.from);
+    }
+
+    fn visit_object_property_domain(&mut self, axiom: &'ast ObjectPropertyDomain) {
+        self.add_object_property_expression(&axiom.ope);
+    }
+
+    fn visit_object_property_range(&mut self, axiom: &'ast ObjectPropertyRange) {
+        self.add_object_property_expression(&axiom.ope);
+    }
+
+    fn visit_reflexive_object_property(&mut self, axiom: &'ast ReflexiveObjectProperty) {
+        self.add_object_property_expression(&axiom.0);
+    }
+
+    fn visit_same_individual(&mut self, axiom: &'ast SameIndividual) {
+        for individual in axiom.0.iter() {
+            self.add_individual(individual);
+        }
+    }
+
+    fn visit_sub_annotation_property_of(&mut self, axiom: &'ast SubAnnotationPropertyOf) {
+        self.add_entity(&axiom.sub);
+    }
+
+    fn visit_sub_class_of(&mut self, axiom: &'ast SubClassOf) {
+        self.add_class_expression(&axiom.sub);
+    }
+
+    fn visit_sub_data_property_of(&mut self, axiom: &'ast SubDataPropertyOf) {
+        self.add_entity(&axiom.sub);
+    }
+
+    fn visit_sub_object_property_of(&mut self, axiom: &'ast SubObjectPropertyOf) {
+        if let SubObjectPropertyExpression::ObjectPropertyExpression(ope) = &axiom.sub {
+            self.add_object_property_expression(ope);
+        }
+    }
+
+    fn visit_symmetric_object_property(&mut self, axiom: &'ast SymmetricObjectProperty) {
+        self.add_object_property_expression(&axiom.0);
+    }
+
+    fn visit_transitive_object_property(&mut self, axiom: &'ast TransitiveObjectProperty) {
+        self.add_object_property_expression(&axiom.0);
+    }
+}
+
+/// Get the subjects of an axiom.
+pub fn subjects(axiom: &Axiom) -> BTreeSet<Subject> {
+    let mut collector = SubjectCollector::new();
+    collector.visit_axiom(axiom);
+    collector.into_subjects()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::*;
+
+    fn entity<E: Into<Entity>>(e: E) -> Subject {
+        Subject::Entity(e.into())
+    }
+
+    fn assert_subjects<A: Into<Axiom>>(axiom: A, expected: Vec<Subject>) {
+        let axiom = axiom.into();
+        assert_eq!(
+            subjects(&axiom),
+            expected.into_iter().collect(),
+            "{:?}",
+            axiom
+        );
+    }
+
+    #[test]
+    fn class_axioms() {
+        let existential = some("r", class("B"));
+        assert_subjects(
+            SubClassOf {
+                sub: class("A"),
+                sup: existential.clone(),
+            },
+            vec![entity(named_class("A"))],
+        );
+        assert_subjects(
+            SubClassOf {
+                sub: existential.clone(),
+                sup: class("A"),
+            },
+            vec![],
+        );
+        assert_subjects(
+            EquivalentClasses(vec![class("A"), class("B"), existential.clone()]),
+            vec![entity(named_class("A")), entity(named_class("B"))],
+        );
+        assert_subjects(
+            DisjointClasses(vec![class("A"), existential.clone()]),
+            vec![entity(named_class("A"))],
+        );
+        assert_subjects(
+            DisjointUnion(named_class("A"), vec![class("B"), class("C")]),
+            vec![entity(named_class("A"))],
+        );
+        assert_subjects(
+            HasKey {
+                ce: class("A"),
+                vpe: vec![PropertyExpression::ObjectPropertyExpression(op("r"))],
+            },
+            vec![entity(named_class("A"))],
+        );
+    }
+
+    #[test]
+    fn object_property_axioms() {
+        let r = || entity(ObjectProperty(iri("r")));
+        assert_subjects(
+            SubObjectPropertyOf {
+                sub: SubObjectPropertyExpression::ObjectPropertyExpression(op("r")),
+                sup: op("s"),
+            },
+            vec![r()],
+        );
+        assert_subjects(
+            SubObjectPropertyOf {
+                sub: SubObjectPropertyExpression::ObjectPropertyChain(vec![op("r"), op("s")]),
+                sup: op("t"),
+            },
+            vec![],
+        );
+        assert_subjects(
+            EquivalentObjectProperties(vec![op("r"), inverse_of("s")]),
+            vec![r()],
+        );
+        assert_subjects(
+            DisjointObjectProperties(vec![op("r"), op("s")]),
+            vec![r(), entity(ObjectProperty(iri("s")))],
+        );
+        assert_subjects(
+            InverseObjectProperties(ObjectProperty(iri("r")), ObjectProperty(iri("s"))),
+            vec![r(), entity(ObjectProperty(iri("s")))],
+        );
+        assert_subjects(
+            ObjectPropertyDomain {
+                ope: op("r"),
+                ce: class("A"),
+            },
+            vec![r()],
+        );
+        assert_subjects(
+            ObjectPropertyRange {
+                ope: inverse_of("r"),
+                ce: class("A"),
+            },
+            vec![],
+        );
+        assert_subjects(FunctionalObjectProperty(op("r")), vec![r()]);
+        assert_subjects(InverseFunctionalObjectProperty(op("r")), vec![r()]);
+        assert_subjects(ReflexiveObjectProperty(op("r")), vec![r()]);
+        assert_subjects(IrreflexiveObjectProperty(op("r")), vec![r()]);
+        assert_subjects(SymmetricObjectProperty(op("r")), vec![r()]);
+        assert_subjects(AsymmetricObjectProperty(op("r")), vec![r()]);
+        assert_subjects(TransitiveObjectProperty(inverse_of("r")), vec![]);
+    }
+
+    #[test]
+    fn data_property_axioms() {
+        let d = DataProperty(iri("d"));
+        let e = DataProperty(iri("e"));
+        let integer = Datatype(Build::new().iri("http://www.w3.org/2001/XMLSchema#integer"));
+        assert_subjects(
+            SubDataPropertyOf {
+                sub: d.clone(),
+                sup: e.clone(),
+            },
+            vec![entity(&d)],
+        );
+        assert_subjects(
+            EquivalentDataProperties(vec![d.clone(), e.clone()]),
+            vec![entity(&d), entity(&e)],
+        );
+        assert_subjects(
+            DisjointDataProperties(vec![d.clone(), e.clone()]),
+            vec![entity(&d), entity(&e)],
+        );
+        assert_subjects(
+            DataPropertyDomain {
+                dp: d.clone(),
+                ce: class("A"),
+            },
+            vec![entity(&d)],
+        );
+        assert_subjects(
+            DataPropertyRange {
+                dp: d.clone(),
+                dr: integer.clone().into(),
+            },
+            vec![entity(&d)],
+        );
+        assert_subjects(FunctionalDataProperty(d.clone()), vec![entity(&d)]);
+        assert_subjects(
+            DatatypeDefinition {
+                kind: Datatype(iri("T")),
+                range: integer.into(),
+            },
+            vec![entity(Datatype(iri("T")))],
+        );
+    }
+
+    #[test]
+    fn assertions() {
+        let a = || entity(NamedIndividual(iri("a")));
+        let literal = Literal::Simple {
+            literal: "x".into(),
+        };
+        assert_subjects(
+            ClassAssertion {
+                ce: class("A"),
+                i: individual("a"),
+            },
+            vec![a()],
+        );
+        assert_subjects(
+            ClassAssertion {
+                ce: class("A"),
+                i: anonymous("x"),
+            },
+            vec![],
+        );
+        assert_subjects(
+            ObjectPropertyAssertion {
+                ope: op("r"),
+                from: individual("a"),
+                to: individual("b"),
+            },
+            vec![a()],
+        );
+        assert_subjects(
+            NegativeObjectPropertyAssertion {
+                ope: op("r"),
+                from: anonymous("x"),
+                to: individual("b"),
+            },
+            vec![],
+        );
+        assert_subjects(
+            DataPropertyAssertion {
+                dp: DataProperty(iri("d")),
+                from: individual("a"),
+                to: literal.clone(),
+            },
+            vec![a()],
+        );
+        assert_subjects(
+            NegativeDataPropertyAssertion {
+                dp: DataProperty(iri("d")),
+                from: individual("a"),
+                to: literal,
+            },
+            vec![a()],
+        );
+        assert_subjects(
+            SameIndividual(vec![individual("a"), anonymous("x")]),
+            vec![a()],
+        );
+        assert_subjects(
+            DifferentIndividuals(vec![individual("a"), individual("b")]),
+            vec![a(), entity(NamedIndividual(iri("b")))],
+        );
+    }
+
+    #[test]
+    fn annotation_axioms() {
+        let p = AnnotationProperty(iri("p"));
+        let q = AnnotationProperty(iri("q"));
+        let ann = Annotation {
+            ap: p.clone(),
+            av: AnnotationValue::IRI(iri("B")),
+        };
+        assert_subjects(
+            AnnotationAssertion {
+                subject: AnnotationSubject::IRI(iri("A")),
+                ann: ann.clone(),
+            },
+            vec![Subject::IRI(iri("A"))],
+        );
+        assert_subjects(
+            AnnotationAssertion {
+                subject: AnnotationSubject::AnonymousIndividual("x".to_string().into()),
+                ann,
+            },
+            vec![],
+        );
+        assert_subjects(
+            SubAnnotationPropertyOf {
+                sub: p.clone(),
+                sup: q,
+            },
+            vec![entity(&p)],
+        );
+        assert_subjects(
+            AnnotationPropertyDomain {
+                ap: p.clone(),
+                iri: iri("A"),
+            },
+            vec![entity(&p)],
+        );
+        assert_subjects(
+            AnnotationPropertyRange {
+                ap: p.clone(),
+                iri: iri("A"),
+            },
+            vec![entity(&p)],
+        );
+    }
+
+    #[test]
+    fn no_subject() {
+        assert_subjects(DeclareClass(named_class("A")), vec![]);
+        assert_subjects(Import(iri("ontology")), vec![]);
+        assert_subjects(
+            OntologyAnnotation(Annotation {
+                ap: AnnotationProperty(iri("p")),
+                av: AnnotationValue::IRI(iri("A")),
+            }),
+            vec![],
+        );
+    }
+
+    #[test]
+    fn is_entity() {
+        let class = Entity::from(named_class("A"));
+        let property = Entity::from(ObjectProperty(iri("A")));
+        assert!(entity(class.clone()).is_entity(&class));
+        assert!(!entity(class.clone()).is_entity(&property));
+        assert!(Subject::IRI(iri("A")).is_entity(&class));
+        assert!(Subject::IRI(iri("A")).is_entity(&property));
+    }
+}
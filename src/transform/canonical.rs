@@ -0,0 +1,287 @@
+//! Canonical form and structural equivalence of ontology elements.
+//!
+//! The [OWL2 structural specification](https://www.w3.org/TR/owl2-syntax/#Structural_Specification)
+//! considers the operands of constructs such as `ObjectIntersectionOf` or
+//! `EquivalentClasses` to be *sets*, but `horned-owl` stores them in a
+//! [`Vec`], so that two structurally equivalent elements may compare as
+//! different. The canonical form of an element sorts and deduplicates
+//! such operands, recursively, so that structurally equivalent elements
+//! have equal canonical forms.
+//!
+//! # Example
+//!
+//! ```rust
+//! # extern crate horned_owl;
+//! # extern crate horned_visit;
+//! use horned_owl::model::*;
+//! use horned_visit::transform::canonical::structurally_equal;
+//!
+//! let b = Build::new();
+//! let a: ClassExpression = b.class("http://example.com/A").into();
+//! let c: ClassExpression = b.class("http://example.com/B").into();
+//!
+//! let ab = ClassExpression::ObjectIntersectionOf(vec![a.clone(), c.clone()]);
+//! let ba = ClassExpression::ObjectIntersectionOf(vec![c.clone(), a.clone()]);
+//! assert_ne!(ab, ba);
+//! assert!(structurally_equal(&ab, &ba));
+//! ```
+
+use horned_owl::model::*;
+
+use crate::sink::AxiomSink;
+use crate::visit_mut;
+use crate::VisitMut;
+
+/// Sort and deduplicate the operands of an n-ary construct.
+fn normalize<T: Ord>(operands: &mut Vec<T>) {
+    operands.sort_unstable();
+    operands.dedup();
+}
+
+/// A visitor turning the visited elements into their canonical form.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Canonicalizer;
+
+impl VisitMut for Canonicalizer {
+    fn visit_class_expression(&mut self, class_expression: &mut ClassExpression) {
+        visit_mut::visit_class_expression(self, class_expression);
+        match class_expression {
+            ClassExpression::ObjectIntersectionOf(ces) | ClassExpression::ObjectUnionOf(ces) => {
+                normalize(ces)
+            }
+            ClassExpression::ObjectOneOf(is) => normalize(is),
+            _ => (),
+        }
+    }
+
+    fn visit_data_range(&mut self, data_range: &mut DataRange) {
+        visit_mut::visit_data_range(self, data_range);
+        match data_range {
+            DataRange::DataIntersectionOf(drs) | DataRange::DataUnionOf(drs) => normalize(drs),
+            DataRange::DataOneOf(lits) => normalize(lits),
+            DataRange::DatatypeRestriction(_, frs) => normalize(frs),
+            _ => (),
+        }
+    }
+
+    fn visit_different_individuals(&mut self, di: &mut DifferentIndividuals) {
+        visit_mut::visit_different_individuals(self, di);
+        normalize(&mut di.0);
+    }
+
+    fn visit_disjoint_classes(&mut self, dc: &mut DisjointClasses) {
+        visit_mut::visit_disjoint_classes(self, dc);
+        normalize(&mut dc.0);
+    }
+
+    fn visit_disjoint_data_properties(&mut self, ddp: &mut DisjointDataProperties) {
+        visit_mut::visit_disjoint_data_properties(self, ddp);
+        normalize(&mut ddp.0);
+    }
+
+    fn visit_disjoint_object_properties(&mut self, dop: &mut DisjointObjectProperties) {
+        visit_mut::visit_disjoint_object_properties(self, dop);
+        normalize(&mut dop.0);
+    }
+
+    fn visit_disjoint_union(&mut self, du: &mut DisjointUnion) {
+        visit_mut::visit_disjoint_union(self, du);
+        normalize(&mut du.1);
+    }
+
+    fn visit_equivalent_classes(&mut self, ec: &mut EquivalentClasses) {
+        visit_mut::visit_equivalent_classes(self, ec);
+        normalize(&mut ec.0);
+    }
+
+    fn visit_equivalent_data_properties(&mut self, edp: &mut EquivalentDataProperties) {
+        visit_mut::visit_equivalent_data_properties(self, edp);
+        normalize(&mut edp.0);
+    }
+
+    fn visit_equivalent_object_properties(&mut self, eop: &mut EquivalentObjectProperties) {
+        visit_mut::visit_equivalent_object_properties(self, eop);
+        normalize(&mut eop.0);
+    }
+
+    fn visit_has_key(&mut self, has_key: &mut HasKey) {
+        visit_mut::visit_has_key(self, has_key);
+        normalize(&mut has_key.vpe);
+    }
+
+    fn visit_same_individual(&mut self, si: &mut SameIndividual) {
+        visit_mut::visit_same_individual(self, si);
+        normalize(&mut si.0);
+    }
+}
+
+/// A trait for elements that have a canonical form.
+pub trait Canonicalize: Clone {
+    /// Turn the element into its canonical form, in place.
+    fn canonicalize(&mut self);
+
+    /// Get the canonical form of the element.
+    fn canonical(&self) -> Self {
+        let mut canonical = self.clone();
+        canonical.canonicalize();
+        canonical
+    }
+}
+
+macro_rules! impl_canonicalize {
+    ($($name:ident($type:ty),)*) => {
+        paste! {
+            $(
+                impl Canonicalize for $type {
+                    fn canonicalize(&mut self) {
+                        Canonicalizer.[<visit_ $name>](self);
+                    }
+                }
+            )*
+        }
+    }
+}
+
+impl_canonicalize! {
+    annotated_axiom(AnnotatedAxiom),
+    axiom(Axiom),
+    class_assertion(ClassAssertion),
+    class_expression(ClassExpression),
+    data_range(DataRange),
+    different_individuals(DifferentIndividuals),
+    disjoint_classes(DisjointClasses),
+    disjoint_data_properties(DisjointDataProperties),
+    disjoint_object_properties(DisjointObjectProperties),
+    disjoint_union(DisjointUnion),
+    equivalent_classes(EquivalentClasses),
+    equivalent_data_properties(EquivalentDataProperties),
+    equivalent_object_properties(EquivalentObjectProperties),
+    has_key(HasKey),
+    same_individual(SameIndividual),
+    sub_class_of(SubClassOf),
+}
+
+/// Check whether two elements are structurally equivalent.
+pub fn structurally_equal<T: Canonicalize + PartialEq>(a: &T, b: &T) -> bool {
+    a == b || a.canonical() == b.canonical()
+}
+
+/// Replace every axiom of an ontology with its canonical form.
+///
+/// Axioms that are structurally equivalent are merged in the process.
+pub fn canonicalize_ontology<O>(ontology: &mut O)
+where
+    O: MutableOntology,
+    for<'a> &'a O: IntoIterator<Item = &'a AnnotatedAxiom>,
+{
+    let mut sink = AxiomSink::new();
+    for aa in ontology.into_iter() {
+        let canonical = aa.canonical();
+        if &canonical != aa {
+            sink.replace(aa.clone(), canonical);
+        }
+    }
+    sink.apply(ontology);
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::fixtures::*;
+
+    #[test]
+    fn disjoint_classes_dedup() {
+        let dc = DisjointClasses(vec![class("B"), class("A"), class("B")]);
+        assert_eq!(
+            dc.canonical(),
+            DisjointClasses(vec![class("A"), class("B")])
+        );
+    }
+
+    #[test]
+    fn nested_operands() {
+        let ce = ClassExpression::ObjectUnionOf(vec![
+            some(
+                "r",
+                ClassExpression::ObjectIntersectionOf(vec![class("B"), class("A")]),
+            ),
+            class("C"),
+            class("C"),
+        ]);
+        let mut operands = vec![
+            class("C"),
+            some(
+                "r",
+                ClassExpression::ObjectIntersectionOf(vec![class("A"), class("B")]),
+            ),
+        ];
+        operands.sort();
+        assert_eq!(ce.canonical(), ClassExpression::ObjectUnionOf(operands));
+    }
+
+    #[test]
+    fn one_of_dedup() {
+        let ce =
+            ClassExpression::ObjectOneOf(vec![individual("b"), individual("a"), individual("a")]);
+        assert_eq!(
+            ce.canonical(),
+            ClassExpression::ObjectOneOf(vec![individual("a"), individual("b")])
+        );
+    }
+
+    #[test]
+    fn disjoint_union_keeps_defined_class() {
+        let du = DisjointUnion(named_class("Z"), vec![class("B"), class("A")]);
+        assert_eq!(
+            du.canonical(),
+            DisjointUnion(named_class("Z"), vec![class("A"), class("B")])
+        );
+    }
+
+    #[test]
+    fn sub_class_of_sides_are_not_swapped() {
+        let axiom = sub_class_of(class("B"), class("A"));
+        assert_eq!(axiom.canonical(), axiom);
+        assert!(!structurally_equal(
+            &axiom,
+            &sub_class_of(class("A"), class("B"))
+        ));
+    }
+
+    #[test]
+    fn annotations_are_kept() {
+        let annotation = Annotation {
+            ap: AnnotationProperty(iri("p")),
+            av: AnnotationValue::IRI(iri("v")),
+        };
+        let aa = AnnotatedAxiom::new(
+            EquivalentClasses(vec![class("B"), class("A")]),
+            vec![annotation.clone()].into_iter().collect(),
+        );
+        assert_eq!(
+            aa.canonical(),
+            AnnotatedAxiom::new(
+                EquivalentClasses(vec![class("A"), class("B")]),
+                vec![annotation].into_iter().collect(),
+            )
+        );
+    }
+
+    #[test]
+    fn merge_equivalent_axioms() {
+        let mut o = ontology(vec![
+            DisjointClasses(vec![class("A"), class("B")]).into(),
+            DisjointClasses(vec![class("B"), class("A"), class("A")]).into(),
+            sub_class_of(class("C"), class("A")),
+        ]);
+        canonicalize_ontology(&mut o);
+        assert_eq!(
+            o,
+            ontology(vec![
+                DisjointClasses(vec![class("A"), class("B")]).into(),
+                sub_class_of(class("C"), class("A")),
+            ])
+        );
+    }
+}
@@ -0,0 +1,427 @@
+//! Expansion of syntactic shortcuts into core OWL2 constructs.
+//!
+//! The [`Desugarer`] rewrites the following constructs:
+//!
+//! | Shortcut                         | Expansion                                          |
+//! |----------------------------------|----------------------------------------------------|
+//! | `DisjointUnion(C CE1 ... CEn)`   | `EquivalentClasses(C ObjectUnionOf(CE1 ... CEn))` and `DisjointClasses(CE1 ... CEn)` |
+//! | `DisjointClasses(CE1 ... CEn)`   | `DisjointClasses(CEi CEj)` for every `i < j`       |
+//! | `ObjectPropertyDomain(P CE)`     | `SubClassOf(ObjectSomeValuesFrom(P owl:Thing) CE)` |
+//! | `ObjectPropertyRange(P CE)`      | `SubClassOf(owl:Thing ObjectAllValuesFrom(P CE))`  |
+//! | `InverseObjectProperties(P Q)`   | `SubObjectPropertyOf(P ObjectInverseOf(Q))` and `SubObjectPropertyOf(ObjectInverseOf(Q) P)` |
+//! | `ObjectHasValue(P a)`            | `ObjectSomeValuesFrom(P ObjectOneOf(a))`           |
+//! | `ObjectExactCardinality(n P CE)` | `ObjectIntersectionOf(ObjectMinCardinality(n P CE) ObjectMaxCardinality(n P CE))` |
+//! | `DataHasValue(R v)`              | `DataSomeValuesFrom(R DataOneOf(v))`               |
+//! | `DataExactCardinality(n R D)`    | `ObjectIntersectionOf(DataMinCardinality(n R D) DataMaxCardinality(n R D))` |
+//!
+//! The annotations of a rewritten axiom are copied to each of the axioms it
+//! expands into.
+//!
+//! # Example
+//!
+//! ```rust
+//! # extern crate horned_owl;
+//! # extern crate horned_visit;
+//! use horned_owl::model::*;
+//! use horned_owl::ontology::set::SetOntology;
+//!
+//! let b = Build::new();
+//! let mut ontology = SetOntology::new();
+//! ontology.insert(DisjointClasses(vec![
+//!     b.class("http://example.com/A").into(),
+//!     b.class("http://example.com/B").into(),
+//!     b.class("http://example.com/C").into(),
+//! ]));
+//!
+//! horned_visit::transform::desugar::desugar_ontology(&mut ontology);
+//! assert_eq!(ontology.iter().count(), 3);
+//! ```
+
+use horned_owl::model::*;
+use horned_owl::vocab::WithIRI;
+use horned_owl::vocab::OWL;
+
+use crate::sink::edit;
+use crate::sink::AxiomSink;
+use crate::sink::Emit;
+use crate::transform::pairs;
+use crate::visit_mut;
+use crate::Visit;
+use crate::VisitMut;
+
+/// A visitor expanding the shortcuts of the visited class expressions.
+#[derive(Clone, Copy, Debug, Default)]
+struct ExpressionDesugarer;
+
+impl VisitMut for ExpressionDesugarer {
+    fn visit_class_expression(&mut self, class_expression: &mut ClassExpression) {
+        use self::ClassExpression::*;
+        visit_mut::visit_class_expression(self, class_expression);
+        let desugared = match class_expression {
+            ObjectHasValue { ope, i } => ObjectSomeValuesFrom {
+                ope: ope.clone(),
+                bce: Box::new(ObjectOneOf(vec![i.clone()])),
+            },
+            ObjectExactCardinality { n, ope, bce } => ObjectIntersectionOf(vec![
+                ObjectMinCardinality {
+                    n: *n,
+                    ope: ope.clone(),
+                    bce: bce.clone(),
+                },
+                ObjectMaxCardinality {
+                    n: *n,
+                    ope: ope.clone(),
+                    bce: bce.clone(),
+                },
+            ]),
+            DataHasValue { dp, l } => DataSomeValuesFrom {
+                dp: dp.clone(),
+                dr: DataRange::DataOneOf(vec![l.clone()]),
+            },
+            DataExactCardinality { n, dp, dr } => ObjectIntersectionOf(vec![
+                DataMinCardinality {
+                    n: *n,
+                    dp: dp.clone(),
+                    dr: dr.clone(),
+                },
+                DataMaxCardinality {
+                    n: *n,
+                    dp: dp.clone(),
+                    dr: dr.clone(),
+                },
+            ]),
+            _ => return,
+        };
+        *class_expression = desugared;
+    }
+}
+
+/// A visitor replacing the visited axioms with their desugared form.
+#[derive(Debug, Default)]
+pub struct Desugarer {
+    build: Build,
+    sink: AxiomSink,
+}
+
+impl Desugarer {
+    /// Create a new desugarer with an empty sink.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn thing(&self) -> ClassExpression {
+        ClassExpression::Class(self.build.class(OWL::Thing.iri_str()))
+    }
+
+    /// Get the axioms an axiom expands into.
+    pub fn desugar(&self, axiom: &AnnotatedAxiom) -> Vec<AnnotatedAxiom> {
+        let mut logical = axiom.axiom.clone();
+        ExpressionDesugarer.visit_axiom(&mut logical);
+
+        let mut expanded = Vec::new();
+        match logical {
+            Axiom::DisjointUnion(DisjointUnion(c, ces)) => {
+                expanded.push(Axiom::from(EquivalentClasses(vec![
+                    ClassExpression::Class(c),
+                    ClassExpression::ObjectUnionOf(ces.clone()),
+                ])));
+                expanded.push(Axiom::from(DisjointClasses(ces)));
+            }
+            Axiom::ObjectPropertyDomain(ObjectPropertyDomain { ope, ce }) => {
+                expanded.push(Axiom::from(SubClassOf {
+                    sub: ClassExpression::ObjectSomeValuesFrom {
+                        ope,
+                        bce: Box::new(self.thing()),
+                    },
+                    sup: ce,
+                }));
+            }
+            Axiom::ObjectPropertyRange(ObjectPropertyRange { ope, ce }) => {
+                expanded.push(Axiom::from(SubClassOf {
+                    sub: self.thing(),
+                    sup: ClassExpression::ObjectAllValuesFrom {
+                        ope,
+                        bce: Box::new(ce),
+                    },
+                }));
+            }
+            Axiom::InverseObjectProperties(InverseObjectProperties(p, q)) => {
+                let p = ObjectPropertyExpression::ObjectProperty(p);
+                let q = ObjectPropertyExpression::InverseObjectProperty(q);
+                expanded.push(Axiom::from(SubObjectPropertyOf {
+                    sub: SubObjectPropertyExpression::ObjectPropertyExpression(p.clone()),
+                    sup: q.clone(),
+                }));
+                expanded.push(Axiom::from(SubObjectPropertyOf {
+                    sub: SubObjectPropertyExpression::ObjectPropertyExpression(q),
+                    sup: p,
+                }));
+            }
+            other => expanded.push(other),
+        }
+
+        let mut desugared = Vec::with_capacity(expanded.len());
+        for logical in expanded {
+            match logical {
+                Axiom::DisjointClasses(DisjointClasses(ces)) if ces.len() > 2 => {
+                    for pair in pairs(&ces) {
+                        let pair = DisjointClasses(pair);
+                        desugared.push(AnnotatedAxiom::new(pair, axiom.ann.clone()));
+                    }
+                }
+                other => desugared.push(AnnotatedAxiom::new(other, axiom.ann.clone())),
+            }
+        }
+        desugared
+    }
+}
+
+impl Emit for Desugarer {
+    fn sink(&mut self) -> &mut AxiomSink {
+        &mut self.sink
+    }
+}
+
+impl<'ast> Visit<'ast> for Desugarer {
+    fn visit_annotated_axiom(&mut self, annotated_axiom: &'ast AnnotatedAxiom) {
+        let desugared = self.desugar(annotated_axiom);
+        if desugared.len() != 1 || &desugared[0] != annotated_axiom {
+            self.sink.remove(annotated_axiom.clone());
+            desugared.into_iter().for_each(|aa| self.sink.insert(aa));
+        }
+    }
+}
+
+/// Desugar every axiom of an ontology.
+pub fn desugar_ontology<O>(ontology: &mut O)
+where
+    O: MutableOntology,
+    for<'a> &'a O: IntoIterator<Item = &'a AnnotatedAxiom>,
+{
+    edit(ontology, &mut Desugarer::new());
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::collections::BTreeSet;
+
+    use super::*;
+    use crate::fixtures::*;
+
+    fn thing() -> ClassExpression {
+        Build::new().class(OWL::Thing.iri_str()).into()
+    }
+
+    fn desugar(axiom: Axiom) -> Vec<Axiom> {
+        Desugarer::new()
+            .desugar(&axiom.into())
+            .into_iter()
+            .map(|aa| aa.axiom)
+            .collect()
+    }
+
+    fn disjoint(a: &str, b: &str) -> Axiom {
+        DisjointClasses(vec![class(a), class(b)]).into()
+    }
+
+    #[test]
+    fn disjoint_union() {
+        let axiom = DisjointUnion(named_class("Z"), vec![class("A"), class("B"), class("C")]);
+        assert_eq!(
+            desugar(axiom.into()),
+            vec![
+                EquivalentClasses(vec![
+                    class("Z"),
+                    ClassExpression::ObjectUnionOf(vec![class("A"), class("B"), class("C")]),
+                ])
+                .into(),
+                disjoint("A", "B"),
+                disjoint("A", "C"),
+                disjoint("B", "C"),
+            ]
+        );
+    }
+
+    #[test]
+    fn disjoint_classes() {
+        let pair = disjoint("A", "B");
+        assert_eq!(desugar(pair.clone()), vec![pair]);
+        let axiom = DisjointClasses(vec![class("A"), class("B"), class("C")]);
+        assert_eq!(
+            desugar(axiom.into()),
+            vec![disjoint("A", "B"), disjoint("A", "C"), disjoint("B", "C")]
+        );
+    }
+
+    #[test]
+    fn object_property_domain_and_range() {
+        let domain = ObjectPropertyDomain {
+            ope: op("r"),
+            ce: class("A"),
+        };
+        assert_eq!(
+            desugar(domain.into()),
+            vec![sub_class_of(some("r", thing()), class("A"))]
+        );
+        let range = ObjectPropertyRange {
+            ope: inverse_of("r"),
+            ce: class("A"),
+        };
+        assert_eq!(
+            desugar(range.into()),
+            vec![sub_class_of(
+                thing(),
+                ClassExpression::ObjectAllValuesFrom {
+                    ope: inverse_of("r"),
+                    bce: Box::new(class("A")),
+                }
+            )]
+        );
+    }
+
+    #[test]
+    fn inverse_object_properties() {
+        let axiom = InverseObjectProperties(ObjectProperty(iri("p")), ObjectProperty(iri("q")));
+        assert_eq!(
+            desugar(axiom.into()),
+            vec![
+                sub_object_property_of(op("p"), inverse_of("q")),
+                sub_object_property_of(inverse_of("q"), op("p")),
+            ]
+        );
+    }
+
+    #[test]
+    fn object_restrictions() {
+        let has_value = ClassExpression::ObjectHasValue {
+            ope: op("r"),
+            i: individual("a"),
+        };
+        assert_eq!(
+            desugar(sub_class_of(class("A"), has_value)),
+            vec![sub_class_of(
+                class("A"),
+                some("r", ClassExpression::ObjectOneOf(vec![individual("a")]))
+            )]
+        );
+        let exact = ClassExpression::ObjectExactCardinality {
+            n: 2,
+            ope: op("r"),
+            bce: Box::new(class("B")),
+        };
+        assert_eq!(
+            desugar(sub_class_of(class("A"), exact)),
+            vec![sub_class_of(
+                class("A"),
+                ClassExpression::ObjectIntersectionOf(vec![
+                    ClassExpression::ObjectMinCardinality {
+                        n: 2,
+                        ope: op("r"),
+                        bce: Box::new(class("B")),
+                    },
+                    ClassExpression::ObjectMaxCardinality {
+                        n: 2,
+                        ope: op("r"),
+                        bce: Box::new(class("B")),
+                    },
+                ])
+            )]
+        );
+    }
+
+    #[test]
+    fn data_restrictions() {
+        let dp = DataProperty(iri("d"));
+        let literal = Literal::Simple {
+            literal: "v".into(),
+        };
+        let has_value = ClassExpression::DataHasValue {
+            dp: dp.clone(),
+            l: literal.clone(),
+        };
+        assert_eq!(
+            desugar(sub_class_of(class("A"), has_value)),
+            vec![sub_class_of(
+                class("A"),
+                ClassExpression::DataSomeValuesFrom {
+                    dp: dp.clone(),
+                    dr: DataRange::DataOneOf(vec![literal]),
+                }
+            )]
+        );
+        let dr = DataRange::Datatype(Datatype(iri("t")));
+        let exact = ClassExpression::DataExactCardinality {
+            n: 1,
+            dp: dp.clone(),
+            dr: dr.clone(),
+        };
+        assert_eq!(
+            desugar(sub_class_of(class("A"), exact)),
+            vec![sub_class_of(
+                class("A"),
+                ClassExpression::ObjectIntersectionOf(vec![
+                    ClassExpression::DataMinCardinality {
+                        n: 1,
+                        dp: dp.clone(),
+                        dr: dr.clone(),
+                    },
+                    ClassExpression::DataMaxCardinality { n: 1, dp, dr },
+                ])
+            )]
+        );
+    }
+
+    #[test]
+    fn nested_shortcuts() {
+        let axiom = ObjectPropertyDomain {
+            ope: op("r"),
+            ce: ClassExpression::ObjectHasValue {
+                ope: op("s"),
+                i: individual("a"),
+            },
+        };
+        assert_eq!(
+            desugar(axiom.into()),
+            vec![sub_class_of(
+                some("r", thing()),
+                some("s", ClassExpression::ObjectOneOf(vec![individual("a")]))
+            )]
+        );
+    }
+
+    #[test]
+    fn annotations_are_copied() {
+        let annotations = vec![Annotation {
+            ap: AnnotationProperty(iri("p")),
+            av: AnnotationValue::IRI(iri("v")),
+        }]
+        .into_iter()
+        .collect::<BTreeSet<_>>();
+        let axiom = AnnotatedAxiom::new(
+            DisjointClasses(vec![class("A"), class("B"), class("C")]),
+            annotations.clone(),
+        );
+        let desugared = Desugarer::new().desugar(&axiom);
+        assert_eq!(desugared.len(), 3);
+        assert!(desugared.iter().all(|aa| aa.ann == annotations));
+    }
+
+    #[test]
+    fn unchanged_axioms_are_kept() {
+        let mut o = ontology(vec![
+            sub_class_of(class("A"), class("B")),
+            DisjointClasses(vec![class("A"), class("B"), class("C")]).into(),
+        ]);
+        desugar_ontology(&mut o);
+        assert_eq!(
+            o,
+            ontology(vec![
+                sub_class_of(class("A"), class("B")),
+                disjoint("A", "B"),
+                disjoint("A", "C"),
+                disjoint("B", "C"),
+            ])
+        );
+    }
+}
@@ -0,0 +1,601 @@
+//! Normalisation of EL axioms into the normal forms used by EL reasoners.
+//!
+//! The [`ElNormalizer`] rewrites class axioms of the
+//! [OWL2 EL profile](https://www.w3.org/TR/owl2-profiles/#OWL_2_EL) into a
+//! set of axioms of the four normal forms used by completion-based
+//! reasoners such as CEL or ELK:
+//!
+//! | Normal form       | Axiom             |
+//! |-------------------|-------------------|
+//! | [`Subsumption`]   | `A ⊑ B`           |
+//! | [`Conjunction`]   | `A1 ⊓ A2 ⊑ B`     |
+//! | [`Existential`]   | `A ⊑ ∃r.B`        |
+//! | [`Restriction`]   | `∃r.A ⊑ B`        |
+//!
+//! where `A`, `A1`, `A2` and `B` are class names, `owl:Thing` or
+//! `owl:Nothing`, and `r` is an object property. Complex sub-expressions
+//! are replaced by *fresh* class names, and the mapping from fresh names
+//! back to the sub-expressions they stand for is recorded.
+//!
+//! `SubClassOf`, `EquivalentClasses`, `DisjointClasses` and
+//! `ObjectPropertyDomain` axioms are normalised, provided they only use the
+//! `ObjectIntersectionOf` and `ObjectSomeValuesFrom` constructors with
+//! named object properties. Other class axioms are reported as
+//! unsupported, and axioms that are not class axioms are ignored.
+//!
+//! [`Subsumption`]: NormalForm::Subsumption
+//! [`Conjunction`]: NormalForm::Conjunction
+//! [`Existential`]: NormalForm::Existential
+//! [`Restriction`]: NormalForm::Restriction
+//!
+//! # Example
+//!
+//! ```rust
+//! # extern crate horned_owl;
+//! # extern crate horned_visit;
+//! use horned_owl::model::*;
+//! use horned_owl::ontology::set::SetOntology;
+//!
+//! let b = Build::new();
+//! let mut ontology = SetOntology::new();
+//! ontology.insert(SubClassOf {
+//!     sub: b.class("http://example.com/A").into(),
+//!     sup: ClassExpression::ObjectSomeValuesFrom {
+//!         ope: b.object_property("http://example.com/r").into(),
+//!         bce: Box::new(ClassExpression::ObjectIntersectionOf(vec![
+//!             b.class("http://example.com/B").into(),
+//!             b.class("http://example.com/C").into(),
+//!         ])),
+//!     },
+//! });
+//!
+//! let normal_form = horned_visit::transform::el_normal_form::normalize(&ontology);
+//! assert_eq!(normal_form.fresh_classes().count(), 1);
+//! assert_eq!(normal_form.axioms().len(), 3);
+//! ```
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use horned_owl::model::*;
+use horned_owl::vocab::is_nothing;
+use horned_owl::vocab::is_thing;
+use horned_owl::vocab::WithIRI;
+use horned_owl::vocab::OWL;
+
+use crate::transform::pairs;
+use crate::visit_mut;
+use crate::Visit;
+use crate::VisitMut;
+
+/// The default prefix of the IRIs of fresh class names.
+pub const FRESH_PREFIX: &str = "urn:horned-visit:fresh:";
+
+/// An axiom in EL normal form.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum NormalForm {
+    /// `sub ⊑ sup`.
+    Subsumption { sub: Class, sup: Class },
+    /// `left ⊓ right ⊑ sup`.
+    Conjunction {
+        left: Class,
+        right: Class,
+        sup: Class,
+    },
+    /// `sub ⊑ ∃property.filler`.
+    Existential {
+        sub: Class,
+        property: ObjectProperty,
+        filler: Class,
+    },
+    /// `∃property.filler ⊑ sup`.
+    Restriction {
+        property: ObjectProperty,
+        filler: Class,
+        sup: Class,
+    },
+}
+
+impl From<&NormalForm> for Axiom {
+    fn from(nf: &NormalForm) -> Self {
+        use self::NormalForm::*;
+        let (sub, sup) = match nf.clone() {
+            Subsumption { sub, sup } => (sub.into(), sup.into()),
+            Conjunction { left, right, sup } => (
+                ClassExpression::ObjectIntersectionOf(vec![left.into(), right.into()]),
+                sup.into(),
+            ),
+            Existential {
+                sub,
+                property,
+                filler,
+            } => (
+                sub.into(),
+                ClassExpression::ObjectSomeValuesFrom {
+                    ope: property.into(),
+                    bce: filler.into(),
+                },
+            ),
+            Restriction {
+                property,
+                filler,
+                sup,
+            } => (
+                ClassExpression::ObjectSomeValuesFrom {
+                    ope: property.into(),
+                    bce: filler.into(),
+                },
+                sup.into(),
+            ),
+        };
+        Axiom::SubClassOf(SubClassOf { sub, sup })
+    }
+}
+
+impl From<NormalForm> for Axiom {
+    fn from(nf: NormalForm) -> Self {
+        Axiom::from(&nf)
+    }
+}
+
+/// The result of the normalisation of a set of EL axioms.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ElNormalForm {
+    axioms: BTreeSet<NormalForm>,
+    fresh: BTreeMap<Class, ClassExpression>,
+    unsupported: Vec<Axiom>,
+}
+
+impl ElNormalForm {
+    /// Get the normalised axioms.
+    pub fn axioms(&self) -> &BTreeSet<NormalForm> {
+        &self.axioms
+    }
+
+    /// Get the class axioms that could not be normalised.
+    pub fn unsupported(&self) -> &[Axiom] {
+        &self.unsupported
+    }
+
+    /// Iterate over the fresh class names introduced during normalisation.
+    pub fn fresh_classes(&self) -> impl Iterator<Item = &Class> {
+        self.fresh.keys()
+    }
+
+    /// Check whether a class was introduced during normalisation.
+    pub fn is_fresh(&self, class: &Class) -> bool {
+        self.fresh.contains_key(class)
+    }
+
+    /// Get the original class expression a fresh class name stands for.
+    ///
+    /// The fresh names nested in the expression are expanded as well.
+    pub fn original(&self, class: &Class) -> Option<ClassExpression> {
+        let mut expression = self.fresh.get(class)?.clone();
+        self.expand(&mut expression);
+        Some(expression)
+    }
+
+    /// Replace the fresh class names of a class expression by their originals.
+    pub fn expand(&self, class_expression: &mut ClassExpression) {
+        Expander(&self.fresh).visit_class_expression(class_expression);
+    }
+}
+
+/// A visitor replacing fresh class names by the expressions they stand for.
+struct Expander<'a>(&'a BTreeMap<Class, ClassExpression>);
+
+impl VisitMut for Expander<'_> {
+    fn visit_class_expression(&mut self, class_expression: &mut ClassExpression) {
+        if let ClassExpression::Class(c) = class_expression {
+            if let Some(original) = self.0.get(c) {
+                *class_expression = original.clone();
+            }
+        }
+        visit_mut::visit_class_expression(self, class_expression);
+    }
+}
+
+/// Check whether a class expression only uses EL constructors.
+fn is_el(class_expression: &ClassExpression) -> bool {
+    match class_expression {
+        ClassExpression::Class(_) => true,
+        ClassExpression::ObjectIntersectionOf(ces) => ces.iter().all(is_el),
+        ClassExpression::ObjectSomeValuesFrom {
+            ope: ObjectPropertyExpression::ObjectProperty(_),
+            bce,
+        } => is_el(bce),
+        _ => false,
+    }
+}
+
+/// Get the operands of a class expression, flattening nested intersections.
+fn conjuncts(ce: &ClassExpression) -> Vec<&ClassExpression> {
+    match ce {
+        ClassExpression::ObjectIntersectionOf(ces) => ces.iter().flat_map(conjuncts).collect(),
+        other => vec![other],
+    }
+}
+
+/// A visitor normalising the visited EL class axioms.
+#[derive(Debug)]
+pub struct ElNormalizer {
+    build: Build,
+    prefix: String,
+    counter: usize,
+    names: BTreeMap<ClassExpression, Class>,
+    positive: BTreeSet<Class>,
+    negative: BTreeSet<Class>,
+    result: ElNormalForm,
+}
+
+impl Default for ElNormalizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ElNormalizer {
+    /// Create a new normaliser using the default prefix for fresh names.
+    pub fn new() -> Self {
+        Self::with_prefix(FRESH_PREFIX)
+    }
+
+    /// Create a new normaliser using the given prefix for fresh names.
+    pub fn with_prefix<S: Into<String>>(prefix: S) -> Self {
+        Self {
+            build: Build::new(),
+            prefix: prefix.into(),
+            counter: 0,
+            names: BTreeMap::new(),
+            positive: BTreeSet::new(),
+            negative: BTreeSet::new(),
+            result: ElNormalForm::default(),
+        }
+    }
+
+    /// Consume the normaliser and return the normalised axioms.
+    pub fn into_normal_form(self) -> ElNormalForm {
+        self.result
+    }
+
+    fn thing(&self) -> ClassExpression {
+        ClassExpression::Class(self.build.class(OWL::Thing.iri_str()))
+    }
+
+    fn nothing(&self) -> ClassExpression {
+        ClassExpression::Class(self.build.class(OWL::Nothing.iri_str()))
+    }
+
+    /// Get the fresh class name standing for a complex class expression.
+    fn fresh(&mut self, class_expression: &ClassExpression) -> Class {
+        if let Some(class) = self.names.get(class_expression) {
+            return class.clone();
+        }
+        self.counter += 1;
+        let class = self.build.class(format!("{}{}", self.prefix, self.counter));
+        self.names.insert(class_expression.clone(), class.clone());
+        self.result
+            .fresh
+            .insert(class.clone(), class_expression.clone());
+        class
+    }
+
+    /// Get a class name `A` such that `class_expression ⊑ A`.
+    fn name_negative(&mut self, class_expression: &ClassExpression) -> Class {
+        if let ClassExpression::Class(c) = class_expression {
+            return c.clone();
+        }
+        let class = self.fresh(class_expression);
+        if self.negative.insert(class.clone()) {
+            self.normalize(class_expression, &ClassExpression::Class(class.clone()));
+        }
+        class
+    }
+
+    /// Get a class name `A` such that `A ⊑ class_expression`.
+    fn name_positive(&mut self, class_expression: &ClassExpression) -> Class {
+        if let ClassExpression::Class(c) = class_expression {
+            return c.clone();
+        }
+        let class = self.fresh(class_expression);
+        if self.positive.insert(class.clone()) {
+            self.normalize(&ClassExpression::Class(class.clone()), class_expression);
+        }
+        class
+    }
+
+    /// Normalise the axiom `sub ⊑ sup`, assuming both expressions are EL.
+    fn normalize(&mut self, sub: &ClassExpression, sup: &ClassExpression) {
+        match sub {
+            ClassExpression::Class(c) if is_nothing(&c.0) => return,
+            _ => (),
+        }
+        match sup {
+            ClassExpression::Class(c) if is_thing(&c.0) => (),
+            ClassExpression::Class(c) => self.normalize_sub(sub, c.clone()),
+            ClassExpression::ObjectIntersectionOf(ces) => {
+                ces.iter().for_each(|ce| self.normalize(sub, ce));
+            }
+            ClassExpression::ObjectSomeValuesFrom {
+                ope: ObjectPropertyExpression::ObjectProperty(property),
+                bce,
+            } => {
+                let sub = self.name_negative(sub);
+                let filler = self.name_positive(bce);
+                self.result.axioms.insert(NormalForm::Existential {
+                    sub,
+                    property: property.clone(),
+                    filler,
+                });
+            }
+            _ => unreachable!("only EL class expressions are normalised"),
+        }
+    }
+
+    /// Normalise the axiom `sub ⊑ sup` where `sup` is a class name.
+    fn normalize_sub(&mut self, sub: &ClassExpression, sup: Class) {
+        match sub {
+            ClassExpression::Class(c) => {
+                if c != &sup {
+                    self.result.axioms.insert(NormalForm::Subsumption {
+                        sub: c.clone(),
+                        sup,
+                    });
+                }
+            }
+            ClassExpression::ObjectSomeValuesFrom {
+                ope: ObjectPropertyExpression::ObjectProperty(property),
+                bce,
+            } => {
+                let filler = self.name_negative(bce);
+                self.result.axioms.insert(NormalForm::Restriction {
+                    property: property.clone(),
+                    filler,
+                    sup,
+                });
+            }
+            ClassExpression::ObjectIntersectionOf(_) => {
+                let mut atoms = conjuncts(sub)
+                    .into_iter()
+                    .map(|ce| self.name_negative(ce))
+                    .filter(|c| !is_thing(&c.0))
+                    .collect::<Vec<Class>>();
+                atoms.sort_unstable();
+                atoms.dedup();
+                match atoms.len() {
+                    0 => {
+                        let thing = self.thing();
+                        self.normalize_sub(&thing, sup);
+                    }
+                    1 => {
+                        let atom = ClassExpression::Class(atoms.pop().unwrap());
+                        self.normalize_sub(&atom, sup);
+                    }
+                    2 => {
+                        let right = atoms.pop().unwrap();
+                        let left = atoms.pop().unwrap();
+                        self.result
+                            .axioms
+                            .insert(NormalForm::Conjunction { left, right, sup });
+                    }
+                    _ => {
+                        let right = atoms.pop().unwrap();
+                        let prefix = ClassExpression::ObjectIntersectionOf(
+                            atoms.into_iter().map(ClassExpression::Class).collect(),
+                        );
+                        let left = self.name_negative(&prefix);
+                        self.result
+                            .axioms
+                            .insert(NormalForm::Conjunction { left, right, sup });
+                    }
+                }
+            }
+            _ => unreachable!("only EL class expressions are normalised"),
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for ElNormalizer {
+    fn visit_axiom(&mut self, axiom: &'ast Axiom) {
+        match axiom {
+            Axiom::SubClassOf(SubClassOf { sub, sup }) => {
+                if is_el(sub) && is_el(sup) {
+                    self.normalize(sub, sup);
+                } else {
+                    self.result.unsupported.push(axiom.clone());
+                }
+            }
+            Axiom::EquivalentClasses(EquivalentClasses(ces)) => {
+                if ces.iter().all(is_el) {
+                    for ce in ces.iter().skip(1) {
+                        self.normalize(&ces[0], ce);
+                        self.normalize(ce, &ces[0]);
+                    }
+                } else {
+                    self.result.unsupported.push(axiom.clone());
+                }
+            }
+            Axiom::DisjointClasses(DisjointClasses(ces)) => {
+                if ces.iter().all(is_el) {
+                    let nothing = self.nothing();
+                    for pair in pairs(ces) {
+                        let both = ClassExpression::ObjectIntersectionOf(pair);
+                        self.normalize(&both, &nothing);
+                    }
+                } else {
+                    self.result.unsupported.push(axiom.clone());
+                }
+            }
+            Axiom::ObjectPropertyDomain(ObjectPropertyDomain { ope, ce }) => match ope {
+                ObjectPropertyExpression::ObjectProperty(_) if is_el(ce) => {
+                    let some = ClassExpression::ObjectSomeValuesFrom {
+                        ope: ope.clone(),
+                        bce: Box::new(self.thing()),
+                    };
+                    self.normalize(&some, ce);
+                }
+                _ => self.result.unsupported.push(axiom.clone()),
+            },
+            Axiom::DisjointUnion(_) | Axiom::HasKey(_) | Axiom::ObjectPropertyRange(_) => {
+                self.result.unsupported.push(axiom.clone());
+            }
+            _ => (),
+        }
+    }
+}
+
+/// Normalise the EL class axioms of an ontology.
+pub fn normalize<'a, I>(axioms: I) -> ElNormalForm
+where
+    I: IntoIterator<Item = &'a AnnotatedAxiom>,
+{
+    let mut normalizer = ElNormalizer::new();
+    for aa in axioms.into_iter() {
+        normalizer.visit_axiom(&aa.axiom);
+    }
+    normalizer.into_normal_form()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::*;
+
+    fn normalize_axioms(axioms: &[Axiom]) -> ElNormalForm {
+        normalize(&ontology(axioms.iter().cloned()))
+    }
+
+    /// Get the axioms of a normal form with the fresh names expanded.
+    fn expanded(normal_form: &ElNormalForm) -> BTreeSet<Axiom> {
+        normal_form
+            .axioms()
+            .iter()
+            .map(|nf| match Axiom::from(nf) {
+                Axiom::SubClassOf(SubClassOf { mut sub, mut sup }) => {
+                    normal_form.expand(&mut sub);
+                    normal_form.expand(&mut sup);
+                    sub_class_of(sub, sup)
+                }
+                _ => unreachable!(),
+            })
+            .collect()
+    }
+
+    fn non_el_axioms() -> Vec<Axiom> {
+        vec![
+            sub_class_of(
+                class("A"),
+                ClassExpression::ObjectUnionOf(vec![class("B"), class("C")]),
+            ),
+            sub_class_of(
+                class("A"),
+                some(
+                    "r",
+                    ClassExpression::ObjectComplementOf(Box::new(class("B"))),
+                ),
+            ),
+            sub_class_of(
+                ClassExpression::ObjectAllValuesFrom {
+                    ope: op("r"),
+                    bce: Box::new(class("B")),
+                },
+                class("A"),
+            ),
+            sub_class_of(
+                ClassExpression::ObjectSomeValuesFrom {
+                    ope: inverse_of("r"),
+                    bce: Box::new(class("B")),
+                },
+                class("A"),
+            ),
+            EquivalentClasses(vec![
+                class("A"),
+                ClassExpression::ObjectIntersectionOf(vec![
+                    class("B"),
+                    ClassExpression::ObjectMinCardinality {
+                        n: 2,
+                        ope: op("r"),
+                        bce: Box::new(class("C")),
+                    },
+                ]),
+            ])
+            .into(),
+            DisjointClasses(vec![class("A"), ClassExpression::ObjectHasSelf(op("r"))]).into(),
+            ObjectPropertyDomain {
+                ope: inverse_of("r"),
+                ce: class("A"),
+            }
+            .into(),
+            ObjectPropertyRange {
+                ope: op("r"),
+                ce: class("A"),
+            }
+            .into(),
+            DisjointUnion(named_class("A"), vec![class("B"), class("C")]).into(),
+        ]
+    }
+
+    #[test]
+    fn non_el_axioms_are_unsupported() {
+        let axioms = non_el_axioms();
+        let normal_form = normalize_axioms(&axioms);
+        assert!(normal_form.axioms().is_empty());
+        assert_eq!(normal_form.fresh_classes().count(), 0);
+
+        let unsupported: BTreeSet<&Axiom> = normal_form.unsupported().iter().collect();
+        assert_eq!(unsupported, axioms.iter().collect());
+    }
+
+    #[test]
+    fn mixed_input() {
+        let el = sub_class_of(class("A"), some("r", class("B")));
+        let mut axioms = non_el_axioms();
+        axioms.push(el.clone());
+        axioms.push(
+            SubObjectPropertyOf {
+                sub: SubObjectPropertyExpression::ObjectPropertyExpression(op("r")),
+                sup: op("s"),
+            }
+            .into(),
+        );
+
+        let normal_form = normalize_axioms(&axioms);
+        assert_eq!(normal_form.unsupported().len(), non_el_axioms().len());
+        assert!(!normal_form.unsupported().contains(&el));
+        assert_eq!(expanded(&normal_form), vec![el].into_iter().collect());
+    }
+
+    #[test]
+    fn roundtrip_el_axioms() {
+        let existential = sub_class_of(
+            class("A"),
+            some(
+                "r",
+                ClassExpression::ObjectIntersectionOf(vec![class("B"), class("C")]),
+            ),
+        );
+        let definition =
+            ClassExpression::ObjectIntersectionOf(vec![class("D"), some("s", class("E"))]);
+        let equivalence = EquivalentClasses(vec![class("F"), definition.clone()]).into();
+
+        let normal_form = normalize_axioms(&[existential.clone(), equivalence]);
+        assert!(normal_form.unsupported().is_empty());
+        let expanded = expanded(&normal_form);
+        assert!(expanded.contains(&existential));
+        assert!(expanded.contains(&sub_class_of(definition, class("F"))));
+        assert!(expanded.contains(&sub_class_of(class("F"), some("s", class("E")))));
+        assert!(expanded.contains(&sub_class_of(class("F"), class("D"))));
+    }
+
+    #[test]
+    fn disjoint_classes() {
+        let normal_form =
+            normalize_axioms(&[DisjointClasses(vec![class("A"), class("B"), class("C")]).into()]);
+        assert_eq!(normal_form.axioms().len(), 3);
+        assert!(normal_form.axioms().iter().all(|nf| matches!(
+            nf,
+            NormalForm::Conjunction { sup, .. } if is_nothing(&sup.0)
+        )));
+    }
+}
@@ -0,0 +1,485 @@
+//! Merge of an entity into another one.
+//!
+//! The [`EntityMerger`] replaces every occurrence of a *source* IRI with a
+//! *target* IRI, in the logical axioms as well as in the annotations and
+//! annotation assertions of an ontology. Axioms made trivial by the merge
+//! are dropped, namely subsumptions of an entity by itself and equivalence
+//! or sameness axioms with a single distinct operand, and duplicated
+//! operands are removed from the other equivalence and sameness axioms.
+//! When the merge yields an axiom that is already in the ontology with
+//! different annotations, a single axiom with the union of annotations is
+//! kept.
+//!
+//! The merger can optionally record a stub for the source entity, made of
+//! its original declarations, an `owl:deprecated` annotation and a
+//! [`REPLACED_BY`](crate::compat::REPLACED_BY) annotation pointing to the
+//! target entity, as expected by the OBO Foundry guidelines on obsoletion.
+//!
+//! # Example
+//!
+//! ```rust
+//! # extern crate horned_owl;
+//! # extern crate horned_visit;
+//! use std::collections::BTreeSet;
+//! use horned_owl::model::*;
+//! use horned_owl::ontology::set::SetOntology;
+//!
+//! let b = Build::new();
+//! let comment = |text: &str| Annotation {
+//!     ap: b.annotation_property("http://www.w3.org/2000/01/rdf-schema#comment"),
+//!     av: AnnotationValue::Literal(Literal::Simple { literal: text.into() }),
+//! };
+//!
+//! let mut ontology = SetOntology::new();
+//! ontology.insert(SubClassOf {
+//!     sub: b.class("http://example.com/B").into(),
+//!     sup: b.class("http://example.com/A").into(),
+//! });
+//! ontology.insert(AnnotatedAxiom::new(
+//!     SubClassOf {
+//!         sub: b.class("http://example.com/A").into(),
+//!         sup: b.class("http://example.com/C").into(),
+//!     },
+//!     vec![comment("from A")].into_iter().collect::<BTreeSet<_>>(),
+//! ));
+//! ontology.insert(AnnotatedAxiom::new(
+//!     SubClassOf {
+//!         sub: b.class("http://example.com/B").into(),
+//!         sup: b.class("http://example.com/C").into(),
+//!     },
+//!     vec![comment("from B")].into_iter().collect::<BTreeSet<_>>(),
+//! ));
+//!
+//! horned_visit::transform::merge::merge_entities(
+//!     &mut ontology,
+//!     b.iri("http://example.com/B"),
+//!     b.iri("http://example.com/A"),
+//! );
+//!
+//! let axioms: Vec<&AnnotatedAxiom> = ontology.iter().collect();
+//! assert_eq!(axioms.len(), 1);
+//! assert_eq!(axioms[0].ann.len(), 2);
+//! ```
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::mem;
+
+use horned_owl::model::*;
+use horned_owl::vocab::AnnotationBuiltIn;
+use horned_owl::vocab::WithIRI;
+
+use crate::compat::REPLACED_BY;
+use crate::sink::AxiomSink;
+use crate::VisitMut;
+
+/// The IRI of the `xsd:boolean` datatype, missing from `horned_owl::vocab`.
+const BOOLEAN: &str = "http://www.w3.org/2001/XMLSchema#boolean";
+
+/// A visitor replacing an IRI with another one.
+#[derive(Clone, Debug)]
+struct IriReplacer<'a> {
+    source: &'a IRI,
+    target: &'a IRI,
+}
+
+impl VisitMut for IriReplacer<'_> {
+    fn visit_annotations(&mut self, annotations: &mut BTreeSet<Annotation>) {
+        // annotations cannot be edited in place without breaking the set order
+        *annotations = mem::take(annotations)
+            .into_iter()
+            .map(|mut annotation| {
+                self.visit_annotation(&mut annotation);
+                annotation
+            })
+            .collect();
+    }
+
+    fn visit_iri(&mut self, iri: &mut IRI) {
+        if iri == self.source {
+            *iri = self.target.clone();
+        }
+    }
+}
+
+/// A configurable transform merging an entity into another one.
+#[derive(Clone, Debug)]
+pub struct EntityMerger {
+    source: IRI,
+    target: IRI,
+    deprecate: bool,
+}
+
+impl EntityMerger {
+    /// Create a new merger replacing `source` with `target`.
+    pub fn new(source: IRI, target: IRI) -> Self {
+        Self {
+            source,
+            target,
+            deprecate: false,
+        }
+    }
+
+    /// Set whether to record a deprecated stub for the source entity.
+    pub fn deprecate(mut self, deprecate: bool) -> Self {
+        self.deprecate = deprecate;
+        self
+    }
+
+    /// Merge the source entity into the target entity in an ontology.
+    pub fn merge<O>(&self, ontology: &mut O)
+    where
+        O: MutableOntology,
+        for<'a> &'a O: IntoIterator<Item = &'a AnnotatedAxiom>,
+    {
+        let mut sink = AxiomSink::new();
+        let mut replacer = IriReplacer {
+            source: &self.source,
+            target: &self.target,
+        };
+
+        // rewrite the axioms referencing the source entity
+        let mut merged = BTreeMap::<Axiom, BTreeSet<Annotation>>::new();
+        let mut rewritten_axioms = BTreeSet::new();
+        for aa in ontology.into_iter() {
+            let mut rewritten = aa.clone();
+            replacer.visit_annotated_axiom(&mut rewritten);
+            if &rewritten == aa {
+                continue;
+            }
+            rewritten_axioms.insert(aa);
+            sink.remove(aa.clone());
+            if self.deprecate && is_declaration(&aa.axiom) {
+                sink.insert(aa.clone());
+            }
+            if let Some(axiom) = simplify(rewritten.axiom) {
+                merged.entry(axiom).or_default().extend(rewritten.ann);
+            }
+        }
+
+        // merge the annotations of the axioms made identical by the merge
+        for aa in ontology.into_iter() {
+            if rewritten_axioms.contains(aa) {
+                continue;
+            }
+            if let Some(ann) = merged.get_mut(&aa.axiom) {
+                sink.remove(aa.clone());
+                ann.extend(aa.ann.iter().cloned());
+            }
+        }
+        for (axiom, ann) in merged {
+            sink.insert(AnnotatedAxiom::new(axiom, ann));
+        }
+
+        // record the deprecated stub of the source entity
+        if self.deprecate {
+            let build = Build::new();
+            sink.insert(AnnotationAssertion {
+                subject: AnnotationSubject::IRI(self.source.clone()),
+                ann: Annotation {
+                    ap: build.annotation_property(AnnotationBuiltIn::DEPRECATED.iri_str()),
+                    av: AnnotationValue::Literal(Literal::Datatype {
+                        literal: "true".into(),
+                        datatype_iri: build.iri(BOOLEAN),
+                    }),
+                },
+            });
+            sink.insert(AnnotationAssertion {
+                subject: AnnotationSubject::IRI(self.source.clone()),
+                ann: Annotation {
+                    ap: build.annotation_property(REPLACED_BY),
+                    av: AnnotationValue::IRI(self.target.clone()),
+                },
+            });
+        }
+
+        sink.apply(ontology);
+    }
+}
+
+/// Check whether an axiom is a declaration.
+fn is_declaration(axiom: &Axiom) -> bool {
+    matches!(
+        axiom,
+        Axiom::DeclareClass(_)
+            | Axiom::DeclareObjectProperty(_)
+            | Axiom::DeclareAnnotationProperty(_)
+            | Axiom::DeclareDataProperty(_)
+            | Axiom::DeclareNamedIndividual(_)
+            | Axiom::DeclareDatatype(_)
+    )
+}
+
+/// Remove the duplicate operands of an axiom, keeping the first occurrences.
+fn dedup<T: Clone + Ord>(operands: Vec<T>) -> Option<Vec<T>> {
+    let mut seen = BTreeSet::new();
+    let operands: Vec<T> = operands
+        .into_iter()
+        .filter(|operand| seen.insert(operand.clone()))
+        .collect();
+    if operands.len() >= 2 {
+        Some(operands)
+    } else {
+        None
+    }
+}
+
+/// Simplify an axiom rewritten by a merge, or drop it if it became trivial.
+fn simplify(axiom: Axiom) -> Option<Axiom> {
+    match axiom {
+        Axiom::SubClassOf(SubClassOf { sub, sup }) if sub == sup => None,
+        Axiom::SubObjectPropertyOf(SubObjectPropertyOf {
+            sub: SubObjectPropertyExpression::ObjectPropertyExpression(sub),
+            sup,
+        }) if sub == sup => None,
+        Axiom::SubDataPropertyOf(SubDataPropertyOf { sub, sup }) if sub == sup => None,
+        Axiom::SubAnnotationPropertyOf(SubAnnotationPropertyOf { sub, sup }) if sub == sup => None,
+        Axiom::EquivalentClasses(EquivalentClasses(ces)) => {
+            dedup(ces).map(|ces| EquivalentClasses(ces).into())
+        }
+        Axiom::EquivalentObjectProperties(EquivalentObjectProperties(opes)) => {
+            dedup(opes).map(|opes| EquivalentObjectProperties(opes).into())
+        }
+        Axiom::EquivalentDataProperties(EquivalentDataProperties(dps)) => {
+            dedup(dps).map(|dps| EquivalentDataProperties(dps).into())
+        }
+        Axiom::SameIndividual(SameIndividual(individuals)) => {
+            dedup(individuals).map(|individuals| SameIndividual(individuals).into())
+        }
+        other => Some(other),
+    }
+}
+
+/// Merge an entity into another one in an ontology.
+///
+/// No stub is recorded for the source entity, use an [`EntityMerger`] to
+/// configure this behaviour.
+pub fn merge_entities<O>(ontology: &mut O, source: IRI, target: IRI)
+where
+    O: MutableOntology,
+    for<'a> &'a O: IntoIterator<Item = &'a AnnotatedAxiom>,
+{
+    EntityMerger::new(source, target).merge(ontology)
+}
+
+#[cfg(test)]
+mod tests {
+    use horned_owl::ontology::set::SetOntology;
+    use horned_owl::vocab::AnnotationBuiltIn;
+    use horned_owl::vocab::WithIRI;
+
+    use super::*;
+
+    fn axioms(ontology: &SetOntology) -> BTreeSet<AnnotatedAxiom> {
+        ontology.iter().cloned().collect()
+    }
+
+    #[test]
+    fn rewrite_axiom_annotations() {
+        let b = Build::new();
+        let see_also = b.annotation_property(AnnotationBuiltIn::SEEALSO.iri_str());
+        let see = |iri: &str| Annotation {
+            ap: see_also.clone(),
+            av: AnnotationValue::IRI(b.iri(iri)),
+        };
+
+        let mut ontology = SetOntology::new();
+        ontology.insert(AnnotatedAxiom::new(
+            SubClassOf {
+                sub: b.class("http://example.com/X").into(),
+                sup: b.class("http://example.com/B").into(),
+            },
+            vec![see("http://example.com/B")].into_iter().collect(),
+        ));
+        merge_entities(
+            &mut ontology,
+            b.iri("http://example.com/B"),
+            b.iri("http://example.com/A"),
+        );
+
+        let expected = AnnotatedAxiom::new(
+            SubClassOf {
+                sub: b.class("http://example.com/X").into(),
+                sup: b.class("http://example.com/A").into(),
+            },
+            vec![see("http://example.com/A")].into_iter().collect(),
+        );
+        assert_eq!(axioms(&ontology), vec![expected].into_iter().collect());
+    }
+
+    #[test]
+    fn rewrite_annotation_property() {
+        let b = Build::new();
+        let mut ontology = SetOntology::new();
+        ontology.insert(AnnotatedAxiom::new(
+            DeclareClass(b.class("http://example.com/X")),
+            vec![Annotation {
+                ap: b.annotation_property("http://example.com/B"),
+                av: AnnotationValue::IRI(b.iri("http://example.com/Y")),
+            }]
+            .into_iter()
+            .collect(),
+        ));
+        merge_entities(
+            &mut ontology,
+            b.iri("http://example.com/B"),
+            b.iri("http://example.com/A"),
+        );
+
+        let expected = AnnotatedAxiom::new(
+            DeclareClass(b.class("http://example.com/X")),
+            vec![Annotation {
+                ap: b.annotation_property("http://example.com/A"),
+                av: AnnotationValue::IRI(b.iri("http://example.com/Y")),
+            }]
+            .into_iter()
+            .collect(),
+        );
+        assert_eq!(axioms(&ontology), vec![expected].into_iter().collect());
+    }
+
+    #[test]
+    fn rewrite_annotations_only() {
+        let b = Build::new();
+        let see_also = b.annotation_property(AnnotationBuiltIn::SEEALSO.iri_str());
+        let see = |iri: &str| Annotation {
+            ap: see_also.clone(),
+            av: AnnotationValue::IRI(b.iri(iri)),
+        };
+        let axiom = SubClassOf {
+            sub: b.class("http://example.com/X").into(),
+            sup: b.class("http://example.com/A").into(),
+        };
+
+        let mut ontology = SetOntology::new();
+        ontology.insert(AnnotatedAxiom::new(
+            axiom.clone(),
+            vec![see("http://example.com/B")].into_iter().collect(),
+        ));
+        merge_entities(
+            &mut ontology,
+            b.iri("http://example.com/B"),
+            b.iri("http://example.com/A"),
+        );
+
+        let expected = AnnotatedAxiom::new(
+            axiom,
+            vec![see("http://example.com/A")].into_iter().collect(),
+        );
+        assert_eq!(axioms(&ontology), vec![expected].into_iter().collect());
+    }
+
+    #[test]
+    fn merge_annotations_of_identical_axioms() {
+        let b = Build::new();
+        let see_also = b.annotation_property(AnnotationBuiltIn::SEEALSO.iri_str());
+        let see = |iri: &str| Annotation {
+            ap: see_also.clone(),
+            av: AnnotationValue::IRI(b.iri(iri)),
+        };
+        let axiom = |sup: &str| SubClassOf {
+            sub: b.class("http://example.com/X").into(),
+            sup: b.class(sup).into(),
+        };
+
+        let mut ontology = SetOntology::new();
+        ontology.insert(AnnotatedAxiom::new(
+            axiom("http://example.com/A"),
+            vec![see("http://example.com/Y")].into_iter().collect(),
+        ));
+        ontology.insert(AnnotatedAxiom::new(
+            axiom("http://example.com/B"),
+            vec![see("http://example.com/B")].into_iter().collect(),
+        ));
+        merge_entities(
+            &mut ontology,
+            b.iri("http://example.com/B"),
+            b.iri("http://example.com/A"),
+        );
+
+        let expected = AnnotatedAxiom::new(
+            axiom("http://example.com/A"),
+            vec![see("http://example.com/A"), see("http://example.com/Y")]
+                .into_iter()
+                .collect(),
+        );
+        assert_eq!(axioms(&ontology), vec![expected].into_iter().collect());
+    }
+
+    #[test]
+    fn drop_annotated_trivial_axioms() {
+        let b = Build::new();
+        let comment = Annotation {
+            ap: b.annotation_property(AnnotationBuiltIn::COMMENT.iri_str()),
+            av: AnnotationValue::Literal(Literal::Simple {
+                literal: "B is an A".into(),
+            }),
+        };
+
+        let mut ontology = SetOntology::new();
+        ontology.insert(AnnotatedAxiom::new(
+            SubClassOf {
+                sub: b.class("http://example.com/B").into(),
+                sup: b.class("http://example.com/A").into(),
+            },
+            vec![comment.clone()].into_iter().collect(),
+        ));
+        ontology.insert(AnnotatedAxiom::new(
+            EquivalentClasses(vec![
+                b.class("http://example.com/A").into(),
+                b.class("http://example.com/B").into(),
+            ]),
+            vec![comment].into_iter().collect(),
+        ));
+        merge_entities(
+            &mut ontology,
+            b.iri("http://example.com/B"),
+            b.iri("http://example.com/A"),
+        );
+
+        assert!(axioms(&ontology).is_empty());
+    }
+
+    #[test]
+    fn deprecate_source() {
+        let b = Build::new();
+        let mut ontology = SetOntology::new();
+        ontology.declare(b.class("http://example.com/B"));
+        ontology.insert(SubClassOf {
+            sub: b.class("http://example.com/B").into(),
+            sup: b.class("http://example.com/C").into(),
+        });
+        EntityMerger::new(b.iri("http://example.com/B"), b.iri("http://example.com/A"))
+            .deprecate(true)
+            .merge(&mut ontology);
+
+        let expected: BTreeSet<AnnotatedAxiom> = vec![
+            AnnotatedAxiom::from(DeclareClass(b.class("http://example.com/A"))),
+            AnnotatedAxiom::from(DeclareClass(b.class("http://example.com/B"))),
+            AnnotatedAxiom::from(SubClassOf {
+                sub: b.class("http://example.com/A").into(),
+                sup: b.class("http://example.com/C").into(),
+            }),
+            AnnotatedAxiom::from(AnnotationAssertion {
+                subject: AnnotationSubject::IRI(b.iri("http://example.com/B")),
+                ann: Annotation {
+                    ap: b.annotation_property(AnnotationBuiltIn::DEPRECATED.iri_str()),
+                    av: AnnotationValue::Literal(Literal::Datatype {
+                        literal: "true".into(),
+                        datatype_iri: b.iri(BOOLEAN),
+                    }),
+                },
+            }),
+            AnnotatedAxiom::from(AnnotationAssertion {
+                subject: AnnotationSubject::IRI(b.iri("http://example.com/B")),
+                ann: Annotation {
+                    ap: b.annotation_property(REPLACED_BY),
+                    av: AnnotationValue::IRI(b.iri("http://example.com/A")),
+                },
+            }),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(axioms(&ontology), expected);
+    }
+}
@@ -0,0 +1,21 @@
+//! Transformations of ontology elements built on the [`VisitMut`](crate::VisitMut) trait.
+
+pub mod canonical;
+pub mod desugar;
+pub mod el_normal_form;
+pub mod merge;
+pub mod reduce;
+pub mod remove;
+pub mod simplify;
+pub mod split;
+
+/// Get all the pairs of distinct operands of an n-ary construct.
+pub(crate) fn pairs<T: Clone>(operands: &[T]) -> Vec<Vec<T>> {
+    let mut pairs = Vec::new();
+    for (i, x) in operands.iter().enumerate() {
+        for y in operands[i + 1..].iter() {
+            pairs.push(vec![x.clone(), y.clone()]);
+        }
+    }
+    pairs
+}
@@ -0,0 +1,355 @@
+//! Removal of redundant subsumptions between named classes.
+//!
+//! An axiom `SubClassOf(A C)` between two named classes is *redundant*
+//! when it is implied by a chain `A ⊑ B ⊑ ... ⊑ C` of the told class
+//! hierarchy (see [`hierarchy`](crate::hierarchy)). The [`Reducer`] reports
+//! such axioms together with the chain implying them, and can remove them
+//! from an ontology, effectively computing the transitive reduction of the
+//! asserted hierarchy.
+//!
+//! Redundant axioms are checked one at a time, and an axiom is only
+//! reported if it is still implied once the previously reported axioms have
+//! been removed, so that removing all of them never changes the told
+//! hierarchy, even in the presence of equivalent classes.
+//!
+//! # Example
+//!
+//! ```rust
+//! # extern crate horned_owl;
+//! # extern crate horned_visit;
+//! use horned_owl::model::*;
+//! use horned_owl::ontology::set::SetOntology;
+//!
+//! let b = Build::new();
+//! let mut ontology = SetOntology::new();
+//! ontology.insert(SubClassOf {
+//!     sub: b.class("http://example.com/A").into(),
+//!     sup: b.class("http://example.com/B").into(),
+//! });
+//! ontology.insert(SubClassOf {
+//!     sub: b.class("http://example.com/B").into(),
+//!     sup: b.class("http://example.com/C").into(),
+//! });
+//! ontology.insert(SubClassOf {
+//!     sub: b.class("http://example.com/A").into(),
+//!     sup: b.class("http://example.com/C").into(),
+//! });
+//!
+//! let redundancies = horned_visit::transform::reduce::reduce_ontology(&mut ontology);
+//! assert_eq!(redundancies.len(), 1);
+//! assert_eq!(redundancies[0].path().len(), 3);
+//! assert_eq!(ontology.iter().count(), 2);
+//! ```
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::VecDeque;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::fmt::Result as FmtResult;
+
+use horned_owl::model::*;
+
+use crate::display::Functional;
+use crate::hierarchy::Hierarchy;
+use crate::hierarchy::HierarchyBuilder;
+use crate::sink::AxiomSink;
+use crate::Visit;
+
+/// A redundant subsumption axiom.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Redundancy {
+    axiom: AnnotatedAxiom,
+    path: Vec<Class>,
+}
+
+impl Redundancy {
+    /// Get the redundant axiom.
+    pub fn axiom(&self) -> &AnnotatedAxiom {
+        &self.axiom
+    }
+
+    /// Get the chain of classes implying the axiom.
+    ///
+    /// The chain starts with the subclass and ends with the superclass of
+    /// the axiom. It only contains the subclass if the axiom is a
+    /// tautology such as `SubClassOf(A A)`.
+    pub fn path(&self) -> &[Class] {
+        &self.path
+    }
+}
+
+impl Display for Redundancy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{} implied by ", Functional(&self.axiom))?;
+        for (i, class) in self.path.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ⊑ ")?;
+            }
+            write!(f, "{}", Functional(class))?;
+        }
+        Ok(())
+    }
+}
+
+/// A configurable transform removing redundant subsumptions.
+#[derive(Clone, Debug)]
+pub struct Reducer {
+    keep_annotated: bool,
+}
+
+impl Default for Reducer {
+    fn default() -> Self {
+        Self {
+            keep_annotated: true,
+        }
+    }
+}
+
+impl Reducer {
+    /// Create a new reducer, keeping redundant axioms with annotations.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set whether redundant axioms with annotations are kept.
+    pub fn keep_annotated(mut self, keep_annotated: bool) -> Self {
+        self.keep_annotated = keep_annotated;
+        self
+    }
+
+    /// Find the redundant subsumptions among a set of axioms.
+    pub fn redundancies<'a, I>(&self, axioms: I) -> Vec<Redundancy>
+    where
+        I: IntoIterator<Item = &'a AnnotatedAxiom>,
+    {
+        let mut builder = HierarchyBuilder::new();
+        let mut candidates = BTreeMap::<(&Class, &Class), Vec<&AnnotatedAxiom>>::new();
+        for aa in axioms.into_iter() {
+            match &aa.axiom {
+                Axiom::SubClassOf(SubClassOf {
+                    sub: ClassExpression::Class(sub),
+                    sup: ClassExpression::Class(sup),
+                }) => {
+                    candidates.entry((sub, sup)).or_default().push(aa);
+                }
+                _ => builder.visit_axiom(&aa.axiom),
+            }
+        }
+
+        let base = builder.into_hierarchies().classes;
+        let mut hierarchy = base.clone();
+        for (sub, sup) in candidates.keys() {
+            hierarchy.add_edge((*sub).clone(), (*sup).clone());
+        }
+
+        let mut redundancies = Vec::new();
+        for ((sub, sup), versions) in candidates.into_iter() {
+            let path = if sub == sup {
+                Some(vec![sub.clone()])
+            } else if base.contains_edge(sub, sup) {
+                Some(vec![sub.clone(), sup.clone()])
+            } else {
+                hierarchy.remove_edge(sub, sup);
+                path(&hierarchy, sub, sup)
+            };
+            let mut kept = false;
+            match path {
+                Some(path) => {
+                    for aa in versions {
+                        if self.keep_annotated && !aa.ann.is_empty() {
+                            kept = true;
+                        } else {
+                            redundancies.push(Redundancy {
+                                axiom: aa.clone(),
+                                path: path.clone(),
+                            });
+                        }
+                    }
+                }
+                None => kept = true,
+            }
+            if kept {
+                hierarchy.add_edge(sub.clone(), sup.clone());
+            }
+        }
+
+        redundancies
+    }
+
+    /// Remove the redundant subsumptions of an ontology.
+    pub fn reduce<O>(&self, ontology: &mut O) -> Vec<Redundancy>
+    where
+        O: MutableOntology,
+        for<'a> &'a O: IntoIterator<Item = &'a AnnotatedAxiom>,
+    {
+        let redundancies = self.redundancies(&*ontology);
+        let mut sink = AxiomSink::new();
+        for redundancy in redundancies.iter() {
+            sink.remove(redundancy.axiom.clone());
+        }
+        sink.apply(ontology);
+        redundancies
+    }
+}
+
+/// Find a path from a class to one of its ancestors in a hierarchy.
+fn path(hierarchy: &Hierarchy<Class>, sub: &Class, sup: &Class) -> Option<Vec<Class>> {
+    let mut previous = BTreeMap::<&Class, &Class>::new();
+    let mut seen = BTreeSet::new();
+    let mut queue = VecDeque::new();
+    seen.insert(sub);
+    queue.push_back(sub);
+    while let Some(class) = queue.pop_front() {
+        if class == sup {
+            let mut path = vec![class.clone()];
+            let mut current = class;
+            while let Some(&before) = previous.get(current) {
+                path.push(before.clone());
+                current = before;
+            }
+            path.reverse();
+            return Some(path);
+        }
+        for parent in hierarchy.parents(class) {
+            if seen.insert(parent) {
+                previous.insert(parent, class);
+                queue.push_back(parent);
+            }
+        }
+    }
+    None
+}
+
+/// Remove the redundant subsumptions of an ontology.
+///
+/// Redundant axioms with annotations are kept, use a [`Reducer`] to
+/// configure this behaviour.
+pub fn reduce_ontology<O>(ontology: &mut O) -> Vec<Redundancy>
+where
+    O: MutableOntology,
+    for<'a> &'a O: IntoIterator<Item = &'a AnnotatedAxiom>,
+{
+    Reducer::new().reduce(ontology)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::fixtures::*;
+
+    fn annotated(axiom: Axiom) -> AnnotatedAxiom {
+        let annotation = Annotation {
+            ap: AnnotationProperty(iri("p")),
+            av: AnnotationValue::IRI(iri("v")),
+        };
+        AnnotatedAxiom::new(axiom, vec![annotation].into_iter().collect())
+    }
+
+    #[test]
+    fn keep_annotated() {
+        let redundant = annotated(sub_class_of(class("A"), class("C")));
+        let mut o = ontology(vec![
+            sub_class_of(class("A"), class("B")),
+            sub_class_of(class("B"), class("C")),
+        ]);
+        o.insert(redundant.clone());
+
+        let mut kept = o.clone();
+        assert!(Reducer::new().reduce(&mut kept).is_empty());
+        assert_eq!(kept, o);
+
+        let mut removed = o.clone();
+        let redundancies = Reducer::new().keep_annotated(false).reduce(&mut removed);
+        assert_eq!(redundancies.len(), 1);
+        assert_eq!(redundancies[0].axiom(), &redundant);
+        assert_eq!(
+            redundancies[0].path(),
+            &[named_class("A"), named_class("B"), named_class("C")]
+        );
+        assert!(!removed.iter().any(|aa| aa == &redundant));
+    }
+
+    #[test]
+    fn keep_annotated_version_only() {
+        let axiom = sub_class_of(class("A"), class("C"));
+        let mut o = ontology(vec![
+            sub_class_of(class("A"), class("B")),
+            sub_class_of(class("B"), class("C")),
+            axiom.clone(),
+        ]);
+        o.insert(annotated(axiom.clone()));
+        let redundancies = reduce_ontology(&mut o);
+        assert_eq!(redundancies.len(), 1);
+        assert_eq!(
+            redundancies[0].axiom(),
+            &AnnotatedAxiom::from(axiom.clone())
+        );
+        assert!(o.iter().any(|aa| aa == &annotated(axiom.clone())));
+    }
+
+    #[test]
+    fn tautology() {
+        let mut o = ontology(vec![sub_class_of(class("A"), class("A"))]);
+        let redundancies = reduce_ontology(&mut o);
+        assert_eq!(redundancies.len(), 1);
+        assert_eq!(redundancies[0].path(), &[named_class("A")]);
+        assert_eq!(o.iter().count(), 0);
+    }
+
+    #[test]
+    fn implied_by_other_axioms() {
+        let mut o = ontology(vec![
+            sub_class_of(
+                class("A"),
+                ClassExpression::ObjectIntersectionOf(vec![class("B"), class("C")]),
+            ),
+            sub_class_of(class("A"), class("B")),
+        ]);
+        let redundancies = reduce_ontology(&mut o);
+        assert_eq!(redundancies.len(), 1);
+        assert_eq!(
+            redundancies[0].path(),
+            &[named_class("A"), named_class("B")]
+        );
+        assert_eq!(o.iter().count(), 1);
+    }
+
+    #[test]
+    fn cycles_are_preserved() {
+        // `A ⊑ B ⊑ C ⊑ A` makes the three classes equivalent: only the
+        // shortcut `A ⊑ C` can be removed without changing the hierarchy
+        let mut o = ontology(vec![
+            sub_class_of(class("A"), class("B")),
+            sub_class_of(class("B"), class("C")),
+            sub_class_of(class("C"), class("A")),
+            sub_class_of(class("A"), class("C")),
+        ]);
+        let redundancies = reduce_ontology(&mut o);
+        assert_eq!(redundancies.len(), 1);
+        assert_eq!(
+            redundancies[0].axiom(),
+            &AnnotatedAxiom::from(sub_class_of(class("A"), class("C")))
+        );
+        assert_eq!(
+            o,
+            ontology(vec![
+                sub_class_of(class("A"), class("B")),
+                sub_class_of(class("B"), class("C")),
+                sub_class_of(class("C"), class("A")),
+            ])
+        );
+    }
+
+    #[test]
+    fn anonymous_classes_are_ignored() {
+        let mut o = ontology(vec![
+            sub_class_of(class("A"), some("r", class("B"))),
+            sub_class_of(some("r", class("B")), class("C")),
+        ]);
+        let expected = o.clone();
+        assert!(reduce_ontology(&mut o).is_empty());
+        assert_eq!(o, expected);
+    }
+}
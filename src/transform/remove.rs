@@ -0,0 +1,308 @@
+//! Removal of entities along with the axioms referencing them.
+//!
+//! The [`EntityRemover`] removes from an ontology every axiom referencing
+//! one of the given IRIs, whatever the kind of entity it is used as,
+//! including the declarations of the entities, the axioms annotated with
+//! them, and the annotation assertions on the IRIs, in the same fashion as
+//! the `OWLEntityRemover` of the OWL API.
+//!
+//! Removing a whole n-ary axiom such as `DisjointClasses(A B C)` because
+//! it references `C` loses the information about the other operands. The
+//! remover can be configured with [`EntityRemover::prune_operands`] to
+//! only remove the operands referencing an entity from the following
+//! axioms, as long as at least two operands remain:
+//!
+//! - `EquivalentClasses` and `DisjointClasses`;
+//! - `EquivalentObjectProperties` and `DisjointObjectProperties`;
+//! - `EquivalentDataProperties` and `DisjointDataProperties`;
+//! - `SameIndividual` and `DifferentIndividuals`.
+//!
+//! # Example
+//!
+//! ```rust
+//! # extern crate horned_owl;
+//! # extern crate horned_visit;
+//! use horned_owl::model::*;
+//! use horned_owl::ontology::set::SetOntology;
+//! use horned_visit::transform::remove::EntityRemover;
+//!
+//! let b = Build::new();
+//! let mut ontology = SetOntology::new();
+//! ontology.declare(b.class("http://example.com/C"));
+//! ontology.insert(SubClassOf {
+//!     sub: b.class("http://example.com/A").into(),
+//!     sup: b.class("http://example.com/C").into(),
+//! });
+//! ontology.insert(DisjointClasses(vec![
+//!     b.class("http://example.com/A").into(),
+//!     b.class("http://example.com/B").into(),
+//!     b.class("http://example.com/C").into(),
+//! ]));
+//!
+//! let c = b.iri("http://example.com/C");
+//! let mut remover = EntityRemover::new(vec![c]).prune_operands(true);
+//! horned_visit::sink::edit(&mut ontology, &mut remover);
+//!
+//! let pruned = DisjointClasses(vec![
+//!     b.class("http://example.com/A").into(),
+//!     b.class("http://example.com/B").into(),
+//! ]);
+//! assert_eq!(ontology.iter().count(), 1);
+//! assert!(ontology.iter().any(|aa| aa.axiom == pruned.clone().into()));
+//! ```
+
+use std::collections::BTreeSet;
+
+use horned_owl::model::*;
+
+use crate::sink::edit;
+use crate::sink::AxiomSink;
+use crate::sink::Emit;
+use crate::visit;
+use crate::Visit;
+
+/// A visitor removing entities and the axioms referencing them.
+#[derive(Clone, Debug, Default)]
+pub struct EntityRemover {
+    iris: BTreeSet<IRI>,
+    prune_operands: bool,
+    referenced: bool,
+    sink: AxiomSink,
+}
+
+impl EntityRemover {
+    /// Create a new remover for the entities with the given IRIs.
+    pub fn new<I>(iris: I) -> Self
+    where
+        I: IntoIterator<Item = IRI>,
+    {
+        Self {
+            iris: iris.into_iter().collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Set whether to only remove the referencing operands of n-ary axioms.
+    pub fn prune_operands(mut self, prune_operands: bool) -> Self {
+        self.prune_operands = prune_operands;
+        self
+    }
+
+    /// Check whether a visit encounters one of the removed entities.
+    fn references<F>(&mut self, f: F) -> bool
+    where
+        F: FnOnce(&mut Self),
+    {
+        self.referenced = false;
+        f(self);
+        self.referenced
+    }
+
+    /// Get the operands not referencing the removed entities, if at least two remain.
+    fn retain<'ast, T, F>(&mut self, operands: &'ast [T], visit: F) -> Option<Vec<T>>
+    where
+        T: Clone,
+        F: Fn(&mut Self, &'ast T),
+    {
+        let retained: Vec<T> = operands
+            .iter()
+            .filter(|operand| !self.references(|s| visit(s, operand)))
+            .cloned()
+            .collect();
+        if retained.len() >= 2 {
+            Some(retained)
+        } else {
+            None
+        }
+    }
+
+    /// Remove the referencing operands of an n-ary axiom.
+    fn prune(&mut self, axiom: &Axiom) -> Option<Axiom> {
+        match axiom {
+            Axiom::EquivalentClasses(EquivalentClasses(ces)) => self
+                .retain(ces, |s, ce| s.visit_class_expression(ce))
+                .map(|ces| EquivalentClasses(ces).into()),
+            Axiom::DisjointClasses(DisjointClasses(ces)) => self
+                .retain(ces, |s, ce| s.visit_class_expression(ce))
+                .map(|ces| DisjointClasses(ces).into()),
+            Axiom::EquivalentObjectProperties(EquivalentObjectProperties(opes)) => self
+                .retain(opes, |s, ope| s.visit_object_property_expression(ope))
+                .map(|opes| EquivalentObjectProperties(opes).into()),
+            Axiom::DisjointObjectProperties(DisjointObjectProperties(opes)) => self
+                .retain(opes, |s, ope| s.visit_object_property_expression(ope))
+                .map(|opes| DisjointObjectProperties(opes).into()),
+            Axiom::EquivalentDataProperties(EquivalentDataProperties(dps)) => self
+                .retain(dps, |s, dp| s.visit_data_property(dp))
+                .map(|dps| EquivalentDataProperties(dps).into()),
+            Axiom::DisjointDataProperties(DisjointDataProperties(dps)) => self
+                .retain(dps, |s, dp| s.visit_data_property(dp))
+                .map(|dps| DisjointDataProperties(dps).into()),
+            Axiom::SameIndividual(SameIndividual(individuals)) => self
+                .retain(individuals, |s, i| s.visit_individual(i))
+                .map(|individuals| SameIndividual(individuals).into()),
+            Axiom::DifferentIndividuals(DifferentIndividuals(individuals)) => self
+                .retain(individuals, |s, i| s.visit_individual(i))
+                .map(|individuals| DifferentIndividuals(individuals).into()),
+            _ => None,
+        }
+    }
+}
+
+impl Emit for EntityRemover {
+    fn sink(&mut self) -> &mut AxiomSink {
+        &mut self.sink
+    }
+}
+
+impl<'ast> Visit<'ast> for EntityRemover {
+    fn visit_annotated_axiom(&mut self, annotated_axiom: &'ast AnnotatedAxiom) {
+        if let Axiom::AnnotationAssertion(AnnotationAssertion {
+            subject: AnnotationSubject::IRI(iri),
+            ..
+        }) = &annotated_axiom.axiom
+        {
+            if self.iris.contains(iri) {
+                self.sink.remove(annotated_axiom.clone());
+                return;
+            }
+        }
+
+        if !self.references(|s| visit::visit_annotated_axiom(s, annotated_axiom)) {
+            return;
+        }
+
+        self.sink.remove(annotated_axiom.clone());
+        if self.prune_operands && !self.references(|s| s.visit_annotations(&annotated_axiom.ann)) {
+            if let Some(pruned) = self.prune(&annotated_axiom.axiom) {
+                let ann = annotated_axiom.ann.clone();
+                self.sink.insert(AnnotatedAxiom::new(pruned, ann));
+            }
+        }
+    }
+
+    fn visit_entity(&mut self, _kind: NamedEntityKind, iri: &'ast IRI) {
+        if self.iris.contains(iri) {
+            self.referenced = true;
+        }
+    }
+}
+
+/// Remove the entities with the given IRIs from an ontology.
+///
+/// Every axiom referencing one of the entities is removed, use an
+/// [`EntityRemover`] to only remove the referencing operands of n-ary
+/// axioms.
+pub fn remove_entities<O, I>(ontology: &mut O, iris: I)
+where
+    O: MutableOntology,
+    for<'a> &'a O: IntoIterator<Item = &'a AnnotatedAxiom>,
+    I: IntoIterator<Item = IRI>,
+{
+    edit(ontology, &mut EntityRemover::new(iris));
+}
+
+#[cfg(test)]
+mod tests {
+    use horned_owl::ontology::set::SetOntology;
+    use horned_owl::vocab::AnnotationBuiltIn;
+    use horned_owl::vocab::WithIRI;
+
+    use super::*;
+    use crate::fixtures::*;
+
+    fn remove(axioms: Vec<AnnotatedAxiom>, prune_operands: bool) -> BTreeSet<AnnotatedAxiom> {
+        let mut ontology: SetOntology = axioms.into_iter().collect();
+        let c = iri("C");
+        let mut remover = EntityRemover::new(vec![c]).prune_operands(prune_operands);
+        edit(&mut ontology, &mut remover);
+        ontology.iter().cloned().collect()
+    }
+
+    #[test]
+    fn remove_without_pruning() {
+        let remaining = remove(
+            vec![
+                DisjointClasses(vec![class("A"), class("B"), class("C")]).into(),
+                DeclareClass(named_class("C")).into(),
+                sub_class_of(class("A"), class("B")).into(),
+            ],
+            false,
+        );
+        let expected = AnnotatedAxiom::from(SubClassOf {
+            sub: class("A"),
+            sup: class("B"),
+        });
+        assert_eq!(remaining, vec![expected].into_iter().collect());
+    }
+
+    #[test]
+    fn prune_operands() {
+        let remaining = remove(
+            vec![
+                EquivalentClasses(vec![class("A"), class("B"), class("C")]).into(),
+                SameIndividual(vec![individual("a"), individual("b"), individual("C")]).into(),
+            ],
+            true,
+        );
+        let expected: BTreeSet<AnnotatedAxiom> = vec![
+            AnnotatedAxiom::from(EquivalentClasses(vec![class("A"), class("B")])),
+            AnnotatedAxiom::from(SameIndividual(vec![individual("a"), individual("b")])),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(remaining, expected);
+    }
+
+    #[test]
+    fn prune_below_two_operands() {
+        let nested = ClassExpression::ObjectComplementOf(Box::new(class("C")));
+        let remaining = remove(
+            vec![
+                DisjointClasses(vec![class("A"), class("C")]).into(),
+                EquivalentClasses(vec![class("C"), nested, class("B")]).into(),
+            ],
+            true,
+        );
+        assert!(remaining.is_empty());
+    }
+
+    #[test]
+    fn annotation_reference_is_not_pruned() {
+        let annotation = Annotation {
+            ap: AnnotationProperty(iri("C")),
+            av: AnnotationValue::IRI(iri("D")),
+        };
+        let remaining = remove(
+            vec![AnnotatedAxiom::new(
+                DisjointClasses(vec![class("A"), class("B"), class("D")]),
+                vec![annotation].into_iter().collect(),
+            )],
+            true,
+        );
+        assert!(remaining.is_empty());
+    }
+
+    #[test]
+    fn annotation_assertions() {
+        let b = Build::new();
+        let label = b.annotation_property(AnnotationBuiltIn::LABEL.iri_str());
+        let on_c = AnnotatedAxiom::from(AnnotationAssertion {
+            subject: AnnotationSubject::IRI(iri("C")),
+            ann: Annotation {
+                ap: label.clone(),
+                av: AnnotationValue::Literal(Literal::Simple {
+                    literal: "C".into(),
+                }),
+            },
+        });
+        let to_c = AnnotatedAxiom::from(AnnotationAssertion {
+            subject: AnnotationSubject::IRI(iri("A")),
+            ann: Annotation {
+                ap: b.annotation_property(AnnotationBuiltIn::SEEALSO.iri_str()),
+                av: AnnotationValue::IRI(iri("C")),
+            },
+        });
+        let remaining = remove(vec![on_c, to_c.clone()], false);
+        assert_eq!(remaining, vec![to_c].into_iter().collect());
+    }
+}
@@ -0,0 +1,343 @@
+//! Simplification of redundant class expressions and data ranges.
+//!
+//! The [`Simplifier`] rewrites class expressions and data ranges bottom-up,
+//! applying the following rules until none applies:
+//!
+//! - nested `ObjectIntersectionOf` and `ObjectUnionOf` (resp. `DataIntersectionOf`
+//!   and `DataUnionOf`) are flattened;
+//! - duplicate operands are removed;
+//! - `owl:Thing` is removed from intersections, and `owl:Nothing` from unions;
+//! - intersections and unions with a single operand are replaced by their operand,
+//!   and empty intersections (resp. unions) by `owl:Thing` (resp. `owl:Nothing`);
+//! - double complements are removed;
+//! - `ObjectMinCardinality(0 ...)` and `DataMinCardinality(0 ...)` are
+//!   replaced by `owl:Thing`.
+//!
+//! # Example
+//!
+//! ```rust
+//! # extern crate horned_owl;
+//! # extern crate horned_visit;
+//! use horned_owl::model::*;
+//! use horned_visit::transform::simplify::Simplify;
+//!
+//! let b = Build::new();
+//! let a: ClassExpression = b.class("http://example.com/A").into();
+//! let thing: ClassExpression = b.class("http://www.w3.org/2002/07/owl#Thing").into();
+//!
+//! let mut ce = ClassExpression::ObjectIntersectionOf(vec![
+//!     thing,
+//!     ClassExpression::ObjectIntersectionOf(vec![a.clone(), a.clone()]),
+//! ]);
+//! ce.simplify();
+//! assert_eq!(ce, a);
+//! ```
+
+use std::mem;
+
+use horned_owl::model::*;
+use horned_owl::vocab::is_nothing;
+use horned_owl::vocab::is_thing;
+use horned_owl::vocab::WithIRI;
+use horned_owl::vocab::OWL;
+
+use crate::sink::AxiomSink;
+use crate::visit_mut;
+use crate::VisitMut;
+
+/// Remove duplicate elements from a vector, keeping the first occurrences.
+fn dedup<T: PartialEq>(operands: &mut Vec<T>) {
+    let mut i = 0;
+    while i < operands.len() {
+        if operands[..i].contains(&operands[i]) {
+            operands.remove(i);
+        } else {
+            i += 1;
+        }
+    }
+}
+
+/// A visitor simplifying the visited class expressions and data ranges.
+#[derive(Debug, Default)]
+pub struct Simplifier {
+    build: Build,
+}
+
+impl Simplifier {
+    /// Create a new simplifier.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn thing(&self) -> ClassExpression {
+        ClassExpression::Class(self.build.class(OWL::Thing.iri_str()))
+    }
+
+    fn nothing(&self) -> ClassExpression {
+        ClassExpression::Class(self.build.class(OWL::Nothing.iri_str()))
+    }
+}
+
+impl VisitMut for Simplifier {
+    fn visit_class_expression(&mut self, class_expression: &mut ClassExpression) {
+        use self::ClassExpression::*;
+        visit_mut::visit_class_expression(self, class_expression);
+        let simplified = match class_expression {
+            ObjectIntersectionOf(ces) => {
+                let mut operands = Vec::with_capacity(ces.len());
+                for ce in mem::take(ces) {
+                    match ce {
+                        ObjectIntersectionOf(nested) => operands.extend(nested),
+                        Class(c) if is_thing(&c.0) => (),
+                        other => operands.push(other),
+                    }
+                }
+                dedup(&mut operands);
+                match operands.len() {
+                    0 => self.thing(),
+                    1 => operands.pop().unwrap(),
+                    _ => ObjectIntersectionOf(operands),
+                }
+            }
+            ObjectUnionOf(ces) => {
+                let mut operands = Vec::with_capacity(ces.len());
+                for ce in mem::take(ces) {
+                    match ce {
+                        ObjectUnionOf(nested) => operands.extend(nested),
+                        Class(c) if is_nothing(&c.0) => (),
+                        other => operands.push(other),
+                    }
+                }
+                dedup(&mut operands);
+                match operands.len() {
+                    0 => self.nothing(),
+                    1 => operands.pop().unwrap(),
+                    _ => ObjectUnionOf(operands),
+                }
+            }
+            ObjectComplementOf(ce) => match ce.as_mut() {
+                ObjectComplementOf(inner) => mem::replace(inner.as_mut(), self.thing()),
+                _ => return,
+            },
+            ObjectMinCardinality { n: 0, .. } | DataMinCardinality { n: 0, .. } => self.thing(),
+            _ => return,
+        };
+        *class_expression = simplified;
+    }
+
+    fn visit_data_range(&mut self, data_range: &mut DataRange) {
+        use self::DataRange::*;
+        visit_mut::visit_data_range(self, data_range);
+        let simplified = match data_range {
+            DataIntersectionOf(drs) => {
+                let mut operands = Vec::with_capacity(drs.len());
+                for dr in mem::take(drs) {
+                    match dr {
+                        DataIntersectionOf(nested) => operands.extend(nested),
+                        other => operands.push(other),
+                    }
+                }
+                dedup(&mut operands);
+                match operands.len() {
+                    1 => operands.pop().unwrap(),
+                    _ => DataIntersectionOf(operands),
+                }
+            }
+            DataUnionOf(drs) => {
+                let mut operands = Vec::with_capacity(drs.len());
+                for dr in mem::take(drs) {
+                    match dr {
+                        DataUnionOf(nested) => operands.extend(nested),
+                        other => operands.push(other),
+                    }
+                }
+                dedup(&mut operands);
+                match operands.len() {
+                    1 => operands.pop().unwrap(),
+                    _ => DataUnionOf(operands),
+                }
+            }
+            DataComplementOf(dr) => match dr.as_mut() {
+                DataComplementOf(inner) => {
+                    mem::replace(inner.as_mut(), DataIntersectionOf(Vec::new()))
+                }
+                _ => return,
+            },
+            _ => return,
+        };
+        *data_range = simplified;
+    }
+}
+
+/// A trait for elements that can be simplified.
+pub trait Simplify: Clone {
+    /// Simplify the element in place.
+    fn simplify(&mut self);
+
+    /// Get a simplified copy of the element.
+    fn simplified(&self) -> Self {
+        let mut simplified = self.clone();
+        simplified.simplify();
+        simplified
+    }
+}
+
+macro_rules! impl_simplify {
+    ($($name:ident($type:ty),)*) => {
+        paste! {
+            $(
+                impl Simplify for $type {
+                    fn simplify(&mut self) {
+                        Simplifier::new().[<visit_ $name>](self);
+                    }
+                }
+            )*
+        }
+    }
+}
+
+impl_simplify! {
+    annotated_axiom(AnnotatedAxiom),
+    axiom(Axiom),
+    class_expression(ClassExpression),
+    data_range(DataRange),
+}
+
+/// Simplify every axiom of an ontology.
+pub fn simplify_ontology<O>(ontology: &mut O)
+where
+    O: MutableOntology,
+    for<'a> &'a O: IntoIterator<Item = &'a AnnotatedAxiom>,
+{
+    let mut simplifier = Simplifier::new();
+    let mut sink = AxiomSink::new();
+    for aa in ontology.into_iter() {
+        let mut simplified = aa.clone();
+        simplifier.visit_annotated_axiom(&mut simplified);
+        if &simplified != aa {
+            sink.replace(aa.clone(), simplified);
+        }
+    }
+    sink.apply(ontology);
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::fixtures::*;
+
+    fn thing() -> ClassExpression {
+        Build::new().class(OWL::Thing.iri_str()).into()
+    }
+
+    fn datatype(name: &str) -> DataRange {
+        DataRange::Datatype(Datatype(iri(name)))
+    }
+
+    #[test]
+    fn single_operand_collapse() {
+        let ce = ClassExpression::ObjectIntersectionOf(vec![class("A")]);
+        assert_eq!(ce.simplified(), class("A"));
+        let ce = ClassExpression::ObjectUnionOf(vec![class("A"), class("A")]);
+        assert_eq!(ce.simplified(), class("A"));
+        let dr = DataRange::DataUnionOf(vec![datatype("d")]);
+        assert_eq!(dr.simplified(), datatype("d"));
+        let dr = DataRange::DataIntersectionOf(vec![datatype("d"), datatype("d")]);
+        assert_eq!(dr.simplified(), datatype("d"));
+    }
+
+    #[test]
+    fn empty_operands() {
+        let ce = ClassExpression::ObjectIntersectionOf(vec![thing(), thing()]);
+        assert_eq!(ce.simplified(), thing());
+        let ce = ClassExpression::ObjectUnionOf(vec![nothing()]);
+        assert_eq!(ce.simplified(), nothing());
+    }
+
+    #[test]
+    fn flatten_keeps_operand_order() {
+        let ce = ClassExpression::ObjectUnionOf(vec![
+            class("C"),
+            ClassExpression::ObjectUnionOf(vec![class("A"), nothing(), class("C")]),
+            class("B"),
+        ]);
+        assert_eq!(
+            ce.simplified(),
+            ClassExpression::ObjectUnionOf(vec![class("C"), class("A"), class("B")])
+        );
+    }
+
+    #[test]
+    fn intersection_and_union_are_not_mixed() {
+        let union = ClassExpression::ObjectUnionOf(vec![class("A"), class("B")]);
+        let ce = ClassExpression::ObjectIntersectionOf(vec![union.clone(), class("C")]);
+        assert_eq!(ce.simplified(), ce);
+        assert_eq!(union.simplified(), union);
+    }
+
+    #[test]
+    fn double_complement() {
+        let ce = ClassExpression::ObjectComplementOf(Box::new(
+            ClassExpression::ObjectComplementOf(Box::new(class("A"))),
+        ));
+        assert_eq!(ce.simplified(), class("A"));
+        let single = ClassExpression::ObjectComplementOf(Box::new(class("A")));
+        assert_eq!(single.simplified(), single);
+        let dr = DataRange::DataComplementOf(Box::new(DataRange::DataComplementOf(Box::new(
+            datatype("d"),
+        ))));
+        assert_eq!(dr.simplified(), datatype("d"));
+    }
+
+    #[test]
+    fn min_cardinality_zero() {
+        let ce = ClassExpression::ObjectMinCardinality {
+            n: 0,
+            ope: op("r"),
+            bce: Box::new(class("A")),
+        };
+        assert_eq!(ce.simplified(), thing());
+        let ce = ClassExpression::ObjectMinCardinality {
+            n: 1,
+            ope: op("r"),
+            bce: Box::new(class("A")),
+        };
+        assert_eq!(ce.simplified(), ce);
+    }
+
+    #[test]
+    fn bottom_up() {
+        // the inner double complement simplifies to `owl:Thing`, which in
+        // turn leaves the intersection with a single operand
+        let ce = ClassExpression::ObjectIntersectionOf(vec![
+            some(
+                "r",
+                ClassExpression::ObjectIntersectionOf(vec![class("A"), class("A")]),
+            ),
+            ClassExpression::ObjectComplementOf(Box::new(ClassExpression::ObjectComplementOf(
+                Box::new(thing()),
+            ))),
+        ]);
+        assert_eq!(ce.simplified(), some("r", class("A")));
+    }
+
+    #[test]
+    fn simplify_axioms() {
+        let mut o = ontology(vec![
+            sub_class_of(
+                class("A"),
+                ClassExpression::ObjectIntersectionOf(vec![class("B"), thing()]),
+            ),
+            sub_class_of(class("A"), class("C")),
+        ]);
+        simplify_ontology(&mut o);
+        assert_eq!(
+            o,
+            ontology(vec![
+                sub_class_of(class("A"), class("B")),
+                sub_class_of(class("A"), class("C")),
+            ])
+        );
+    }
+}
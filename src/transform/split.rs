@@ -0,0 +1,307 @@
+//! Splitting of compound axioms into simpler axioms.
+//!
+//! The [`Splitter`] replaces axioms by a set of simpler axioms with the
+//! same semantics:
+//!
+//! - `SubClassOf(CE ObjectIntersectionOf(CE1 ... CEn))` is split into
+//!   `SubClassOf(CE CEi)`, and `SubClassOf(ObjectUnionOf(CE1 ... CEn) CE)`
+//!   into `SubClassOf(CEi CE)`;
+//! - `ClassAssertion(ObjectIntersectionOf(CE1 ... CEn) a)` is split into
+//!   `ClassAssertion(CEi a)`;
+//! - n-ary axioms (`EquivalentClasses`, `DisjointClasses`,
+//!   `EquivalentObjectProperties`, `DisjointObjectProperties`,
+//!   `EquivalentDataProperties`, `DisjointDataProperties`,
+//!   `SameIndividual` and `DifferentIndividuals`) are split into one axiom
+//!   for each pair of operands.
+//!
+//! The annotations of a split axiom are copied to each of its parts.
+//!
+//! # Example
+//!
+//! ```rust
+//! # extern crate horned_owl;
+//! # extern crate horned_visit;
+//! use horned_owl::model::*;
+//! use horned_visit::transform::split::Splitter;
+//!
+//! let b = Build::new();
+//! let axiom = AnnotatedAxiom::from(SubClassOf {
+//!     sub: b.class("http://example.com/A").into(),
+//!     sup: ClassExpression::ObjectIntersectionOf(vec![
+//!         b.class("http://example.com/B").into(),
+//!         b.class("http://example.com/C").into(),
+//!     ]),
+//! });
+//!
+//! assert_eq!(Splitter::new().split(&axiom).len(), 2);
+//! ```
+
+use horned_owl::model::*;
+
+use crate::sink::edit;
+use crate::sink::AxiomSink;
+use crate::sink::Emit;
+use crate::transform::pairs;
+use crate::Visit;
+
+/// Get the operands of a class expression, flattening nested intersections.
+fn conjuncts(ce: &ClassExpression) -> Vec<&ClassExpression> {
+    match ce {
+        ClassExpression::ObjectIntersectionOf(ces) => ces.iter().flat_map(conjuncts).collect(),
+        other => vec![other],
+    }
+}
+
+/// Get the operands of a class expression, flattening nested unions.
+fn disjuncts(ce: &ClassExpression) -> Vec<&ClassExpression> {
+    match ce {
+        ClassExpression::ObjectUnionOf(ces) => ces.iter().flat_map(disjuncts).collect(),
+        other => vec![other],
+    }
+}
+
+/// A visitor replacing the visited axioms with their parts.
+#[derive(Debug, Default)]
+pub struct Splitter {
+    sink: AxiomSink,
+}
+
+impl Splitter {
+    /// Create a new splitter with an empty sink.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the parts of an axiom.
+    ///
+    /// Axioms that cannot be split are returned unchanged.
+    pub fn split(&self, axiom: &AnnotatedAxiom) -> Vec<AnnotatedAxiom> {
+        let parts: Vec<Axiom> = match &axiom.axiom {
+            Axiom::SubClassOf(sco) => {
+                let mut parts = Vec::new();
+                for sub in disjuncts(&sco.sub) {
+                    for sup in conjuncts(&sco.sup) {
+                        parts.push(Axiom::from(SubClassOf {
+                            sub: sub.clone(),
+                            sup: sup.clone(),
+                        }));
+                    }
+                }
+                parts
+            }
+            Axiom::ClassAssertion(ca) => conjuncts(&ca.ce)
+                .into_iter()
+                .map(|ce| {
+                    Axiom::from(ClassAssertion {
+                        ce: ce.clone(),
+                        i: ca.i.clone(),
+                    })
+                })
+                .collect(),
+            Axiom::EquivalentClasses(ec) if ec.0.len() > 2 => pairs(&ec.0)
+                .into_iter()
+                .map(|p| Axiom::from(EquivalentClasses(p)))
+                .collect(),
+            Axiom::DisjointClasses(dc) if dc.0.len() > 2 => pairs(&dc.0)
+                .into_iter()
+                .map(|p| Axiom::from(DisjointClasses(p)))
+                .collect(),
+            Axiom::EquivalentObjectProperties(eop) if eop.0.len() > 2 => pairs(&eop.0)
+                .into_iter()
+                .map(|p| Axiom::from(EquivalentObjectProperties(p)))
+                .collect(),
+            Axiom::DisjointObjectProperties(dop) if dop.0.len() > 2 => pairs(&dop.0)
+                .into_iter()
+                .map(|p| Axiom::from(DisjointObjectProperties(p)))
+                .collect(),
+            Axiom::EquivalentDataProperties(edp) if edp.0.len() > 2 => pairs(&edp.0)
+                .into_iter()
+                .map(|p| Axiom::from(EquivalentDataProperties(p)))
+                .collect(),
+            Axiom::DisjointDataProperties(ddp) if ddp.0.len() > 2 => pairs(&ddp.0)
+                .into_iter()
+                .map(|p| Axiom::from(DisjointDataProperties(p)))
+                .collect(),
+            Axiom::SameIndividual(si) if si.0.len() > 2 => pairs(&si.0)
+                .into_iter()
+                .map(|p| Axiom::from(SameIndividual(p)))
+                .collect(),
+            Axiom::DifferentIndividuals(di) if di.0.len() > 2 => pairs(&di.0)
+                .into_iter()
+                .map(|p| Axiom::from(DifferentIndividuals(p)))
+                .collect(),
+            other => vec![other.clone()],
+        };
+        parts
+            .into_iter()
+            .map(|part| AnnotatedAxiom::new(part, axiom.ann.clone()))
+            .collect()
+    }
+}
+
+impl Emit for Splitter {
+    fn sink(&mut self) -> &mut AxiomSink {
+        &mut self.sink
+    }
+}
+
+impl<'ast> Visit<'ast> for Splitter {
+    fn visit_annotated_axiom(&mut self, annotated_axiom: &'ast AnnotatedAxiom) {
+        let parts = self.split(annotated_axiom);
+        if parts.len() != 1 || &parts[0] != annotated_axiom {
+            self.sink.remove(annotated_axiom.clone());
+            parts.into_iter().for_each(|aa| self.sink.insert(aa));
+        }
+    }
+}
+
+/// Split every axiom of an ontology into its parts.
+pub fn split_ontology<O>(ontology: &mut O)
+where
+    O: MutableOntology,
+    for<'a> &'a O: IntoIterator<Item = &'a AnnotatedAxiom>,
+{
+    edit(ontology, &mut Splitter::new());
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::collections::BTreeSet;
+
+    use super::*;
+    use crate::fixtures::*;
+
+    fn split(axiom: Axiom) -> Vec<Axiom> {
+        Splitter::new()
+            .split(&axiom.into())
+            .into_iter()
+            .map(|aa| aa.axiom)
+            .collect()
+    }
+
+    #[test]
+    fn intersection_superclass() {
+        let sup = ClassExpression::ObjectIntersectionOf(vec![
+            class("B"),
+            ClassExpression::ObjectIntersectionOf(vec![class("C"), class("D")]),
+        ]);
+        assert_eq!(
+            split(sub_class_of(class("A"), sup)),
+            vec![
+                sub_class_of(class("A"), class("B")),
+                sub_class_of(class("A"), class("C")),
+                sub_class_of(class("A"), class("D")),
+            ]
+        );
+    }
+
+    #[test]
+    fn union_subclass() {
+        let sub = ClassExpression::ObjectUnionOf(vec![class("A"), class("B")]);
+        let sup = ClassExpression::ObjectIntersectionOf(vec![class("C"), class("D")]);
+        assert_eq!(
+            split(sub_class_of(sub, sup)),
+            vec![
+                sub_class_of(class("A"), class("C")),
+                sub_class_of(class("A"), class("D")),
+                sub_class_of(class("B"), class("C")),
+                sub_class_of(class("B"), class("D")),
+            ]
+        );
+    }
+
+    #[test]
+    fn unsplittable_class_expressions() {
+        // an intersection on the left or a union on the right cannot be split
+        let axiom = sub_class_of(
+            ClassExpression::ObjectIntersectionOf(vec![class("A"), class("B")]),
+            ClassExpression::ObjectUnionOf(vec![class("C"), class("D")]),
+        );
+        assert_eq!(split(axiom.clone()), vec![axiom]);
+        let nested = sub_class_of(
+            class("A"),
+            some(
+                "r",
+                ClassExpression::ObjectIntersectionOf(vec![class("B"), class("C")]),
+            ),
+        );
+        assert_eq!(split(nested.clone()), vec![nested]);
+    }
+
+    #[test]
+    fn class_assertion() {
+        let axiom = ClassAssertion {
+            ce: ClassExpression::ObjectIntersectionOf(vec![class("A"), class("B")]),
+            i: individual("a"),
+        };
+        assert_eq!(
+            split(axiom.into()),
+            vec![
+                ClassAssertion {
+                    ce: class("A"),
+                    i: individual("a"),
+                }
+                .into(),
+                ClassAssertion {
+                    ce: class("B"),
+                    i: individual("a"),
+                }
+                .into(),
+            ]
+        );
+    }
+
+    #[test]
+    fn nary_pairs() {
+        let axiom = SameIndividual(vec![individual("a"), individual("b"), individual("c")]);
+        assert_eq!(
+            split(axiom.into()),
+            vec![
+                SameIndividual(vec![individual("a"), individual("b")]).into(),
+                SameIndividual(vec![individual("a"), individual("c")]).into(),
+                SameIndividual(vec![individual("b"), individual("c")]).into(),
+            ]
+        );
+        let axiom = EquivalentObjectProperties(vec![op("p"), op("q"), inverse_of("r")]);
+        assert_eq!(split(axiom.into()).len(), 3);
+        let pair = Axiom::from(EquivalentClasses(vec![class("A"), class("B")]));
+        assert_eq!(split(pair.clone()), vec![pair]);
+    }
+
+    #[test]
+    fn annotations_are_copied() {
+        let annotations = vec![Annotation {
+            ap: AnnotationProperty(iri("p")),
+            av: AnnotationValue::IRI(iri("v")),
+        }]
+        .into_iter()
+        .collect::<BTreeSet<_>>();
+        let axiom = AnnotatedAxiom::new(
+            DisjointClasses(vec![class("A"), class("B"), class("C")]),
+            annotations.clone(),
+        );
+        let parts = Splitter::new().split(&axiom);
+        assert_eq!(parts.len(), 3);
+        assert!(parts.iter().all(|aa| aa.ann == annotations));
+    }
+
+    #[test]
+    fn unchanged_axioms_are_kept() {
+        let mut o = ontology(vec![
+            sub_class_of(class("A"), class("B")),
+            sub_class_of(
+                class("A"),
+                ClassExpression::ObjectIntersectionOf(vec![class("B"), class("C")]),
+            ),
+        ]);
+        split_ontology(&mut o);
+        assert_eq!(
+            o,
+            ontology(vec![
+                sub_class_of(class("A"), class("B")),
+                sub_class_of(class("A"), class("C")),
+            ])
+        );
+    }
+}